@@ -16,6 +16,8 @@ use crate::section;
 
 pub struct AccordionStory {
     open_ixs: Vec<usize>,
+    nested_open_ixs: Vec<usize>,
+    inner_open_ixs: Vec<usize>,
     size: Size,
     bordered: bool,
     disabled: bool,
@@ -47,6 +49,8 @@ impl AccordionStory {
         Self {
             bordered: false,
             open_ixs: vec![0, 1, 2],
+            nested_open_ixs: vec![0],
+            inner_open_ixs: vec![],
             size: Size::default(),
             disabled: false,
             multiple: true,
@@ -205,5 +209,53 @@ impl Render for AccordionStory {
                         })),
                 ),
             )
+            .child(
+                section("Nested").max_w_md().child(
+                    Accordion::new("nested")
+                        .bordered(self.bordered)
+                        .with_size(self.size)
+                        .multiple(true)
+                        .item(|this| {
+                            this.open(self.nested_open_ixs.contains(&0))
+                                .title("Outer section")
+                                .child(
+                                    Accordion::new("inner")
+                                        .bordered(self.bordered)
+                                        .with_size(self.size)
+                                        .multiple(true)
+                                        .item(|this| {
+                                            this.open(self.inner_open_ixs.contains(&0))
+                                                .title("Inner section one")
+                                                .child(
+                                                    "Expanding this after the outer section \
+                                                    settled grows the outer section with it.",
+                                                )
+                                        })
+                                        .item(|this| {
+                                            this.open(self.inner_open_ixs.contains(&1))
+                                                .title("Inner section two")
+                                                .child(v_flex().gap_2().children((1..=12).map(
+                                                    |ix| format!("Nested content line {}", ix),
+                                                )))
+                                        })
+                                        .on_toggle_click(cx.listener(
+                                            |this, open_ixs: &[usize], _, cx| {
+                                                this.inner_open_ixs = open_ixs.to_vec();
+                                                cx.notify();
+                                            },
+                                        )),
+                                )
+                        })
+                        .item(|this| {
+                            this.open(self.nested_open_ixs.contains(&1))
+                                .title("Another outer section")
+                                .child("Sibling content below the nested accordion.")
+                        })
+                        .on_toggle_click(cx.listener(|this, open_ixs: &[usize], _, cx| {
+                            this.nested_open_ixs = open_ixs.to_vec();
+                            cx.notify();
+                        })),
+                ),
+            )
     }
 }
@@ -104,7 +104,7 @@ impl CommandPaletteStory {
         let handle = CommandPalette::open(window, cx, provider);
 
         cx.subscribe(&handle.state(), move |this, _state, event, cx| {
-            if let CommandPaletteEvent::Selected { item } = event {
+            if let CommandPaletteEvent::Selected { item, .. } = event {
                 this.last_selected = Some(item.title.clone());
                 cx.notify();
             }
@@ -117,7 +117,7 @@ impl CommandPaletteStory {
         let handle = CommandPalette::open(window, cx, provider);
 
         cx.subscribe(&handle.state(), move |this, _state, event, cx| {
-            if let CommandPaletteEvent::Selected { item } = event {
+            if let CommandPaletteEvent::Selected { item, .. } = event {
                 this.last_selected = Some(item.title.clone());
                 cx.notify();
             }
@@ -153,7 +153,7 @@ impl CommandPaletteStory {
         let handle = CommandPalette::open_with_config(window, cx, provider, custom_config);
 
         cx.subscribe(&handle.state(), move |this, _state, event, cx| {
-            if let CommandPaletteEvent::Selected { item } = event {
+            if let CommandPaletteEvent::Selected { item, .. } = event {
                 this.last_selected = Some(item.title.clone());
                 cx.notify();
             }
@@ -14,7 +14,10 @@ use gpui_component::{
     scroll::ScrollbarShow,
 };
 
-use crate::{SelectFont, SelectRadius, SelectScrollbarShow, ToggleListActiveHighlight, app_menus};
+use crate::{
+    SelectFont, SelectRadius, SelectScrollbarShow, ToggleListActiveHighlight, ToggleShadows,
+    app_menus,
+};
 
 pub struct AppTitleBar {
     app_menu_bar: Entity<AppMenuBar>,
@@ -147,6 +150,17 @@ impl FontSizeSelector {
         theme.list.active_highlight = !theme.list.active_highlight;
         window.refresh();
     }
+
+    fn on_toggle_shadows(
+        &mut self,
+        _: &ToggleShadows,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let theme = Theme::global_mut(cx);
+        theme.shadow = !theme.shadow;
+        window.refresh();
+    }
 }
 
 impl Render for FontSizeSelector {
@@ -163,6 +177,7 @@ impl Render for FontSizeSelector {
             .on_action(cx.listener(Self::on_select_radius))
             .on_action(cx.listener(Self::on_select_scrollbar_show))
             .on_action(cx.listener(Self::on_toggle_list_active_highlight))
+            .on_action(cx.listener(Self::on_toggle_shadows))
             .child(
                 Button::new("btn")
                     .small()
@@ -213,6 +228,7 @@ impl Render for FontSizeSelector {
                                 cx.theme().list.active_highlight,
                                 Box::new(ToggleListActiveHighlight),
                             )
+                            .menu_with_check("Shadows", cx.theme().shadow, Box::new(ToggleShadows))
                     })
                     .anchor(Corner::TopRight),
             )
@@ -2,22 +2,24 @@ use std::{cell::RefCell, collections::HashSet, rc::Rc, sync::Arc, time::Duration
 
 use gpui::{
     AnimationExt as _, AnyElement, App, ElementId, InteractiveElement as _, IntoElement,
-    ParentElement, RenderOnce, SharedString, StatefulInteractiveElement as _, Styled, Window, div,
-    percentage, prelude::FluentBuilder as _, px, rems,
+    MouseButton, ParentElement, RenderOnce, SharedString, StatefulInteractiveElement as _, Styled,
+    Window, div, percentage, prelude::FluentBuilder as _, px,
 };
 
 use crate::{
     ActiveTheme as _, Icon, IconName, Sizable, Size,
     animation::{
-        PresenceOptions, PresencePhase, SpringPreset, keyed_presence, point_to_point_animation,
-        spring_preset_animation, spring_preset_duration_ms,
+        ActiveMotion, PresenceOptions, PresencePhase, PresenceTransition, SpringPreset,
+        keyed_presence, point_to_point_animation, spring_preset_animation,
+        spring_preset_duration_ms,
     },
-    global_state::GlobalState,
     h_flex, v_flex,
 };
 
 /// Generous max for animated height reveal. Content fully visible
 /// well before delta=1 due to decelerating easing.
+///
+/// The cap only applies while a transition is running; settled items are unbounded.
 const ACCORDION_CONTENT_MAX_H: f32 = 1500.0;
 
 /// Shape height progress so sibling reflow lasts longer when max-height cap is large.
@@ -35,6 +37,11 @@ pub struct Accordion {
     disabled: bool,
     children: Vec<AccordionItem>,
     on_toggle_click: Option<Arc<dyn Fn(&[usize], &mut Window, &mut App) + Send + Sync>>,
+    default_open_all: bool,
+    open_indices: Option<Vec<usize>>,
+    on_change: Option<Rc<dyn Fn(&[usize], &mut Window, &mut App)>>,
+    on_item_open: Option<Rc<dyn Fn(usize, &mut Window, &mut App)>>,
+    on_item_close: Option<Rc<dyn Fn(usize, &mut Window, &mut App)>>,
 }
 
 impl Accordion {
@@ -48,6 +55,11 @@ impl Accordion {
             children: Vec::new(),
             disabled: false,
             on_toggle_click: None,
+            default_open_all: false,
+            open_indices: None,
+            on_change: None,
+            on_item_open: None,
+            on_item_close: None,
         }
     }
 
@@ -69,6 +81,58 @@ impl Accordion {
         self
     }
 
+    /// Set whether every item starts open, instead of setting [`AccordionItem::open`]
+    /// on each, default: false
+    ///
+    /// Only seeds the open items on first render, toggling an item afterwards
+    /// opens or closes it as usual. Ignored when the open items are controlled with [`Accordion::open_indices`].
+    pub fn default_open_all(mut self, default_open_all: bool) -> Self {
+        self.default_open_all = default_open_all;
+        self
+    }
+
+    /// Control the open items by their indices, overriding [`AccordionItem::open`].
+    ///
+    /// Toggling an item doesn't change anything by itself, it calls
+    /// [`Accordion::on_change`] with the new indices for the parent to apply.
+    pub fn open_indices(mut self, open_indices: impl Into<Vec<usize>>) -> Self {
+        self.open_indices = Some(open_indices.into());
+        self
+    }
+
+    /// Sets a callback with the new open indices when an item is toggled in a
+    /// controlled Accordion, see [`Accordion::open_indices`].
+    pub fn on_change(
+        mut self,
+        on_change: impl Fn(&[usize], &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_change = Some(Rc::new(on_change));
+        self
+    }
+
+    /// Sets a callback fired with the item index when an item starts to open, or
+    /// is open on first render, to build expensive content lazily.
+    ///
+    /// Fires once per opening, toggling again before the item has fully closed
+    /// doesn't fire it twice.
+    pub fn on_item_open(
+        mut self,
+        on_item_open: impl Fn(usize, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_item_open = Some(Rc::new(on_item_open));
+        self
+    }
+
+    /// Sets a callback fired with the item index once an item has fully closed,
+    /// after its exit animation, to tear down content built in [`Accordion::on_item_open`].
+    pub fn on_item_close(
+        mut self,
+        on_item_close: impl Fn(usize, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_item_close = Some(Rc::new(on_item_close));
+        self
+    }
+
     /// Adds an AccordionItem to the Accordion.
     pub fn item<F>(mut self, child: F) -> Self
     where
@@ -99,10 +163,23 @@ impl Sizable for Accordion {
 }
 
 impl RenderOnce for Accordion {
-    fn render(self, _window: &mut Window, _cx: &mut App) -> impl IntoElement {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
         let open_ixs = Rc::new(RefCell::new(HashSet::new()));
         let is_multiple = self.multiple;
         let accordion_id_prefix = SharedString::from(format!("{}", self.id));
+        let default_open_all = self.default_open_all;
+        let open_indices = self.open_indices;
+        let on_change = self.on_change;
+        let on_item_open = self.on_item_open;
+        let on_item_close = self.on_item_close;
+        let item_count = self.children.len();
+        let seeded_open = (default_open_all && open_indices.is_none()).then(|| {
+            window.use_keyed_state(
+                SharedString::from(format!("{}-open", accordion_id_prefix)),
+                cx,
+                |_, _| (0..item_count).collect::<HashSet<usize>>(),
+            )
+        });
 
         v_flex()
             .id(self.id)
@@ -113,11 +190,18 @@ impl RenderOnce for Accordion {
                     .into_iter()
                     .enumerate()
                     .map(|(ix, accordion)| {
-                        if accordion.open {
+                        let open = match (&open_indices, &seeded_open) {
+                            (Some(open_indices), _) => open_indices.contains(&ix),
+                            (None, Some(seeded_open)) => seeded_open.read(cx).contains(&ix),
+                            (None, None) => accordion.open,
+                        };
+                        if open {
                             open_ixs.borrow_mut().insert(ix);
                         }
 
                         accordion
+                            .open(open)
+                            .lifecycle(on_item_open.clone(), on_item_close.clone())
                             .index(ix)
                             .key_prefix(SharedString::from(format!(
                                 "{}-{}",
@@ -128,15 +212,37 @@ impl RenderOnce for Accordion {
                             .disabled(self.disabled)
                             .on_toggle_click({
                                 let open_ixs = Rc::clone(&open_ixs);
-                                move |open, _, _| {
-                                    let mut open_ixs = open_ixs.borrow_mut();
-                                    if *open {
-                                        if !is_multiple {
-                                            open_ixs.clear();
+                                let on_change = on_change.clone();
+                                let controlled = open_indices.is_some();
+                                let seeded_open = seeded_open.clone();
+                                move |open, window, cx| {
+                                    {
+                                        let mut open_ixs = open_ixs.borrow_mut();
+                                        if *open {
+                                            if !is_multiple {
+                                                open_ixs.clear();
+                                            }
+                                            open_ixs.insert(ix);
+                                        } else {
+                                            open_ixs.remove(&ix);
+                                        }
+                                    }
+
+                                    if let Some(seeded_open) = &seeded_open {
+                                        let open_ixs = open_ixs.borrow().clone();
+                                        seeded_open.update(cx, |seeded_open, cx| {
+                                            *seeded_open = open_ixs;
+                                            cx.notify();
+                                        });
+                                    }
+
+                                    if controlled {
+                                        if let Some(on_change) = &on_change {
+                                            let mut open_ixs: Vec<usize> =
+                                                open_ixs.borrow().iter().copied().collect();
+                                            open_ixs.sort_unstable();
+                                            on_change(&open_ixs, window, cx);
                                         }
-                                        open_ixs.insert(ix);
-                                    } else {
-                                        open_ixs.remove(&ix);
                                     }
                                 }
                             })
@@ -163,12 +269,16 @@ pub struct AccordionItem {
     key_prefix: SharedString,
     icon: Option<Icon>,
     title: AnyElement,
+    header_actions: Option<AnyElement>,
     children: Vec<AnyElement>,
     open: bool,
     size: Size,
     bordered: bool,
     disabled: bool,
     on_toggle_click: Option<Arc<dyn Fn(&bool, &mut Window, &mut App)>>,
+    on_toggle: Option<Rc<dyn Fn(bool, &mut Window, &mut App)>>,
+    on_open: Option<Rc<dyn Fn(usize, &mut Window, &mut App)>>,
+    on_close: Option<Rc<dyn Fn(usize, &mut Window, &mut App)>>,
 }
 
 impl AccordionItem {
@@ -179,10 +289,14 @@ impl AccordionItem {
             key_prefix: "accordion".into(),
             icon: None,
             title: SharedString::default().into_any_element(),
+            header_actions: None,
             children: Vec::new(),
             open: false,
             disabled: false,
             on_toggle_click: None,
+            on_toggle: None,
+            on_open: None,
+            on_close: None,
             size: Size::default(),
             bordered: true,
         }
@@ -200,6 +314,15 @@ impl AccordionItem {
         self
     }
 
+    /// Set the actions shown on the right of the header, before the chevron, e.g. an
+    /// add button or an overflow menu.
+    ///
+    /// Clicking the actions doesn't toggle the item.
+    pub fn header_actions(mut self, actions: impl IntoElement) -> Self {
+        self.header_actions = Some(actions.into_any_element());
+        self
+    }
+
     pub fn bordered(mut self, bordered: bool) -> Self {
         self.bordered = bordered;
         self
@@ -215,6 +338,16 @@ impl AccordionItem {
         self
     }
 
+    /// Sets a callback fired when this item is clicked to open or close.
+    ///
+    /// The first argument is the new open state of this item. It fires before the
+    /// Accordion's `on_toggle_click`, and not for items closed because another one
+    /// was opened in a non-`multiple` Accordion.
+    pub fn on_toggle(mut self, on_toggle: impl Fn(bool, &mut Window, &mut App) + 'static) -> Self {
+        self.on_toggle = Some(Rc::new(on_toggle));
+        self
+    }
+
     fn index(mut self, index: usize) -> Self {
         self.index = index;
         self
@@ -225,6 +358,49 @@ impl AccordionItem {
         self
     }
 
+    fn lifecycle(
+        mut self,
+        on_open: Option<Rc<dyn Fn(usize, &mut Window, &mut App)>>,
+        on_close: Option<Rc<dyn Fn(usize, &mut Window, &mut App)>>,
+    ) -> Self {
+        self.on_open = on_open;
+        self.on_close = on_close;
+        self
+    }
+
+    /// Fire `on_open` when the item starts to show, and `on_close` once it has exited.
+    ///
+    /// The presence only reaches `Exited` from its latest generation, so a quick
+    /// close and reopen stays a single opening.
+    fn notify_lifecycle(&self, presence: PresenceTransition, window: &mut Window, cx: &mut App) {
+        if self.on_open.is_none() && self.on_close.is_none() {
+            return;
+        }
+
+        let opened = window.use_keyed_state(
+            SharedString::from(format!("accordion-lifecycle-{}", self.key_prefix)),
+            cx,
+            |_, _| false,
+        );
+        let was_opened = *opened.read(cx);
+        let is_opened = presence.should_render();
+        if was_opened == is_opened {
+            return;
+        }
+
+        opened.update(cx, |opened, _| *opened = is_opened);
+        let callback = if is_opened {
+            &self.on_open
+        } else {
+            &self.on_close
+        };
+        // Deferred, the view owning the accordion is being rendered
+        if let Some(callback) = callback.clone() {
+            let index = self.index;
+            window.defer(cx, move |window, cx| callback(index, window, cx));
+        }
+    }
+
     fn on_toggle_click(
         mut self,
         on_toggle_click: impl Fn(&bool, &mut Window, &mut App) + 'static,
@@ -249,7 +425,7 @@ impl Sizable for AccordionItem {
 
 impl RenderOnce for AccordionItem {
     fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
-        let reduced_motion = GlobalState::global(cx).reduced_motion();
+        let reduced_motion = !cx.should_animate();
         let motion = cx.theme().motion.clone();
         let spring_preset = SpringPreset::Mild;
         let layout_anim = point_to_point_animation(&motion, reduced_motion);
@@ -277,12 +453,9 @@ impl RenderOnce for AccordionItem {
             cx,
         );
         let expanded_visible = presence.should_render();
+        self.notify_lifecycle(presence, window, cx);
 
-        let text_size = match self.size {
-            Size::XSmall => rems(0.875),
-            Size::Small => rems(0.875),
-            _ => rems(1.0),
-        };
+        let metrics = cx.theme().size_metrics(self.size);
         let is_open = self.open;
         let chevron = {
             let base = Icon::new(IconName::ChevronDown)
@@ -333,18 +506,14 @@ impl RenderOnce for AccordionItem {
                         .rounded(cx.theme().radius)
                         .border_color(cx.theme().border)
                 })
-                .text_size(text_size)
+                .text_size(metrics.text_size)
                 .child(
                     h_flex()
                         .id(self.index)
                         .justify_between()
                         .gap_3()
-                        .map(|this| match self.size {
-                            Size::XSmall => this.py_0().px_1p5(),
-                            Size::Small => this.py_0p5().px_2(),
-                            Size::Large => this.py_1p5().px_4(),
-                            _ => this.py_1().px_3(),
-                        })
+                        .py(metrics.padding_y)
+                        .px(metrics.padding_x)
                         .when(expanded_visible, |this| {
                             this.when(self.bordered, |this| {
                                 this.text_color(cx.theme().foreground)
@@ -358,46 +527,64 @@ impl RenderOnce for AccordionItem {
                         .child(
                             h_flex()
                                 .items_center()
-                                .map(|this| match self.size {
-                                    Size::XSmall => this.gap_1(),
-                                    Size::Small => this.gap_1(),
-                                    _ => this.gap_2(),
-                                })
+                                .gap(metrics.gap)
                                 .when_some(self.icon, |this, icon| {
                                     this.child(
                                         icon.with_size(self.size)
                                             .text_color(cx.theme().muted_foreground),
                                     )
                                 })
+                                .when(self.header_actions.is_some(), |this| this.flex_1())
                                 .child(self.title),
                         )
+                        .when_some(self.header_actions, |this, actions| {
+                            this.child(
+                                h_flex()
+                                    .id("header-actions")
+                                    .flex_shrink_0()
+                                    .gap(metrics.gap)
+                                    // Let the actions use the header padding, so a control
+                                    // taller than the title doesn't grow the header.
+                                    .my(-metrics.padding_y)
+                                    .on_mouse_down(MouseButton::Left, |_, _, cx| {
+                                        cx.stop_propagation();
+                                    })
+                                    .on_click(|_, _, cx| cx.stop_propagation())
+                                    .child(actions),
+                            )
+                        })
                         .when(!self.disabled, |this| {
                             this.hover(|this| this.bg(cx.theme().accordion_hover))
                                 .child(chevron)
-                                .when_some(self.on_toggle_click, |this, on_toggle_click| {
-                                    this.on_click({
-                                        let open = self.open;
-                                        move |_, window, cx| {
-                                            on_toggle_click(&!open, window, cx);
-                                        }
-                                    })
-                                })
+                                .when(
+                                    self.on_toggle_click.is_some() || self.on_toggle.is_some(),
+                                    |this| {
+                                        let on_toggle_click = self.on_toggle_click;
+                                        let on_toggle = self.on_toggle;
+                                        let open = !self.open;
+                                        this.on_click(move |_, window, cx| {
+                                            if let Some(on_toggle) = &on_toggle {
+                                                on_toggle(open, window, cx);
+                                            }
+                                            if let Some(on_toggle_click) = &on_toggle_click {
+                                                on_toggle_click(&open, window, cx);
+                                            }
+                                        })
+                                    },
+                                )
                         }),
                 )
                 .when(expanded_visible, |this| {
                     this.child(
                         div()
+                            // Namespace keyed state so nested accordions with the same id
+                            // in sibling items don't share presence/animation state.
+                            .id(SharedString::from(format!(
+                                "accordion-content-{}",
+                                self.key_prefix
+                            )))
                             .overflow_hidden()
-                            .child(
-                                div()
-                                    .map(|this| match self.size {
-                                        Size::XSmall => this.p_1p5(),
-                                        Size::Small => this.p_2(),
-                                        Size::Large => this.p_4(),
-                                        _ => this.p_3(),
-                                    })
-                                    .children(self.children),
-                            )
+                            .child(div().p(metrics.padding_x).children(self.children))
                             .map(|el| {
                                 if !presence.transition_active() {
                                     return el.into_any_element();
@@ -423,6 +610,11 @@ impl RenderOnce for AccordionItem {
                                     );
                                     el.with_animation(animation_id, anim, move |el, delta| {
                                         let progress = presence.progress(delta).clamp(0.0, 1.0);
+                                        // Drop the cap once fully revealed, so nested content
+                                        // can keep growing while the presence settles.
+                                        if progress >= 1.0 {
+                                            return el.opacity(1.0);
+                                        }
                                         let height_progress = accordion_height_progress(progress);
                                         el.max_h(px(ACCORDION_CONTENT_MAX_H * height_progress))
                                             .opacity(progress)
@@ -1,7 +1,24 @@
 use gpui::{Animation, App, SharedString, Window, spring};
+use std::cell::RefCell;
+use std::collections::HashMap;
 use std::time::Duration;
 
-use crate::ThemeMotion;
+use crate::{ThemeMotion, global_state::GlobalState};
+
+/// Extension trait with the single check of whether animations should play.
+pub trait ActiveMotion {
+    /// Returns false when motion is reduced (by a `ReducedMotionScope` or the base
+    /// setting) or power saving is on, in which case components should jump to
+    /// their end state instead of animating.
+    fn should_animate(&self) -> bool;
+}
+
+impl ActiveMotion for App {
+    fn should_animate(&self) -> bool {
+        let state = GlobalState::global(self);
+        !state.reduced_motion() && !state.power_saving()
+    }
+}
 
 /// A cubic bezier function like CSS `cubic-bezier`.
 ///
@@ -49,9 +66,31 @@ pub fn cubic_bezier_unbounded(x1: f32, y1: f32, x2: f32, y2: f32) -> impl Fn(f32
     }
 }
 
-/// Parse a CSS cubic-bezier string into (x1, y1, x2, y2).
+/// Named easing presets accepted by [`parse_cubic_bezier_easing`], as (x1, y1, x2, y2).
+///
+/// The `ease*` curves match their CSS keywords, `spring` overshoots slightly
+/// before settling.
+const NAMED_EASINGS: &[(&str, (f32, f32, f32, f32))] = &[
+    ("ease", (0.25, 0.1, 0.25, 1.0)),
+    ("ease-in", (0.42, 0.0, 1.0, 1.0)),
+    ("ease-out", (0.0, 0.0, 0.58, 1.0)),
+    ("ease-in-out", (0.42, 0.0, 0.58, 1.0)),
+    ("spring", (0.34, 1.56, 0.64, 1.0)),
+];
+
+/// Parse a CSS cubic-bezier string or a named preset into (x1, y1, x2, y2).
+///
+/// Names are case-insensitive: `ease`, `ease-in`, `ease-out`, `ease-in-out`
+/// and `spring`.
 pub fn parse_cubic_bezier_easing(value: &str) -> Option<(f32, f32, f32, f32)> {
     let trimmed = value.trim();
+    if let Some((_, curve)) = NAMED_EASINGS
+        .iter()
+        .find(|(name, _)| trimmed.eq_ignore_ascii_case(name))
+    {
+        return Some(*curve);
+    }
+
     let body = trimmed
         .strip_prefix("cubic-bezier(")?
         .strip_suffix(')')?
@@ -67,12 +106,30 @@ pub fn parse_cubic_bezier_easing(value: &str) -> Option<(f32, f32, f32, f32)> {
     Some((x1, y1, x2, y2))
 }
 
+thread_local! {
+    /// Parsed theme easing strings, filled on first use or by [`crate::theme::warm_up`].
+    static EASING_CACHE: RefCell<HashMap<String, Option<(f32, f32, f32, f32)>>> =
+        RefCell::new(HashMap::new());
+}
+
+/// [`parse_cubic_bezier_easing`], memoized per easing string.
+pub(crate) fn cached_cubic_bezier_easing(easing: &str) -> Option<(f32, f32, f32, f32)> {
+    EASING_CACHE.with(|cache| {
+        if let Some(parsed) = cache.borrow().get(easing) {
+            return *parsed;
+        }
+        let parsed = parse_cubic_bezier_easing(easing);
+        cache.borrow_mut().insert(easing.to_string(), parsed);
+        parsed
+    })
+}
+
 /// Apply a theme easing string to an Animation.
 pub fn animation_with_theme_easing(animation: Animation, easing: &str) -> Animation {
     if easing.trim().eq_ignore_ascii_case("linear") {
         return animation.with_easing(|delta: f32| delta);
     }
-    if let Some((x1, y1, x2, y2)) = parse_cubic_bezier_easing(easing) {
+    if let Some((x1, y1, x2, y2)) = cached_cubic_bezier_easing(easing) {
         let overshoot = y1 < 0.0 || y1 > 1.0 || y2 < 0.0 || y2 > 1.0;
         if overshoot {
             return animation.with_unbounded_easing(cubic_bezier_unbounded(x1, y1, x2, y2));
@@ -377,6 +434,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_named_easings() {
+        assert_eq!(
+            parse_cubic_bezier_easing("ease"),
+            Some((0.25, 0.1, 0.25, 1.0))
+        );
+        assert_eq!(
+            parse_cubic_bezier_easing("ease-in"),
+            Some((0.42, 0.0, 1.0, 1.0))
+        );
+        assert_eq!(
+            parse_cubic_bezier_easing("ease-out"),
+            Some((0.0, 0.0, 0.58, 1.0))
+        );
+        assert_eq!(
+            parse_cubic_bezier_easing("ease-in-out"),
+            Some((0.42, 0.0, 0.58, 1.0))
+        );
+        assert_eq!(
+            parse_cubic_bezier_easing("spring"),
+            Some((0.34, 1.56, 0.64, 1.0))
+        );
+        assert_eq!(
+            parse_cubic_bezier_easing(" Ease-Out "),
+            Some((0.0, 0.0, 0.58, 1.0))
+        );
+        assert_eq!(parse_cubic_bezier_easing("bouncy"), None);
+    }
+
     #[test]
     fn spring_invoke_respects_reduced_motion() {
         let motion = ThemeMotion::default();
@@ -617,7 +617,7 @@ impl RenderOnce for Button {
                         .build(window, cx)
                 })
             })
-            .focus_ring(is_focused, px(0.), window, cx)
+            .outer_focus_ring(is_focused, px(0.), window, cx)
     }
 }
 
@@ -249,7 +249,7 @@ impl RenderOnce for Checkbox {
                     this.text_color(cx.theme().muted_foreground)
                 })
                 .rounded(cx.theme().radius * 0.5)
-                .focus_ring(is_focused, px(2.), window, cx)
+                .outer_focus_ring(is_focused, px(2.), window, cx)
                 .refine_style(&self.style)
                 .child(
                     div()
@@ -1,11 +1,16 @@
 //! Fuzzy matching implementations for the Command Palette.
 
-use super::types::{CommandMatcher, CommandPaletteItem, CommandPaletteMatch};
+use super::types::{
+    CommandMatcher, CommandPaletteItem, CommandPaletteMatch, CommandPaletteMatchField,
+};
 use fuzzy_matcher::FuzzyMatcher as _;
 use fuzzy_matcher::skim::SkimMatcherV2;
 use nucleo::Utf32Str;
 use nucleo::pattern::{CaseMatching, Normalization, Pattern};
 
+/// Score bonus per query character when the query matches the initials of the text.
+const INITIALISM_BONUS_PER_CHAR: i64 = 50;
+
 /// Nucleo-based fuzzy matcher (default).
 ///
 /// Provides fast, async-friendly fuzzy matching with Unicode support.
@@ -38,6 +43,14 @@ impl NucleoMatcher {
 
         Some((score as i64, ranges))
     }
+
+    fn match_text_with_initials(
+        &self,
+        query: &str,
+        text: &str,
+    ) -> Option<(i64, Vec<(usize, usize)>)> {
+        with_initialism(query, text, self.match_text(query, text))
+    }
 }
 
 impl CommandMatcher for NucleoMatcher {
@@ -47,19 +60,19 @@ impl CommandMatcher for NucleoMatcher {
         }
 
         // Try matching against title first
-        let title_match = self.match_text(query, &item.title);
+        let title_match = self.match_text_with_initials(query, &item.title);
 
         // Try matching against subtitle
         let subtitle_match = item
             .subtitle
             .as_ref()
-            .and_then(|s| self.match_text(query, s));
+            .and_then(|s| self.match_text_with_initials(query, s));
 
         // Try matching against keywords
         let keyword_match = item
             .keywords
             .iter()
-            .filter_map(|k| self.match_text(query, k))
+            .filter_map(|k| self.match_text(query, k).map(|(score, _)| (score, k)))
             .max_by_key(|(score, _)| *score);
 
         // Try matching against category
@@ -67,6 +80,7 @@ impl CommandMatcher for NucleoMatcher {
 
         // Combine scores and use the best match
         let mut best_score = None;
+        let mut matched_field = CommandPaletteMatchField::Title;
         let mut title_ranges = Vec::new();
         let mut subtitle_ranges = Vec::new();
 
@@ -79,14 +93,16 @@ impl CommandMatcher for NucleoMatcher {
             let adjusted = score + 500; // Boost subtitle matches
             if best_score.map_or(true, |s| adjusted > s) {
                 best_score = Some(adjusted);
+                matched_field = CommandPaletteMatchField::Subtitle;
                 subtitle_ranges = ranges;
             }
         }
 
-        if let Some((score, _)) = keyword_match {
+        if let Some((score, keyword)) = keyword_match {
             let adjusted = score + 200; // Moderate boost for keyword matches
             if best_score.map_or(true, |s| adjusted > s) {
                 best_score = Some(adjusted);
+                matched_field = CommandPaletteMatchField::Keyword(keyword.clone());
             }
         }
 
@@ -101,6 +117,7 @@ impl CommandMatcher for NucleoMatcher {
             CommandPaletteMatch::new(score)
                 .with_title_ranges(title_ranges)
                 .with_subtitle_ranges(subtitle_ranges)
+                .with_matched_field(matched_field)
         })
     }
 }
@@ -142,6 +159,14 @@ impl FuzzyMatcherWrapper {
                 (score, ranges)
             })
     }
+
+    fn match_text_with_initials(
+        &self,
+        query: &str,
+        text: &str,
+    ) -> Option<(i64, Vec<(usize, usize)>)> {
+        with_initialism(query, text, self.match_text(query, text))
+    }
 }
 
 impl CommandMatcher for FuzzyMatcherWrapper {
@@ -151,23 +176,24 @@ impl CommandMatcher for FuzzyMatcherWrapper {
         }
 
         // Try matching against title first
-        let title_match = self.match_text(query, &item.title);
+        let title_match = self.match_text_with_initials(query, &item.title);
 
         // Try matching against subtitle
         let subtitle_match = item
             .subtitle
             .as_ref()
-            .and_then(|s| self.match_text(query, s));
+            .and_then(|s| self.match_text_with_initials(query, s));
 
         // Try matching against keywords
         let keyword_match = item
             .keywords
             .iter()
-            .filter_map(|k| self.match_text(query, k))
+            .filter_map(|k| self.match_text(query, k).map(|(score, _)| (score, k)))
             .max_by_key(|(score, _)| *score);
 
         // Combine scores and use the best match
         let mut best_score = None;
+        let mut matched_field = CommandPaletteMatchField::Title;
         let mut title_ranges = Vec::new();
         let mut subtitle_ranges = Vec::new();
 
@@ -180,14 +206,16 @@ impl CommandMatcher for FuzzyMatcherWrapper {
             let adjusted = score + 500; // Boost subtitle matches
             if best_score.map_or(true, |s| adjusted > s) {
                 best_score = Some(adjusted);
+                matched_field = CommandPaletteMatchField::Subtitle;
                 subtitle_ranges = ranges;
             }
         }
 
-        if let Some((score, _)) = keyword_match {
+        if let Some((score, keyword)) = keyword_match {
             let adjusted = score + 200; // Moderate boost for keyword matches
             if best_score.map_or(true, |s| adjusted > s) {
                 best_score = Some(adjusted);
+                matched_field = CommandPaletteMatchField::Keyword(keyword.clone());
             }
         }
 
@@ -195,10 +223,69 @@ impl CommandMatcher for FuzzyMatcherWrapper {
             CommandPaletteMatch::new(score)
                 .with_title_ranges(title_ranges)
                 .with_subtitle_ranges(subtitle_ranges)
+                .with_matched_field(matched_field)
         })
     }
 }
 
+/// Boost a fuzzy match when the query spells the initials of the words in `text`,
+/// e.g. "gsp" for "Git: Status Push", highlighting the initials instead.
+///
+/// Matches the initials even when the fuzzy matcher did not.
+fn with_initialism(
+    query: &str,
+    text: &str,
+    fuzzy_match: Option<(i64, Vec<(usize, usize)>)>,
+) -> Option<(i64, Vec<(usize, usize)>)> {
+    let Some(indices) = initialism_indices(query, text) else {
+        return fuzzy_match;
+    };
+
+    let base_score = fuzzy_match.map_or(0, |(score, _)| score.max(0));
+    let bonus = INITIALISM_BONUS_PER_CHAR * indices.len() as i64;
+    Some((base_score + bonus, indices_to_ranges_usize(&indices, text)))
+}
+
+/// Returns the char indices of word starts in `text` spelling out `query`, if any.
+///
+/// A word starts at the beginning of the text, after a space or one of `:-_/.`,
+/// or at a lowercase to uppercase transition. Whitespace in the query is ignored,
+/// and queries shorter than two characters never match.
+fn initialism_indices(query: &str, text: &str) -> Option<Vec<usize>> {
+    let query: Vec<char> = query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect();
+    if query.len() < 2 {
+        return None;
+    }
+
+    let mut indices = Vec::with_capacity(query.len());
+    let mut prev: Option<char> = None;
+    for (ix, c) in text.chars().enumerate() {
+        let word_start = c.is_alphanumeric()
+            && match prev {
+                None => true,
+                Some(prev) => {
+                    prev.is_whitespace()
+                        || matches!(prev, ':' | '-' | '_' | '/' | '.')
+                        || (prev.is_lowercase() && c.is_uppercase())
+                }
+            };
+        prev = Some(c);
+
+        if word_start && c.to_lowercase().eq(query[indices.len()].to_lowercase()) {
+            indices.push(ix);
+            if indices.len() == query.len() {
+                return Some(indices);
+            }
+        }
+    }
+
+    None
+}
+
 /// Convert nucleo u32 char indices to byte offset ranges.
 ///
 /// Nucleo returns character indices, but Rust strings use byte offsets.
@@ -289,3 +376,48 @@ fn indices_to_ranges_usize(indices: &[usize], text: &str) -> Vec<(usize, usize)>
         })
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{FuzzyMatcherWrapper, NucleoMatcher, initialism_indices};
+    use crate::command_palette::{CommandMatcher, CommandPaletteItem};
+
+    #[test]
+    fn test_initialism_indices() {
+        assert_eq!(
+            initialism_indices("gsp", "Git: Status Push"),
+            Some(vec![0, 5, 12])
+        );
+        assert_eq!(
+            initialism_indices("tsb", "Toggle Side-Bar"),
+            Some(vec![0, 7, 12])
+        );
+        assert_eq!(
+            initialism_indices("oft", "openFileTab"),
+            Some(vec![0, 4, 8])
+        );
+        // Words may be skipped, and the query may contain spaces
+        assert_eq!(
+            initialism_indices("g p", "Git: Status Push"),
+            Some(vec![0, 12])
+        );
+        assert_eq!(initialism_indices("gsx", "Git: Status Push"), None);
+        assert_eq!(initialism_indices("g", "Git: Status Push"), None);
+    }
+
+    #[test]
+    fn test_initialism_ranks_first() {
+        let push = CommandPaletteItem::new("push", "Git: Status Push");
+        let grasp = CommandPaletteItem::new("grasp", "Grasp");
+
+        let nucleo = NucleoMatcher::new();
+        let push_match = nucleo.match_item("gsp", &push).unwrap();
+        assert_eq!(push_match.title_ranges, vec![(0, 1), (5, 6), (12, 13)]);
+        assert!(push_match.score > nucleo.match_item("gsp", &grasp).unwrap().score);
+
+        let fuzzy = FuzzyMatcherWrapper::new();
+        let push_match = fuzzy.match_item("gsp", &push).unwrap();
+        assert_eq!(push_match.title_ranges, vec![(0, 1), (5, 6), (12, 13)]);
+        assert!(push_match.score > fuzzy.match_item("gsp", &grasp).unwrap().score);
+    }
+}
@@ -26,6 +26,9 @@
 //! // Open the palette
 //! let handle = CommandPalette::open(window, cx, provider);
 //!
+//! // Or anchor it to a toolbar button like a dropdown
+//! let handle = CommandPalette::open_anchored(window, cx, button_bounds, Anchor::TopLeft, provider);
+//!
 //! // Close when done
 //! handle.close(window, cx);
 //! ```
@@ -42,8 +45,8 @@ pub use matcher::{FuzzyMatcherWrapper, NucleoMatcher};
 pub use provider::{CommandPaletteProvider, StaticProvider};
 pub use state::{CommandPaletteEvent, CommandPaletteState};
 pub use types::{
-    CommandMatcher, CommandMatcherKind, CommandPaletteConfig, CommandPaletteItem,
-    CommandPaletteMatch, MatchedItem,
+    CommandMatcher, CommandMatcherKind, CommandPaletteConfig, CommandPaletteCopyField,
+    CommandPaletteItem, CommandPaletteMatch, CommandPaletteMatchField, MatchedItem, SelectionMode,
 };
 
 const REVEAL_DELAY_MS: u64 = 100;
@@ -59,13 +62,30 @@ pub(crate) fn reveal_animation_duration(cx: &App) -> Duration {
     Duration::from_millis(u64::from(cx.theme().motion.fast_duration_ms))
 }
 
-use gpui::{App, AppContext as _, Entity, KeyBinding, ParentElement as _, Styled, Window, actions};
+use gpui::{
+    App, AppContext as _, Bounds, Context, Entity, KeyBinding, ParentElement as _, Pixels, Point,
+    SharedString, Styled, Window, actions, prelude::FluentBuilder as _, px,
+};
 use std::sync::Arc;
-use view::CommandPaletteView;
+use view::{CommandPalettePlacement, CommandPaletteView, chrome_height};
 
-use crate::{ActiveTheme as _, WindowExt as _};
+use crate::{ActiveTheme as _, Anchor, WindowExt as _};
 
-actions!(command_palette, [Open]);
+/// Gap between an anchored palette and its trigger.
+const ANCHORED_GAP: f32 = 4.0;
+/// Minimum distance kept between an anchored palette and the window edges.
+const ANCHORED_WINDOW_MARGIN: f32 = 8.0;
+
+actions!(
+    command_palette,
+    [
+        Open,
+        CycleCategory,
+        ToggleCheatsheet,
+        CopySelected,
+        ToggleChecked
+    ]
+);
 
 /// Handle to an open command palette.
 ///
@@ -73,6 +93,8 @@ actions!(command_palette, [Open]);
 pub struct CommandPaletteHandle {
     /// Entity reference to the palette state.
     state: Entity<CommandPaletteState>,
+    /// Entity reference to the palette view, holding the search input.
+    view: Entity<CommandPaletteView>,
 }
 
 impl CommandPaletteHandle {
@@ -83,7 +105,8 @@ impl CommandPaletteHandle {
     /// let handle = CommandPalette::open(window, cx, provider);
     /// cx.subscribe(&handle.state(), |_, event, cx| {
     ///     match event {
-    ///         CommandPaletteEvent::Selected { item } => { /* handle selection */ }
+    ///         CommandPaletteEvent::Selected { item, .. } => { /* handle selection */ }
+    ///         CommandPaletteEvent::SelectedMany { items } => { /* handle multi-selection */ }
     ///         CommandPaletteEvent::Dismissed => { /* handle dismissal */ }
     ///     }
     /// });
@@ -92,9 +115,33 @@ impl CommandPaletteHandle {
         &self.state
     }
 
-    /// Close the command palette.
-    pub fn close(self, window: &mut Window, cx: &mut App) {
-        window.close_dialog(cx);
+    /// Replace the search text with `query` and update the results, like typing it.
+    pub fn set_query(&self, query: impl Into<SharedString>, window: &mut Window, cx: &mut App) {
+        let query = query.into().to_string();
+        self.view.update(cx, |view, cx| {
+            view.set_query(query, window, cx);
+        });
+    }
+
+    /// Select the item with `id` among the current results and scroll it into view,
+    /// e.g. to highlight the current value after [`Self::set_query`].
+    ///
+    /// Returns false if no result has that id, e.g. an async result that hasn't
+    /// arrived yet.
+    pub fn select_id(&self, id: impl Into<SharedString>, cx: &mut App) -> bool {
+        let id = id.into();
+        self.view.update(cx, |view, cx| view.select_id(&id, cx))
+    }
+
+    /// Close the command palette, dismissing it like the Escape key does.
+    ///
+    /// Does nothing if it has already closed.
+    pub fn close(self, _window: &mut Window, cx: &mut App) {
+        self.state.update(cx, |state, cx| {
+            if !state.is_closed() {
+                state.dismiss(cx);
+            }
+        });
     }
 }
 
@@ -107,7 +154,7 @@ impl CommandPalette {
     /// This sets up the global keybinding (if configured) and should be
     /// called once at application startup.
     pub fn init(cx: &mut App, config: CommandPaletteConfig) {
-        view::init(cx);
+        view::init(cx, &config);
 
         // Register the Open action keybinding if configured
         if let Some(shortcut) = &config.shortcut {
@@ -121,6 +168,19 @@ impl CommandPalette {
         cx.set_global(GlobalCommandPaletteConfig(config));
     }
 
+    /// Record `id` as the most recently used item in the default configuration,
+    /// so the palettes opened next list it first while the query is empty.
+    ///
+    /// Call this when handling [`CommandPaletteEvent::Selected`], see
+    /// [`CommandPaletteConfig::recent`]. Does nothing before [`Self::init`].
+    pub fn record_recent(id: impl Into<SharedString>, cx: &mut App) {
+        if cx.has_global::<GlobalCommandPaletteConfig>() {
+            cx.global_mut::<GlobalCommandPaletteConfig>()
+                .0
+                .record_recent(id);
+        }
+    }
+
     /// Open the command palette with the given provider.
     ///
     /// Returns a handle that can be used to close the palette.
@@ -137,37 +197,243 @@ impl CommandPalette {
         Self::open_with_config(window, cx, provider, config)
     }
 
+    /// Open the command palette with the search input pre-filled with `initial_query`,
+    /// e.g. the current name in a rename flow.
+    ///
+    /// See also [`CommandPaletteHandle::select_id`] to pre-select a result.
+    pub fn open_with_query(
+        window: &mut Window,
+        cx: &mut App,
+        provider: Arc<dyn CommandPaletteProvider>,
+        initial_query: impl Into<SharedString>,
+    ) -> CommandPaletteHandle {
+        let handle = Self::open(window, cx, provider);
+        handle.set_query(initial_query, window, cx);
+        handle
+    }
+
+    /// Open the command palette with several labelled providers and a scope
+    /// selector to switch between them, e.g. "Commands" and "Files".
+    ///
+    /// `initial` is the index of the provider to open with. Switching scope clears
+    /// the results and re-runs the current query against the new provider.
+    ///
+    /// Returns `None` without opening if `providers` is empty.
+    pub fn open_multi(
+        window: &mut Window,
+        cx: &mut App,
+        providers: Vec<(SharedString, Arc<dyn CommandPaletteProvider>)>,
+        initial: usize,
+    ) -> Option<CommandPaletteHandle> {
+        if providers.is_empty() {
+            return None;
+        }
+        let config = cx
+            .try_global::<GlobalCommandPaletteConfig>()
+            .map(|g| g.0.clone())
+            .unwrap_or_default();
+
+        Some(Self::open_view(
+            window,
+            cx,
+            config,
+            CommandPalettePlacement::Centered,
+            None,
+            move |config, window, cx| {
+                CommandPaletteView::new_with_scopes(config, providers, initial, window, cx)
+            },
+        ))
+    }
+
+    /// Open the command palette showing the results of all `providers` at once,
+    /// e.g. commands, files and symbols together.
+    ///
+    /// The providers are queried in parallel and their results sorted by score and
+    /// grouped by provider, see [`CommandPaletteState::new_combined`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `providers` is empty.
+    pub fn open_combined(
+        window: &mut Window,
+        cx: &mut App,
+        providers: Vec<Arc<dyn CommandPaletteProvider>>,
+    ) -> CommandPaletteHandle {
+        let config = cx
+            .try_global::<GlobalCommandPaletteConfig>()
+            .map(|g| g.0.clone())
+            .unwrap_or_default();
+
+        Self::open_view(
+            window,
+            cx,
+            config,
+            CommandPalettePlacement::Centered,
+            None,
+            move |config, window, cx| {
+                CommandPaletteView::new_combined(config, providers, window, cx)
+            },
+        )
+    }
+
     /// Open the command palette with custom configuration.
     pub fn open_with_config(
         window: &mut Window,
         cx: &mut App,
         provider: Arc<dyn CommandPaletteProvider>,
         config: CommandPaletteConfig,
+    ) -> CommandPaletteHandle {
+        Self::open_view(
+            window,
+            cx,
+            config,
+            CommandPalettePlacement::Centered,
+            None,
+            move |config, window, cx| CommandPaletteView::new(config, provider, window, cx),
+        )
+    }
+
+    /// Open the command palette anchored to a trigger, like a dropdown menu.
+    ///
+    /// `anchor` follows the `Popover` convention: top anchors open below the
+    /// trigger and bottom anchors open above it, aligned to the left, center or
+    /// right edge. The palette flips to the other side when there is not enough
+    /// room, and its list height shrinks to fit the remaining space.
+    pub fn open_anchored(
+        window: &mut Window,
+        cx: &mut App,
+        trigger_bounds: Bounds<Pixels>,
+        anchor: Anchor,
+        provider: Arc<dyn CommandPaletteProvider>,
+    ) -> CommandPaletteHandle {
+        let config = cx
+            .try_global::<GlobalCommandPaletteConfig>()
+            .map(|g| g.0.clone())
+            .unwrap_or_default();
+
+        Self::open_anchored_with_config(window, cx, trigger_bounds, anchor, provider, config)
+    }
+
+    /// Open the command palette anchored to a trigger with custom configuration.
+    ///
+    /// See [`Self::open_anchored`] for placement rules.
+    pub fn open_anchored_with_config(
+        window: &mut Window,
+        cx: &mut App,
+        trigger_bounds: Bounds<Pixels>,
+        anchor: Anchor,
+        provider: Arc<dyn CommandPaletteProvider>,
+        mut config: CommandPaletteConfig,
+    ) -> CommandPaletteHandle {
+        let viewport = window.viewport_size();
+        let margin = px(ANCHORED_WINDOW_MARGIN);
+        let gap = px(ANCHORED_GAP);
+        let width = px(config.width).min(viewport.width - margin * 2.);
+        config.width = f32::from(width);
+
+        // Vertical placement: prefer the anchor's side, flip if the other side has more room
+        let chrome_height = chrome_height(&config);
+        let space_below = viewport.height - trigger_bounds.bottom() - gap - margin;
+        let space_above = trigger_bounds.top() - gap - margin;
+        let needed = chrome_height + px(config.max_height);
+        let opens_upward = if anchor.is_bottom() {
+            space_above >= needed || space_above > space_below
+        } else {
+            space_below < needed && space_above > space_below
+        };
+        let available = if opens_upward {
+            space_above
+        } else {
+            space_below
+        };
+        let max_list_height = (available - chrome_height).max(px(0.));
+        config.max_height = config.max_height.min(f32::from(max_list_height));
+        let panel_height = chrome_height + px(config.max_height);
+
+        // Horizontal placement, clamped into the window
+        let x = if anchor.is_left() {
+            trigger_bounds.left()
+        } else if anchor.is_right() {
+            trigger_bounds.right() - width
+        } else {
+            trigger_bounds.center().x - width / 2.
+        };
+        let x = x.min(viewport.width - width - margin).max(margin);
+        let y = if opens_upward {
+            trigger_bounds.top() - gap - panel_height
+        } else {
+            trigger_bounds.bottom() + gap
+        };
+
+        let placement = if opens_upward {
+            CommandPalettePlacement::Above
+        } else {
+            CommandPalettePlacement::Below
+        };
+
+        Self::open_view(
+            window,
+            cx,
+            config,
+            placement,
+            Some(gpui::point(x, y.max(margin))),
+            move |config, window, cx| CommandPaletteView::new(config, provider, window, cx),
+        )
+    }
+
+    fn open_view(
+        window: &mut Window,
+        cx: &mut App,
+        config: CommandPaletteConfig,
+        placement: CommandPalettePlacement,
+        position: Option<Point<Pixels>>,
+        new_view: impl FnOnce(
+            CommandPaletteConfig,
+            &mut Window,
+            &mut Context<CommandPaletteView>,
+        ) -> CommandPaletteView,
     ) -> CommandPaletteHandle {
         let width = gpui::px(config.width);
+        let anchored = position.is_some();
 
         // Create the view entity
         let view: Entity<CommandPaletteView> =
-            cx.new(|cx| CommandPaletteView::new(config.clone(), provider, window, cx));
+            cx.new(|cx| new_view(config, window, cx).placement(placement));
 
         // Get the state entity from the view
         let state = view.read(cx).state.clone();
 
-        // Open as a dialog
+        // Every way out of the dialog ends in `notify_closed`, which calls the providers'
+        // `on_close` once: the dialog's own close paths through `on_close`, and closing it
+        // from outside, e.g. with `close_all_dialogs`, by releasing `close_guard`
+        let close_guard = cx.new(|_| ());
+        cx.observe_release(&close_guard, {
+            let state = state.clone();
+            move |_, cx| state.update(cx, |state, cx| state.notify_closed(cx))
+        })
+        .detach();
+
+        // Open as a dialog; anchored palettes skip the overlay and dismiss on outside clicks
+        let dialog_view = view.clone();
+        let dialog_state = state.clone();
         window.open_dialog(cx, move |dialog, _window, _cx| {
+            let _ = &close_guard;
+            let state = dialog_state.clone();
             dialog
                 .w(width)
                 .min_h(gpui::px(0.))
-                .overlay(true)
+                .when_some(position, |dialog, position| dialog.position(position))
+                .overlay(!anchored)
                 .overlay_closable(true)
                 .keyboard(true)
                 .animate(false)
                 .close_button(false)
                 .p_0()
-                .child(view.clone())
+                .on_close(move |_, _, cx| state.update(cx, |state, cx| state.notify_closed(cx)))
+                .child(dialog_view.clone())
         });
 
-        CommandPaletteHandle { state }
+        CommandPaletteHandle { state, view }
     }
 }
 
@@ -175,3 +441,66 @@ impl CommandPalette {
 struct GlobalCommandPaletteConfig(CommandPaletteConfig);
 
 impl gpui::Global for GlobalCommandPaletteConfig {}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    };
+
+    use gpui::{
+        App, AppContext as _, Context, Empty, IntoElement, Render, TestAppContext,
+        VisualTestContext, Window,
+    };
+
+    use super::{CommandPalette, CommandPaletteProvider};
+    use crate::{Root, WindowExt as _};
+
+    struct CloseCounter(Arc<AtomicUsize>);
+
+    impl CommandPaletteProvider for CloseCounter {
+        fn on_close(&self, _cx: &App) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    struct TestView;
+
+    impl Render for TestView {
+        fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+            Empty
+        }
+    }
+
+    #[gpui::test]
+    fn test_on_close_fires_once_on_outside_close(cx: &mut TestAppContext) {
+        cx.update(crate::init);
+        let window = cx.add_window(|window, cx| Root::new(cx.new(|_| TestView), window, cx));
+        let cx = &mut VisualTestContext::from_window(window.into(), cx);
+
+        let closes = Arc::new(AtomicUsize::new(0));
+        let provider = Arc::new(CloseCounter(closes.clone()));
+        let handle = cx.update(|window, cx| CommandPalette::open(window, cx, provider));
+
+        // Closed by the app rather than by the palette itself
+        cx.update(|window, cx| window.close_all_dialogs(cx));
+        cx.run_until_parked();
+        assert_eq!(closes.load(Ordering::SeqCst), 1);
+
+        cx.update(|window, cx| handle.close(window, cx));
+        cx.run_until_parked();
+        assert_eq!(closes.load(Ordering::SeqCst), 1);
+    }
+
+    #[gpui::test]
+    fn test_open_multi_without_providers(cx: &mut TestAppContext) {
+        cx.update(crate::init);
+        let window = cx.add_window(|window, cx| Root::new(cx.new(|_| TestView), window, cx));
+        let cx = &mut VisualTestContext::from_window(window.into(), cx);
+
+        let handle = cx.update(|window, cx| CommandPalette::open_multi(window, cx, Vec::new(), 0));
+        assert!(handle.is_none());
+        assert!(!cx.update(|window, cx| window.has_active_dialog(cx)));
+    }
+}
@@ -25,6 +25,42 @@ pub trait CommandPaletteProvider: Send + Sync {
     fn query(&self, _query: &str, _cx: &App) -> Task<Vec<CommandPaletteItem>> {
         Task::ready(Vec::new())
     }
+
+    /// Query one page of items asynchronously, used when the palette is
+    /// [paginated](super::CommandPaletteConfig::paginated).
+    ///
+    /// `offset` is the number of async items already loaded for `query`. Returning
+    /// fewer than `limit` items marks the last page.
+    ///
+    /// Default implementation returns `query()` for the first page and nothing after.
+    fn query_page(
+        &self,
+        query: &str,
+        offset: usize,
+        _limit: usize,
+        cx: &App,
+    ) -> Task<Vec<CommandPaletteItem>> {
+        if offset == 0 {
+            self.query(query, cx)
+        } else {
+            Task::ready(Vec::new())
+        }
+    }
+
+    /// Called when the palette opens, before `items()` is read.
+    ///
+    /// Use this to warm up resources such as a search index.
+    ///
+    /// Default implementation does nothing.
+    fn on_open(&self, _cx: &App) {}
+
+    /// Called once when the palette closes, after a selection, a dismissal or the
+    /// dialog being closed from outside.
+    ///
+    /// Use this to release resources acquired in `on_open`.
+    ///
+    /// Default implementation does nothing.
+    fn on_close(&self, _cx: &App) {}
 }
 
 /// A simple provider that holds a static list of items.
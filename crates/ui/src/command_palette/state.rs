@@ -2,12 +2,14 @@
 
 use super::REVEAL_QUERY_DELAY;
 use super::matcher::{FuzzyMatcherWrapper, NucleoMatcher};
-use super::provider::CommandPaletteProvider;
+use super::provider::{CommandPaletteProvider, StaticProvider};
 use super::types::{
-    CommandMatcher, CommandMatcherKind, CommandPaletteConfig, CommandPaletteItem, MatchedItem,
+    CommandMatcher, CommandMatcherKind, CommandPaletteConfig, CommandPaletteItem,
+    CommandPaletteMatch, MatchedItem, SelectionMode,
 };
+use crate::IconNamed;
 use crate::global_state::GlobalState;
-use gpui::{Context, EventEmitter, Task, Window};
+use gpui::{Context, EventEmitter, SharedString, Task, Window};
 use smol::Timer;
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -21,6 +23,15 @@ pub enum CommandPaletteEvent {
     Selected {
         /// The selected item.
         item: CommandPaletteItem,
+        /// Whether the item was confirmed with the secondary binding
+        /// (`cmd-enter` / `ctrl-enter` or `shift-enter`). The palette stays open.
+        secondary: bool,
+    },
+    /// The checked items were confirmed in [`SelectionMode::Multi`], in the order
+    /// they were checked.
+    SelectedMany {
+        /// The confirmed items.
+        items: Vec<CommandPaletteItem>,
     },
     /// The palette was dismissed (user pressed Escape or clicked outside).
     Dismissed,
@@ -30,28 +41,73 @@ pub enum CommandPaletteEvent {
 pub struct CommandPaletteState {
     /// The current configuration.
     pub config: CommandPaletteConfig,
-    /// The provider for items.
+    /// The provider for items, the one of the active scope.
+    ///
+    /// In a combined palette this is the first of its providers, see [`Self::new_combined`].
     pub provider: Arc<dyn CommandPaletteProvider>,
+    /// The providers queried together: all of a combined palette, otherwise `provider`.
+    sources: Vec<Arc<dyn CommandPaletteProvider>>,
+    /// The labelled providers the palette can switch between, see [`Self::set_scope`].
+    scopes: Vec<(SharedString, Arc<dyn CommandPaletteProvider>)>,
+    /// Index of the active scope in `scopes`.
+    active_scope: usize,
+    /// The palettes drilled out of, innermost last, see [`Self::push_provider`].
+    parents: Vec<ParentProvider>,
+    /// The prefix of the active mode, see [`CommandPaletteConfig::mode_prefix`].
+    mode: Option<char>,
+    /// The provider and sources to restore when leaving a mode.
+    default_sources: Option<(
+        Arc<dyn CommandPaletteProvider>,
+        Vec<Arc<dyn CommandPaletteProvider>>,
+    )>,
     /// The current query string.
     pub query: String,
     /// The list of matched items (sorted by score).
     pub matched_items: Vec<MatchedItem>,
     /// The number of matched items from the static provider.
     pub matched_static_len: usize,
+    /// The number of recent items at the start of `matched_items`, listed while the
+    /// query is empty, see [`CommandPaletteConfig::recent`].
+    pub matched_recent_len: usize,
     /// The currently selected index.
     pub selected_index: Option<usize>,
+    /// The items checked in [`SelectionMode::Multi`], in the order they were checked.
+    ///
+    /// Checked items stay checked when a new query filters them out.
+    checked: Vec<CommandPaletteItem>,
+    /// The query and cheatsheet mode `matched_items` were last computed for.
+    matched_for: (String, bool),
+    /// The active category filter, `None` shows all categories.
+    category_filter: Option<SharedString>,
     /// The matcher implementation.
-    matcher: Box<dyn CommandMatcher + Send + Sync>,
+    matcher: Arc<dyn CommandMatcher + Send + Sync>,
     /// Query ID for tracking stale results.
     query_id: Arc<AtomicU64>,
     /// Timestamp after which async results can load.
     reveal_deadline: Option<Instant>,
-    /// Static items from the provider.
-    static_items: Vec<CommandPaletteItem>,
-    /// Async items from the provider (keyed by id).
-    async_items: HashMap<String, CommandPaletteItem>,
-    /// The current async query task.
-    _query_task: Task<()>,
+    /// Static items from the providers, with the index in `sources` of each one's provider.
+    static_items: Vec<(usize, CommandPaletteItem)>,
+    /// Async items from the providers (keyed by the index in `sources` and the id),
+    /// so equal ids of different providers don't replace each other.
+    async_items: HashMap<(usize, String), CommandPaletteItem>,
+    /// Number of async items requested so far for the current query when paginated.
+    page_offset: usize,
+    /// Whether the provider may have more pages for the current query.
+    has_more_pages: bool,
+    /// The pending next-page task, if any.
+    page_task: Option<Task<()>>,
+    /// Whether the provider has been notified that the palette closed.
+    closed: bool,
+    /// Whether the cheatsheet of all commands is shown instead of the matches.
+    cheatsheet: bool,
+    /// Incremented on every match update, to drop stale background match results.
+    match_generation: u64,
+    /// The pending background match task, if any.
+    _match_task: Task<()>,
+    /// The number of sources whose async query for the current query hasn't returned yet.
+    pending_queries: usize,
+    /// The current async query tasks, one per source.
+    _query_tasks: Vec<Task<()>>,
 }
 
 impl EventEmitter<CommandPaletteEvent> for CommandPaletteState {}
@@ -64,18 +120,81 @@ impl CommandPaletteState {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) -> Self {
+        Self::new_with_scopes(
+            config,
+            vec![(SharedString::default(), provider)],
+            0,
+            window,
+            cx,
+        )
+    }
+
+    /// Create a new command palette state that can switch between labelled providers.
+    ///
+    /// `initial` is the index of the scope to open with, clamped to the last one.
+    /// Without any scope, the palette has no items.
+    pub fn new_with_scopes(
+        config: CommandPaletteConfig,
+        mut scopes: Vec<(SharedString, Arc<dyn CommandPaletteProvider>)>,
+        initial: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        if scopes.is_empty() {
+            scopes.push((
+                SharedString::default(),
+                Arc::new(StaticProvider::new(Vec::new())),
+            ));
+        }
+        let active_scope = initial.min(scopes.len() - 1);
+        let provider = scopes[active_scope].1.clone();
+        Self::build(config, scopes, active_scope, vec![provider], window, cx)
+    }
+
+    /// Create a new command palette state showing the results of all `providers` at once.
+    ///
+    /// Every provider is queried in parallel and its async results merged as they
+    /// arrive, without waiting for the slower ones. Matches are sorted by score and
+    /// grouped by provider, the provider with the best match first; see
+    /// [`MatchedItem::source`]. Pagination is not supported with more than one provider.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `providers` is empty.
+    pub fn new_combined(
+        config: CommandPaletteConfig,
+        providers: Vec<Arc<dyn CommandPaletteProvider>>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        assert!(
+            !providers.is_empty(),
+            "CommandPalette needs at least one provider"
+        );
+        let scopes = vec![(SharedString::default(), providers[0].clone())];
+        Self::build(config, scopes, 0, providers, window, cx)
+    }
+
+    fn build(
+        config: CommandPaletteConfig,
+        scopes: Vec<(SharedString, Arc<dyn CommandPaletteProvider>)>,
+        active_scope: usize,
+        sources: Vec<Arc<dyn CommandPaletteProvider>>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let provider = sources[0].clone();
+
         // Create the matcher based on config
-        let matcher: Box<dyn CommandMatcher + Send + Sync> = match &config.matcher {
-            CommandMatcherKind::Nucleo => Box::new(NucleoMatcher::new()),
-            CommandMatcherKind::FuzzyMatcher => Box::new(FuzzyMatcherWrapper::new()),
-            CommandMatcherKind::Custom(m) => {
-                // Wrap the custom matcher
-                Box::new(CustomMatcherWrapper(m.clone()))
-            }
+        let matcher: Arc<dyn CommandMatcher + Send + Sync> = match &config.matcher {
+            CommandMatcherKind::Nucleo => Arc::new(NucleoMatcher::new()),
+            CommandMatcherKind::FuzzyMatcher => Arc::new(FuzzyMatcherWrapper::new()),
+            CommandMatcherKind::Custom(m) => m.clone(),
         };
 
-        // Get static items
-        let static_items = provider.items(cx);
+        for source in &sources {
+            source.on_open(cx);
+        }
 
         let reveal_deadline = if GlobalState::global(cx).reduced_motion() {
             None
@@ -86,17 +205,36 @@ impl CommandPaletteState {
         let mut state = Self {
             config,
             provider,
+            sources,
+            scopes,
+            active_scope,
+            parents: Vec::new(),
+            mode: None,
+            default_sources: None,
             query: String::new(),
             matched_items: Vec::new(),
             matched_static_len: 0,
+            matched_recent_len: 0,
             selected_index: None,
+            checked: Vec::new(),
+            matched_for: (String::new(), false),
+            category_filter: None,
             matcher,
             query_id: Arc::new(AtomicU64::new(0)),
             reveal_deadline,
-            static_items,
+            static_items: Vec::new(),
             async_items: HashMap::new(),
-            _query_task: Task::ready(()),
+            page_offset: 0,
+            has_more_pages: false,
+            page_task: None,
+            closed: false,
+            cheatsheet: false,
+            match_generation: 0,
+            _match_task: Task::ready(()),
+            pending_queries: 0,
+            _query_tasks: Vec::new(),
         };
+        state.load_static_items(cx);
 
         // Initial matching with empty query
         state.update_matches(window, cx);
@@ -105,152 +243,561 @@ impl CommandPaletteState {
     }
 
     /// Set the query string and update matches.
+    ///
+    /// A leading prefix of [`CommandPaletteConfig::mode_prefix`] switches to its
+    /// provider and is stripped from the query, see [`Self::mode`].
     pub fn set_query(&mut self, query: String, window: &mut Window, cx: &mut Context<Self>) {
+        let (mode, query) = self.split_mode_prefix(query);
+        if mode != self.mode {
+            self.set_mode(mode, query, window, cx);
+            return;
+        }
         if self.query == query {
             return;
         }
 
         self.query = query;
+        self.cheatsheet = false;
 
         // Increment query ID to invalidate stale results
         let current_query_id = self.query_id.fetch_add(1, Ordering::SeqCst) + 1;
 
         // Clear async items so stale results don't appear while awaiting fresh results
         self.async_items.clear();
+        self.page_offset = 0;
+        self.has_more_pages = false;
+        self.page_task = None;
 
         // Update matches immediately with static items
         self.update_matches(window, cx);
+        self.start_query(current_query_id, window, cx);
+    }
+
+    /// Returns the prefix of the active mode, if any.
+    pub fn mode(&self) -> Option<char> {
+        self.mode
+    }
+
+    /// Split a leading mode prefix off `query`, keeping the current mode while
+    /// drilled in.
+    fn split_mode_prefix(&self, query: String) -> (Option<char>, String) {
+        if !self.parents.is_empty() {
+            return (self.mode, query);
+        }
+        match query.chars().next() {
+            Some(prefix) if self.config.mode_prefixes.contains_key(&prefix) => {
+                (Some(prefix), query[prefix.len_utf8()..].to_string())
+            }
+            _ => (None, query),
+        }
+    }
 
+    /// Switch to the provider of `mode`, or back to the default provider for `None`.
+    fn set_mode(
+        &mut self,
+        mode: Option<char>,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let provider = match mode {
+            Some(prefix) => self.config.mode_prefixes.get(&prefix).cloned(),
+            None => None,
+        };
+        let (provider, sources) = match (provider, self.default_sources.take()) {
+            (Some(provider), default_sources) => {
+                self.default_sources = Some(
+                    default_sources
+                        .unwrap_or_else(|| (self.provider.clone(), self.sources.clone())),
+                );
+                (provider.clone(), vec![provider])
+            }
+            (None, Some(default_sources)) => default_sources,
+            (None, None) => (self.provider.clone(), self.sources.clone()),
+        };
+
+        for source in &self.sources {
+            source.on_close(cx);
+        }
+        self.mode = mode;
+        self.provider = provider;
+        self.sources = sources;
+        for source in &self.sources {
+            source.on_open(cx);
+        }
+        self.query = query;
+        self.reload(window, cx);
+    }
+
+    /// Read the static items of every source, remembering which source each came from.
+    fn load_static_items(&mut self, cx: &mut Context<Self>) {
+        self.static_items.clear();
+        for (source, provider) in self.sources.iter().enumerate() {
+            for item in provider.items(cx) {
+                self.static_items.push((source, item));
+            }
+        }
+    }
+
+    /// Query the providers for async items matching the current query.
+    ///
+    /// Each source is queried by its own task, so a slow provider doesn't hold back
+    /// the results of the others. The query waits for the reveal delay and the
+    /// debounce delay, a newer query in the meantime drops it.
+    fn start_query(&mut self, current_query_id: u64, window: &mut Window, cx: &mut Context<Self>) {
+        self._query_tasks.clear();
+        self.pending_queries = 0;
         if self.query.len() < 2 {
-            self._query_task = Task::ready(());
             return;
         }
+        self.pending_queries = self.sources.len();
 
         let query_delay = self
             .reveal_deadline
             .and_then(|deadline| deadline.checked_duration_since(Instant::now()))
-            .unwrap_or(Duration::ZERO);
+            .unwrap_or(Duration::ZERO)
+            .max(Duration::from_millis(self.config.query_debounce_ms));
+        let page_size = self.config.page_size.filter(|_| self.sources.len() == 1);
 
-        // Start async query
-        let provider = self.provider.clone();
-        let query_id = self.query_id.clone();
+        for (source, provider) in self.sources.iter().enumerate() {
+            let provider = provider.clone();
+            let query_id = self.query_id.clone();
 
-        self._query_task = cx.spawn_in(window, async move |this, window| {
-            if !query_delay.is_zero() {
-                Timer::after(query_delay).await;
-            }
+            let task = cx.spawn_in(window, async move |this, window| {
+                if !query_delay.is_zero() {
+                    Timer::after(query_delay).await;
+                }
 
-            if query_id.load(Ordering::SeqCst) != current_query_id {
-                return;
-            }
+                if query_id.load(Ordering::SeqCst) != current_query_id {
+                    return;
+                }
 
-            let task = this.update_in(window, |this, _, cx| provider.query(&this.query, cx));
+                let task = this.update_in(window, |this, _, cx| match page_size {
+                    Some(page_size) => provider.query_page(&this.query, 0, page_size, cx),
+                    None => provider.query(&this.query, cx),
+                });
 
-            let Ok(task) = task else {
-                return;
-            };
+                let Ok(task) = task else {
+                    return;
+                };
+
+                let async_items = task.await;
+
+                // Check if this query is still current
+                if query_id.load(Ordering::SeqCst) != current_query_id {
+                    return;
+                }
+
+                _ = this.update_in(window, |this, window, cx| {
+                    if let Some(page_size) = page_size {
+                        this.page_offset = async_items.len();
+                        this.has_more_pages = async_items.len() >= page_size;
+                    }
+
+                    let was_loading = this.is_loading();
+                    this.pending_queries = this.pending_queries.saturating_sub(1);
+                    this.merge_async_items(source, async_items);
+                    this.update_matches(window, cx);
+                    // The skeletons go away even when the matches are unchanged
+                    if this.config.show_loading_skeletons && was_loading != this.is_loading() {
+                        cx.notify();
+                    }
+                });
+            });
+            self._query_tasks.push(task);
+        }
+    }
 
-            let async_items = task.await;
+    /// Replace the async items of `source` with `items`.
+    fn merge_async_items(&mut self, source: usize, items: Vec<CommandPaletteItem>) {
+        self.async_items
+            .retain(|(item_source, _), _| *item_source != source);
+        for item in items {
+            self.async_items.insert((source, item.id.to_string()), item);
+        }
+    }
+
+    /// Returns the labels of the scopes the palette can switch between.
+    ///
+    /// A palette opened with a single provider has one unlabelled scope.
+    pub fn scopes(&self) -> impl Iterator<Item = &SharedString> {
+        self.scopes.iter().map(|(label, _)| label)
+    }
+
+    /// Returns the index of the active scope.
+    pub fn active_scope(&self) -> usize {
+        self.active_scope
+    }
+
+    /// Switch to the provider of the scope at `index`, clearing the results and
+    /// re-running the current query against it.
+    ///
+    /// The leaving provider gets `on_close` and the new one `on_open`.
+    pub fn set_scope(&mut self, index: usize, window: &mut Window, cx: &mut Context<Self>) {
+        if index == self.active_scope
+            || index >= self.scopes.len()
+            || self.closed
+            || !self.parents.is_empty()
+            || self.mode.is_some()
+        {
+            return;
+        }
+
+        self.provider.on_close(cx);
+        self.active_scope = index;
+        self.provider = self.scopes[index].1.clone();
+        self.sources = vec![self.provider.clone()];
+        self.provider.on_open(cx);
+        self.reload(window, cx);
+    }
+
+    /// Returns the titles of the items drilled into, outermost first.
+    pub fn breadcrumbs(&self) -> impl Iterator<Item = &SharedString> {
+        self.parents.iter().map(|parent| &parent.title)
+    }
+
+    /// Open the sub-palette of `item`, see [`CommandPaletteItem::children`].
+    ///
+    /// The current provider and query are kept to return to with [`Self::pop_provider`],
+    /// and the query is cleared. The mode can't change while drilled in.
+    /// Returns false if `item` has no children.
+    pub fn push_provider(
+        &mut self,
+        item: &CommandPaletteItem,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        let Some(children) = item.children_provider.clone() else {
+            return false;
+        };
+        if item.disabled || self.closed {
+            return false;
+        }
+
+        let query = self.input_query();
+        self.query.clear();
+        self.parents.push(ParentProvider {
+            title: item.title.clone(),
+            provider: std::mem::replace(&mut self.provider, children.clone()),
+            sources: std::mem::replace(&mut self.sources, vec![children.clone()]),
+            query,
+        });
+        children.on_open(cx);
+        self.reload(window, cx);
+        true
+    }
 
-            // Check if this query is still current
+    /// Returns the text of the search input for the current query, with the mode
+    /// prefix at the top level only.
+    pub(crate) fn input_query(&self) -> String {
+        match self.mode.filter(|_| self.parents.is_empty()) {
+            Some(prefix) => format!("{}{}", prefix, self.query),
+            None => self.query.clone(),
+        }
+    }
+
+    /// Return to the parent palette, restoring its query.
+    ///
+    /// Returns the restored query including the mode prefix, or `None` if the palette
+    /// is not drilled in.
+    pub fn pop_provider(&mut self, window: &mut Window, cx: &mut Context<Self>) -> Option<String> {
+        let parent = self.parents.pop()?;
+        for source in &self.sources {
+            source.on_close(cx);
+        }
+
+        self.provider = parent.provider;
+        self.sources = parent.sources;
+        self.query = self.split_mode_prefix(parent.query.clone()).1;
+        self.reload(window, cx);
+        Some(parent.query)
+    }
+
+    /// Reload the items of the current sources and re-run the current query,
+    /// clearing the results and filters.
+    fn reload(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let current_query_id = self.query_id.fetch_add(1, Ordering::SeqCst) + 1;
+        self.load_static_items(cx);
+        self.async_items.clear();
+        self.page_offset = 0;
+        self.has_more_pages = false;
+        self.page_task = None;
+        self.category_filter = None;
+        self.cheatsheet = false;
+
+        self.update_matches(window, cx);
+        self.start_query(current_query_id, window, cx);
+        cx.notify();
+    }
+
+    /// Returns true while an async query for the current query is in flight,
+    /// including its debounce delay.
+    pub fn is_loading(&self) -> bool {
+        self.pending_queries > 0
+    }
+
+    /// Returns true if a paginated provider may have more results for the current query.
+    pub fn has_more_pages(&self) -> bool {
+        self.has_more_pages
+    }
+
+    /// Request the next page of async results, when paginated.
+    ///
+    /// Does nothing if a page is already loading or the last page was reached.
+    /// Results are appended while keeping the current selection.
+    pub fn load_next_page(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(page_size) = self.config.page_size else {
+            return;
+        };
+        if !self.has_more_pages || self.page_task.is_some() {
+            return;
+        }
+
+        let provider = self.provider.clone();
+        let query_id = self.query_id.clone();
+        let current_query_id = query_id.load(Ordering::SeqCst);
+        let offset = self.page_offset;
+        let task = provider.query_page(&self.query, offset, page_size, cx);
+
+        self.page_task = Some(cx.spawn_in(window, async move |this, window| {
+            let page_items = task.await;
+
+            // Drop pages that belong to a previous query
             if query_id.load(Ordering::SeqCst) != current_query_id {
                 return;
             }
 
             _ = this.update_in(window, |this, window, cx| {
-                // Merge async items
-                this.async_items.clear();
-                for item in async_items {
-                    this.async_items.insert(item.id.to_string(), item);
+                this.page_task = None;
+                this.page_offset = offset + page_items.len();
+                this.has_more_pages = page_items.len() >= page_size;
+
+                let selected_id = this.selected_item().map(|matched| matched.item.id.clone());
+                // Pages come from `provider`, the first of the sources
+                for item in page_items {
+                    this.async_items.insert((0, item.id.to_string()), item);
                 }
-                this.update_matches(window, cx);
+                this.update_matches_keeping_selection(selected_id, window, cx);
             });
-        });
+        }));
+    }
+
+    /// Returns true if the cheatsheet of all commands is shown.
+    pub fn is_cheatsheet_open(&self) -> bool {
+        self.cheatsheet
+    }
+
+    /// Toggle the cheatsheet, listing every command grouped by category regardless
+    /// of the query and category filter.
+    pub fn toggle_cheatsheet(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.cheatsheet = !self.cheatsheet;
+        self.update_matches(window, cx);
+        cx.notify();
+    }
+
+    /// Returns the active category filter, if any.
+    pub fn category_filter(&self) -> Option<&SharedString> {
+        self.category_filter.as_ref()
+    }
+
+    /// Returns the distinct non-empty categories of the current items, in first-seen order.
+    pub fn categories(&self) -> Vec<SharedString> {
+        let mut categories: Vec<SharedString> = Vec::new();
+        let static_items = self.static_items.iter().map(|(_, item)| item);
+        for item in static_items.chain(self.async_items.values()) {
+            if !item.category.is_empty() && !categories.contains(&item.category) {
+                categories.push(item.category.clone());
+            }
+        }
+        categories
+    }
+
+    /// Set the category filter and update matches.
+    ///
+    /// Pass `None` to show items from all categories.
+    pub fn set_category_filter(
+        &mut self,
+        category: Option<SharedString>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.category_filter == category {
+            return;
+        }
 
+        self.category_filter = category;
+        self.update_matches(window, cx);
         cx.notify();
     }
 
+    /// Cycle the category filter to the next category.
+    ///
+    /// After the last category the filter wraps back to "all".
+    pub fn cycle_category(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let categories = self.categories();
+        let next = match &self.category_filter {
+            None => categories.first().cloned(),
+            Some(current) => categories
+                .iter()
+                .position(|category| category == current)
+                .and_then(|ix| categories.get(ix + 1))
+                .cloned(),
+        };
+        self.set_category_filter(next, window, cx);
+    }
+
     /// Update the matched items based on the current query.
-    fn update_matches(&mut self, _window: &mut Window, cx: &mut Context<Self>) {
-        if self.query.len() == 1 {
-            self.matched_static_len = 0;
-            self.matched_items.clear();
-            self.selected_index = None;
-            cx.notify();
+    fn update_matches(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.update_matches_keeping_selection(None, window, cx);
+    }
+
+    /// Update the matched items, keeping `selected_id` selected if it still matches.
+    ///
+    /// Matching runs on a background thread when the number of candidates reaches
+    /// [`CommandPaletteConfig::background_match_threshold`].
+    fn update_matches_keeping_selection(
+        &mut self,
+        selected_id: Option<SharedString>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        // Invalidate any pending background match
+        self.match_generation += 1;
+        self._match_task = Task::ready(());
+
+        if self.cheatsheet {
+            let items = self.cheatsheet_items();
+            let len = items.len();
+            self.apply_matches(items, len, 0, selected_id, cx);
             return;
         }
-        let mut static_items: Vec<CommandPaletteItem> = self.static_items.clone();
-        let mut async_only_items: Vec<CommandPaletteItem> = Vec::new();
 
-        let static_ids: std::collections::HashSet<_> =
-            static_items.iter().map(|i| i.id.to_string()).collect();
+        if self.query.len() == 1 {
+            self.apply_matches(Vec::new(), 0, 0, None, cx);
+            return;
+        }
+        let mut static_items: Vec<(usize, CommandPaletteItem)> = self.static_items.clone();
+        let mut async_only_items: Vec<(usize, CommandPaletteItem)> = Vec::new();
 
-        for (id, item) in &self.async_items {
-            if static_ids.contains(id) {
-                if let Some(pos) = static_items
-                    .iter()
-                    .position(|i| i.id.as_ref() == id.as_str())
-                {
-                    static_items[pos] = item.clone();
-                }
+        // An async item replaces the static item with the same id of the same source
+        for ((source, id), item) in &self.async_items {
+            if let Some(pos) = static_items
+                .iter()
+                .position(|(s, i)| s == source && i.id.as_ref() == id.as_str())
+            {
+                static_items[pos].1 = item.clone();
             } else {
-                async_only_items.push(item.clone());
+                async_only_items.push((*source, item.clone()));
             }
         }
 
-        let mut matched_static: Vec<MatchedItem> = static_items
-            .into_iter()
-            .filter_map(|item| {
-                self.matcher
-                    .match_item(&self.query, &item)
-                    .map(|match_info| MatchedItem::new(item, match_info))
-            })
-            .collect();
+        if let Some(category) = &self.category_filter {
+            static_items.retain(|(_, item)| &item.category == category);
+            async_only_items.retain(|(_, item)| &item.category == category);
+        }
 
-        let mut matched_async: Vec<MatchedItem> = async_only_items
-            .into_iter()
-            .filter_map(|item| {
-                self.matcher
-                    .match_item(&self.query, &item)
-                    .map(|match_info| MatchedItem::new(item, match_info))
-            })
-            .collect();
+        let candidates = MatchCandidates {
+            query: self.query.clone(),
+            static_items,
+            async_items: async_only_items,
+            max_results: self.config.max_results,
+            paginated: self.config.page_size.is_some(),
+            grouped: self.sources.len() > 1,
+            recent_ids: if self.query.is_empty() {
+                self.config
+                    .recent_ids
+                    .iter()
+                    .take(self.config.max_recent)
+                    .cloned()
+                    .collect()
+            } else {
+                Vec::new()
+            },
+        };
 
-        if !self.query.is_empty() {
-            matched_static.sort_by(|a, b| {
-                b.match_info
-                    .score
-                    .cmp(&a.match_info.score)
-                    .then_with(|| a.item.title.cmp(&b.item.title))
-            });
-            matched_async.sort_by(|a, b| {
-                b.match_info
-                    .score
-                    .cmp(&a.match_info.score)
-                    .then_with(|| a.item.title.cmp(&b.item.title))
-            });
+        let run_in_background = !self.query.is_empty()
+            && self
+                .config
+                .background_match_threshold
+                .is_some_and(|threshold| candidates.len() >= threshold);
+        if !run_in_background {
+            let (matched_items, static_len, recent_len) = candidates.rank(self.matcher.as_ref());
+            self.apply_matches(matched_items, static_len, recent_len, selected_id, cx);
+            return;
         }
 
-        let max_results = self.config.max_results;
-        let total_len = matched_static.len() + matched_async.len();
-        let total_limit = total_len.min(max_results);
-        let static_limit = matched_static.len().min(total_limit);
-        let async_limit = total_limit.saturating_sub(static_limit);
+        let matcher = self.matcher.clone();
+        let generation = self.match_generation;
+        let task = cx.background_spawn(async move { candidates.rank(matcher.as_ref()) });
+        self._match_task = cx.spawn_in(window, async move |this, window| {
+            let (matched_items, static_len, recent_len) = task.await;
 
-        matched_static.truncate(static_limit);
-        matched_async.truncate(async_limit);
+            _ = this.update_in(window, |this, _, cx| {
+                // Drop results of a query or filter that has changed since
+                if this.match_generation == generation {
+                    this.apply_matches(matched_items, static_len, recent_len, selected_id, cx);
+                }
+            });
+        });
+    }
 
-        self.matched_static_len = matched_static.len();
-        self.matched_items.clear();
-        self.matched_items.extend(matched_static);
-        self.matched_items.extend(matched_async);
+    /// All static items grouped by category, in order of first appearance.
+    fn cheatsheet_items(&self) -> Vec<MatchedItem> {
+        let mut categories: Vec<&SharedString> = Vec::new();
+        for (_, item) in &self.static_items {
+            if !categories.contains(&&item.category) {
+                categories.push(&item.category);
+            }
+        }
 
-        // Reset selection to first item if available
-        self.selected_index = if self.matched_items.is_empty() {
-            None
-        } else {
-            Some(0)
-        };
+        let mut items: Vec<&CommandPaletteItem> =
+            self.static_items.iter().map(|(_, item)| item).collect();
+        items.sort_by_key(|item| {
+            categories
+                .iter()
+                .position(|category| *category == &item.category)
+        });
+        items
+            .into_iter()
+            .map(|item| MatchedItem::new(item.clone(), CommandPaletteMatch::default()))
+            .collect()
+    }
+
+    fn apply_matches(
+        &mut self,
+        matched_items: Vec<MatchedItem>,
+        static_len: usize,
+        recent_len: usize,
+        selected_id: Option<SharedString>,
+        cx: &mut Context<Self>,
+    ) {
+        // Skip the re-render when the same query yields the same items again, e.g. an
+        // async batch identical to the current one. The selection is kept as is.
+        let matched_for = (self.query.clone(), self.cheatsheet);
+        let same_query = self.matched_for == matched_for;
+        let recent_len = recent_len.min(static_len);
+        let unchanged = same_query
+            && self.matched_static_len == static_len
+            && self.matched_recent_len == recent_len
+            && self.matched_items.len() == matched_items.len()
+            && self
+                .matched_items
+                .iter()
+                .zip(&matched_items)
+                .all(|(a, b)| same_match(a, b));
+        let previous_id = self.selected_item().map(|matched| matched.item.id.clone());
+        self.matched_static_len = static_len;
+        self.matched_recent_len = recent_len;
+        self.matched_items = matched_items;
+        if unchanged {
+            return;
+        }
+        self.matched_for = matched_for;
+
+        // Keep the selected item if given. New results for the same query, e.g. async
+        // items arriving, keep the current selection even if they reorder the list.
+        let selected_id = selected_id.or(if same_query { previous_id } else { None });
+        self.selected_index = selection_index(&self.matched_items, selected_id.as_ref());
 
         cx.notify();
     }
@@ -284,24 +831,137 @@ impl CommandPaletteState {
         cx.notify();
     }
 
-    /// Confirm the current selection.
-    pub fn confirm(&mut self, cx: &mut Context<Self>) {
+    /// Confirm the current selection, or open its sub-palette if it has children,
+    /// see [`Self::push_provider`].
+    ///
+    /// In [`SelectionMode::Multi`] this confirms the checked items, or the selected
+    /// one if none is checked.
+    pub fn confirm(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.checked.is_empty() {
+            let parent = self
+                .selected_item()
+                .filter(|matched| matched.item.has_children())
+                .map(|matched| matched.item.clone());
+            if let Some(parent) = parent {
+                self.push_provider(&parent, window, cx);
+                return;
+            }
+        }
+
+        if self.config.selection_mode.is_multi() {
+            self.emit_selected_many(cx);
+        } else {
+            self.emit_selected(false, cx);
+        }
+    }
+
+    /// Confirm the current selection with the secondary action, keeping the palette open.
+    pub fn confirm_secondary(&mut self, cx: &mut Context<Self>) {
+        self.emit_selected(true, cx);
+    }
+
+    /// Confirm an item that is not part of the results, e.g. a quick action.
+    pub fn confirm_item(&mut self, item: CommandPaletteItem, cx: &mut Context<Self>) {
+        if item.disabled {
+            return;
+        }
+
+        self.notify_closed(cx);
+        cx.emit(CommandPaletteEvent::Selected {
+            item,
+            secondary: false,
+        });
+    }
+
+    fn emit_selected(&mut self, secondary: bool, cx: &mut Context<Self>) {
         if let Some(index) = self.selected_index {
             if let Some(matched) = self.matched_items.get(index) {
                 if !matched.item.disabled {
-                    cx.emit(CommandPaletteEvent::Selected {
-                        item: matched.item.clone(),
-                    });
+                    let item = matched.item.clone();
+                    if !secondary {
+                        self.notify_closed(cx);
+                    }
+                    cx.emit(CommandPaletteEvent::Selected { item, secondary });
                 }
             }
         }
     }
 
+    fn emit_selected_many(&mut self, cx: &mut Context<Self>) {
+        let items = if self.checked.is_empty() {
+            match self.selected_item() {
+                Some(matched) if !matched.item.disabled => vec![matched.item.clone()],
+                _ => return,
+            }
+        } else {
+            self.checked.clone()
+        };
+
+        self.notify_closed(cx);
+        cx.emit(CommandPaletteEvent::SelectedMany { items });
+    }
+
+    /// Returns the items checked in [`SelectionMode::Multi`], in the order they were checked.
+    pub fn checked_items(&self) -> &[CommandPaletteItem] {
+        &self.checked
+    }
+
+    /// Returns true if the item with `id` is checked.
+    pub fn is_checked(&self, id: &SharedString) -> bool {
+        self.checked.iter().any(|item| &item.id == id)
+    }
+
+    /// Check or uncheck the selected item, in [`SelectionMode::Multi`].
+    ///
+    /// Disabled items can't be checked.
+    pub fn toggle_checked(&mut self, cx: &mut Context<Self>) {
+        if self.config.selection_mode != SelectionMode::Multi {
+            return;
+        }
+        let Some(matched) = self.selected_item() else {
+            return;
+        };
+        if matched.item.disabled {
+            return;
+        }
+
+        let item = matched.item.clone();
+        if let Some(ix) = self
+            .checked
+            .iter()
+            .position(|checked| checked.id == item.id)
+        {
+            self.checked.remove(ix);
+        } else {
+            self.checked.push(item);
+        }
+        cx.notify();
+    }
+
     /// Dismiss the palette.
     pub fn dismiss(&mut self, cx: &mut Context<Self>) {
+        self.notify_closed(cx);
         cx.emit(CommandPaletteEvent::Dismissed);
     }
 
+    /// Returns true once the palette has closed and its providers got `on_close`.
+    pub(crate) fn is_closed(&self) -> bool {
+        self.closed
+    }
+
+    /// Call the provider's `on_close` hook, at most once per palette.
+    ///
+    /// The providers of the parents of a drilled-in palette are closed too.
+    pub(crate) fn notify_closed(&mut self, cx: &mut Context<Self>) {
+        if !self.closed {
+            self.closed = true;
+            let parent_sources = self.parents.iter().flat_map(|parent| &parent.sources);
+            for source in self.sources.iter().chain(parent_sources) {
+                source.on_close(cx);
+            }
+        }
+    }
+
     /// Select an item at a specific index.
     pub fn select_index(&mut self, index: usize, cx: &mut Context<Self>) {
         if index < self.matched_items.len() {
@@ -310,21 +970,397 @@ impl CommandPaletteState {
         }
     }
 
+    /// Select the matched item with `id`, returning false if it is not among the matches.
+    pub fn select_id(&mut self, id: &SharedString, cx: &mut Context<Self>) -> bool {
+        match self
+            .matched_items
+            .iter()
+            .position(|matched| &matched.item.id == id)
+        {
+            Some(index) => {
+                self.select_index(index, cx);
+                true
+            }
+            None => false,
+        }
+    }
+
     /// Get the currently selected item.
     pub fn selected_item(&self) -> Option<&MatchedItem> {
         self.selected_index.and_then(|i| self.matched_items.get(i))
     }
 }
 
-/// Wrapper to implement CommandMatcher for Arc<dyn CommandMatcher>.
-struct CustomMatcherWrapper(Arc<dyn CommandMatcher + Send + Sync>);
+/// A palette drilled out of with [`CommandPaletteState::push_provider`].
+struct ParentProvider {
+    /// The title of the item drilled into.
+    title: SharedString,
+    /// The provider of the parent palette.
+    provider: Arc<dyn CommandPaletteProvider>,
+    /// The providers queried by the parent palette.
+    sources: Vec<Arc<dyn CommandPaletteProvider>>,
+    /// The query to restore on return, including the mode prefix at the top level.
+    query: String,
+}
+
+/// The index of `selected_id` in `items`, or the first item if it's gone.
+fn selection_index(items: &[MatchedItem], selected_id: Option<&SharedString>) -> Option<usize> {
+    selected_id
+        .and_then(|id| items.iter().position(|matched| &matched.item.id == id))
+        .or(if items.is_empty() { None } else { Some(0) })
+}
 
-impl CommandMatcher for CustomMatcherWrapper {
-    fn match_item(
-        &self,
-        query: &str,
-        item: &CommandPaletteItem,
-    ) -> Option<super::types::CommandPaletteMatch> {
-        self.0.match_item(query, item)
+/// Whether `a` and `b` render the same, comparing everything but the payloads
+/// and child providers by identity.
+fn same_match(a: &MatchedItem, b: &MatchedItem) -> bool {
+    let (item, other) = (&a.item, &b.item);
+    a.source == b.source
+        && a.match_info == b.match_info
+        && item.id == other.id
+        && item.title == other.title
+        && item.subtitle == other.subtitle
+        && item.category == other.category
+        && item.icon.clone().map(IconNamed::path) == other.icon.clone().map(IconNamed::path)
+        && item.shortcut == other.shortcut
+        && item.keywords == other.keywords
+        && item.disabled == other.disabled
+        && item.title_ranges == other.title_ranges
+        && match (&item.payload, &other.payload) {
+            (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+            (a, b) => a.is_none() && b.is_none(),
+        }
+        && match (&item.children_provider, &other.children_provider) {
+            (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+            (a, b) => a.is_none() && b.is_none(),
+        }
+}
+
+/// Items to match against a query, detached from the state so matching can run
+/// on a background thread.
+struct MatchCandidates {
+    query: String,
+    /// The items with the index of their source.
+    static_items: Vec<(usize, CommandPaletteItem)>,
+    async_items: Vec<(usize, CommandPaletteItem)>,
+    max_results: usize,
+    paginated: bool,
+    /// Whether to group the matches by source, in a combined palette.
+    grouped: bool,
+    /// Ids of the recent items to move to the front, most recent first.
+    recent_ids: Vec<SharedString>,
+}
+
+impl MatchCandidates {
+    fn len(&self) -> usize {
+        self.static_items.len() + self.async_items.len()
+    }
+
+    /// Match, sort and truncate the candidates, returning the matched items
+    /// (static first), the number of static matches and the number of recent
+    /// items at the front.
+    fn rank(
+        self,
+        matcher: &(dyn CommandMatcher + Send + Sync),
+    ) -> (Vec<MatchedItem>, usize, usize) {
+        let query = self.query;
+        let match_items = |items: Vec<(usize, CommandPaletteItem)>| -> Vec<MatchedItem> {
+            items
+                .into_iter()
+                .filter_map(|(source, item)| {
+                    matcher.match_item(&query, &item).map(|match_info| {
+                        let mut matched = MatchedItem::new(item, match_info);
+                        matched.source = source;
+                        matched
+                    })
+                })
+                .collect()
+        };
+
+        let mut matched_static = match_items(self.static_items);
+        let mut matched_async = match_items(self.async_items);
+
+        if !query.is_empty() {
+            matched_static.sort_by(|a, b| {
+                b.match_info
+                    .score
+                    .cmp(&a.match_info.score)
+                    .then_with(|| a.item.title.cmp(&b.item.title))
+            });
+            matched_async.sort_by(|a, b| {
+                b.match_info
+                    .score
+                    .cmp(&a.match_info.score)
+                    .then_with(|| a.item.title.cmp(&b.item.title))
+            });
+        }
+
+        let recent_len = move_recent_first(&mut matched_static, &self.recent_ids);
+
+        let total_len = matched_static.len() + matched_async.len();
+        let total_limit = total_len.min(self.max_results);
+        let static_limit = matched_static.len().min(total_limit);
+        // Paginated results are already bounded by the pages loaded so far
+        let async_limit = if self.paginated {
+            matched_async.len()
+        } else {
+            total_limit.saturating_sub(static_limit)
+        };
+
+        matched_static.truncate(static_limit);
+        matched_async.truncate(async_limit);
+
+        if self.grouped {
+            group_by_source(&mut matched_static[recent_len.min(static_limit)..]);
+            group_by_source(&mut matched_async);
+        }
+
+        let static_len = matched_static.len();
+        matched_static.extend(matched_async);
+        (matched_static, static_len, recent_len.min(static_len))
+    }
+}
+
+/// Move the items of `recent_ids` to the front of `items` in that order, keeping
+/// the order of the others. Returns the number of recent items found.
+fn move_recent_first(items: &mut [MatchedItem], recent_ids: &[SharedString]) -> usize {
+    if recent_ids.is_empty() {
+        return 0;
+    }
+
+    items.sort_by_key(|matched| {
+        recent_ids
+            .iter()
+            .position(|id| id == &matched.item.id)
+            .unwrap_or(recent_ids.len())
+    });
+    items
+        .iter()
+        .take_while(|matched| recent_ids.contains(&matched.item.id))
+        .count()
+}
+
+/// Group `items` by source, ordering the sources by their first, best ranked item
+/// and keeping the order of the items within each source.
+fn group_by_source(items: &mut [MatchedItem]) {
+    let mut order: Vec<usize> = Vec::new();
+    for matched in items.iter() {
+        if !order.contains(&matched.source) {
+            order.push(matched.source);
+        }
+    }
+    items.sort_by_key(|matched| order.iter().position(|source| *source == matched.source));
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc, sync::Arc};
+
+    use super::{group_by_source, move_recent_first, same_match, selection_index};
+    use crate::Root;
+    use crate::command_palette::{
+        CommandPaletteConfig, CommandPaletteEvent, CommandPaletteItem, CommandPaletteMatch,
+        CommandPaletteState, MatchedItem, StaticProvider,
+    };
+    use gpui::{
+        AppContext as _, Context, Empty, IntoElement, Render, SharedString, TestAppContext,
+        VisualTestContext, Window,
+    };
+
+    struct TestView;
+
+    impl Render for TestView {
+        fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+            Empty
+        }
+    }
+
+    fn items(ids: &[&'static str]) -> Vec<MatchedItem> {
+        ids.iter()
+            .map(|id| {
+                MatchedItem::new(
+                    CommandPaletteItem::new(*id, *id),
+                    CommandPaletteMatch::default(),
+                )
+            })
+            .collect()
+    }
+
+    fn matched_ids(state: &CommandPaletteState) -> Vec<&str> {
+        state
+            .matched_items
+            .iter()
+            .map(|matched| matched.item.id.as_ref())
+            .collect()
+    }
+
+    #[test]
+    fn test_selection_follows_reordered_item() {
+        let selected = SharedString::from("b");
+
+        // Async results arrive and move the selected item down the list
+        let reordered = items(&["d", "a", "c", "b"]);
+        assert_eq!(selection_index(&reordered, Some(&selected)), Some(3));
+
+        // The selected item is gone, fall back to the first one
+        let without = items(&["d", "a", "c"]);
+        assert_eq!(selection_index(&without, Some(&selected)), Some(0));
+
+        assert_eq!(selection_index(&reordered, None), Some(0));
+        assert_eq!(selection_index(&[], Some(&selected)), None);
+    }
+
+    #[gpui::test]
+    fn test_reordered_matches_keep_selected_id(cx: &mut TestAppContext) {
+        cx.update(crate::init);
+        let window = cx.add_window(|window, cx| Root::new(cx.new(|_| TestView), window, cx));
+        let cx = &mut VisualTestContext::from_window(window.into(), cx);
+
+        let provider = Arc::new(StaticProvider::new(
+            items(&["a", "b", "c"])
+                .into_iter()
+                .map(|matched| matched.item)
+                .collect(),
+        ));
+        let state = cx.update(|window, cx| {
+            cx.new(|cx| {
+                CommandPaletteState::new(CommandPaletteConfig::default(), provider, window, cx)
+            })
+        });
+        let selected_id = |state: &CommandPaletteState| {
+            state.selected_item().map(|matched| matched.item.id.clone())
+        };
+
+        state.update(cx, |state, cx| {
+            assert!(state.select_id(&"b".into(), cx));
+
+            // New results for the same query, e.g. an async batch, move the item
+            state.apply_matches(items(&["c", "a", "b"]), 3, 0, None, cx);
+            assert_eq!(state.selected_index, Some(2));
+            assert_eq!(selected_id(state), Some("b".into()));
+        });
+
+        // Matching again restores the static order, still on the same item
+        cx.update(|window, cx| state.update(cx, |state, cx| state.update_matches(window, cx)));
+        state.update(cx, |state, _| {
+            assert_eq!(state.selected_index, Some(1));
+            assert_eq!(selected_id(state), Some("b".into()));
+        });
+    }
+
+    #[gpui::test]
+    fn test_push_pop_and_confirm_parent(cx: &mut TestAppContext) {
+        cx.update(crate::init);
+        let window = cx.add_window(|window, cx| Root::new(cx.new(|_| TestView), window, cx));
+        let cx = &mut VisualTestContext::from_window(window.into(), cx);
+
+        let children = Arc::new(StaticProvider::new(vec![
+            CommandPaletteItem::new("child-1", "Child 1"),
+            CommandPaletteItem::new("child-2", "Child 2"),
+        ]));
+        let provider = Arc::new(StaticProvider::new(vec![
+            CommandPaletteItem::new("apple", "Apple"),
+            CommandPaletteItem::new("parent", "Parent").children(children),
+        ]));
+        let state = cx.update(|window, cx| {
+            cx.new(|cx| {
+                CommandPaletteState::new(CommandPaletteConfig::default(), provider, window, cx)
+            })
+        });
+        let selected = Rc::new(RefCell::new(Vec::<SharedString>::new()));
+        cx.update(|_, cx| {
+            let selected = selected.clone();
+            cx.subscribe(&state, move |_, event: &CommandPaletteEvent, _| {
+                if let CommandPaletteEvent::Selected { item, .. } = event {
+                    selected.borrow_mut().push(item.id.clone());
+                }
+            })
+            .detach();
+        });
+
+        // Confirming a parent opens its children instead of selecting it
+        cx.update(|window, cx| {
+            state.update(cx, |state, cx| {
+                state.set_query("par".into(), window, cx);
+                assert_eq!(matched_ids(state), ["parent"]);
+                state.confirm(window, cx);
+            })
+        });
+        cx.run_until_parked();
+        state.update(cx, |state, _| {
+            let breadcrumbs: Vec<&str> = state.breadcrumbs().map(|title| title.as_ref()).collect();
+            assert_eq!(breadcrumbs, ["Parent"]);
+            assert_eq!(state.query, "");
+            assert_eq!(matched_ids(state), ["child-1", "child-2"]);
+        });
+        assert!(selected.borrow().is_empty());
+
+        // Popping restores the parent items and query
+        let query =
+            cx.update(|window, cx| state.update(cx, |state, cx| state.pop_provider(window, cx)));
+        assert_eq!(query.as_deref(), Some("par"));
+        state.update(cx, |state, _| {
+            assert_eq!(state.breadcrumbs().count(), 0);
+            assert_eq!(matched_ids(state), ["parent"]);
+        });
+        let query =
+            cx.update(|window, cx| state.update(cx, |state, cx| state.pop_provider(window, cx)));
+        assert_eq!(query, None);
+
+        // A leaf of the sub-palette is selected
+        let parent = state.update(cx, |state, _| state.matched_items[0].item.clone());
+        cx.update(|window, cx| {
+            state.update(cx, |state, cx| {
+                assert!(state.push_provider(&parent, window, cx));
+                state.confirm(window, cx);
+            })
+        });
+        cx.run_until_parked();
+        assert_eq!(*selected.borrow(), [SharedString::from("child-1")]);
+    }
+
+    #[test]
+    fn test_same_match() {
+        let matched = items(&["a"]).remove(0);
+        assert!(same_match(&matched, &matched.clone()));
+
+        // Same id, but a new title or highlight renders differently
+        let mut renamed = matched.clone();
+        renamed.item.title = "Renamed".into();
+        assert!(!same_match(&matched, &renamed));
+
+        let mut highlighted = matched.clone();
+        highlighted.match_info.title_ranges = vec![(0, 1)];
+        assert!(!same_match(&matched, &highlighted));
+    }
+
+    #[test]
+    fn test_group_by_source() {
+        // Sorted by score, the best match is from source 2
+        let mut matched = items(&["a", "b", "c", "d", "e"]);
+        for (matched, source) in matched.iter_mut().zip([2, 0, 2, 1, 0]) {
+            matched.source = source;
+        }
+
+        group_by_source(&mut matched);
+        let ids: Vec<&str> = matched
+            .iter()
+            .map(|matched| matched.item.id.as_ref())
+            .collect();
+        assert_eq!(ids, ["a", "c", "b", "e", "d"]);
+    }
+
+    #[test]
+    fn test_move_recent_first() {
+        let mut matched = items(&["a", "b", "c", "d"]);
+        let recent: [SharedString; 3] = ["c".into(), "missing".into(), "a".into()];
+
+        assert_eq!(move_recent_first(&mut matched, &recent), 2);
+        let ids: Vec<&str> = matched
+            .iter()
+            .map(|matched| matched.item.id.as_ref())
+            .collect();
+        assert_eq!(ids, ["c", "a", "b", "d"]);
+
+        assert_eq!(move_recent_first(&mut matched, &[]), 0);
     }
 }
@@ -1,7 +1,9 @@
 //! Types for the Command Palette component.
 
-use gpui::SharedString;
+use super::provider::CommandPaletteProvider;
+use gpui::{AnyElement, App, SharedString, Window};
 use std::any::Any;
+use std::collections::HashMap;
 use std::sync::Arc;
 
 /// Configuration for the Command Palette.
@@ -10,26 +12,81 @@ pub struct CommandPaletteConfig {
     /// The keyboard shortcut to open the palette. Default: "cmd-p" on macOS, "ctrl-p" elsewhere.
     /// Set to None to disable the default keybinding.
     pub shortcut: Option<SharedString>,
+    /// The keyboard shortcut to cycle the category filter. Default: "tab".
+    /// Set to None to disable category cycling.
+    pub category_filter_shortcut: Option<SharedString>,
+    /// The keyboard shortcut to toggle the cheatsheet of all commands grouped by category,
+    /// see [`CommandPaletteConfig::cheatsheet`]. Default: None (disabled).
+    pub cheatsheet_shortcut: Option<SharedString>,
+    /// The keyboard shortcut to copy the selected item to the clipboard,
+    /// see [`CommandPaletteConfig::copy_selected`]. Default: None (disabled).
+    pub copy_shortcut: Option<SharedString>,
+    /// What the copy shortcut copies. Default: the item id.
+    pub copy_field: CommandPaletteCopyField,
     /// The matcher implementation to use. Default: Nucleo.
     pub matcher: CommandMatcherKind,
     /// Maximum number of results to display. Default: 50.
+    ///
+    /// When paginated, this caps static results only; async results grow page by page.
     pub max_results: usize,
+    /// Page size for loading async results on scroll, see [`CommandPaletteConfig::paginated`].
+    /// Default: None.
+    pub page_size: Option<usize>,
+    /// Minimum number of candidate items for matching to run on a background thread,
+    /// see [`CommandPaletteConfig::background_match_threshold`]. Default: None.
+    pub background_match_threshold: Option<usize>,
+    /// Delay in milliseconds after the last keystroke before providers are queried for
+    /// async items, see [`CommandPaletteConfig::query_debounce_ms`]. Default: 120.
+    pub query_debounce_ms: u64,
     /// Placeholder text for the search input.
     pub placeholder: SharedString,
     /// Width of the palette in pixels. Default: 560.0.
     pub width: f32,
     /// Maximum height of the palette in pixels. Default: 400.0.
     pub max_height: f32,
+    /// Number of extra rows rendered beyond each edge of the visible range, to avoid
+    /// blank rows while scrolling quickly. Default: 3 (about 30% of the default list height).
+    pub overscan_rows: usize,
     /// Whether to show the footer with keyboard hints. Default: true.
     pub show_footer: bool,
     /// Whether to show category inline with item. Default: true.
     pub show_categories_inline: bool,
+    /// Whether to show placeholder rows below the results while async results are
+    /// loading. Default: false.
+    pub show_loading_skeletons: bool,
     /// Optional title for the commands section when a query is present. Default: "Commands".
     pub commands_section_title: Option<SharedString>,
     /// Optional title for the results section when a query is present. Default: "Search Results".
     pub results_section_title: Option<SharedString>,
     /// Optional status provider for footer text (e.g. indexing status).
     pub status_provider: Option<Arc<dyn Fn(&str) -> Option<SharedString> + Send + Sync>>,
+    /// Optional content rendered above the search input (e.g. a title or scope selector).
+    pub header: Option<Arc<dyn Fn(&mut Window, &mut App) -> AnyElement>>,
+    /// Height of the header area in pixels, used only when `header` is set. Default: 36.0.
+    pub header_height: f32,
+    /// Actions shown in a fixed row under the search input, regardless of the query.
+    /// They are not matched against the query. Default: empty.
+    pub quick_actions: Vec<CommandPaletteItem>,
+    /// Whether Escape clears a non-empty query before dismissing,
+    /// see [`CommandPaletteConfig::escape_clears_first`]. Default: false.
+    pub escape_clears_first: bool,
+    /// Ids of recently used items, most recent first, listed in their own section
+    /// while the query is empty, see [`CommandPaletteConfig::recent`]. Default: empty.
+    pub recent_ids: Vec<SharedString>,
+    /// Optional title for the recent items section. Default: "Recent".
+    pub recent_section_title: Option<SharedString>,
+    /// Maximum number of recent items kept by [`CommandPaletteConfig::record_recent`]
+    /// and shown in the recent section. Default: 5.
+    pub max_recent: usize,
+    /// Whether one item or several checked items are confirmed at once,
+    /// see [`CommandPaletteConfig::selection_mode`]. Default: single.
+    pub selection_mode: SelectionMode,
+    /// Providers switched to by a leading sigil in the query, e.g. `'@'` for symbols,
+    /// see [`CommandPaletteConfig::mode_prefix`]. Default: empty.
+    pub mode_prefixes: HashMap<char, Arc<dyn CommandPaletteProvider>>,
+    /// Footer hint for the secondary confirm binding, see
+    /// [`CommandPaletteConfig::secondary_action`]. Default: None (no hint).
+    pub secondary_action_hint: Option<SharedString>,
 }
 
 impl Default for CommandPaletteConfig {
@@ -41,20 +98,196 @@ impl Default for CommandPaletteConfig {
 
         Self {
             shortcut,
+            category_filter_shortcut: Some("tab".into()),
+            cheatsheet_shortcut: None,
+            copy_shortcut: None,
+            copy_field: CommandPaletteCopyField::default(),
             matcher: CommandMatcherKind::Nucleo,
             max_results: 50,
+            page_size: None,
+            background_match_threshold: None,
+            query_debounce_ms: 120,
             placeholder: "Type a command...".into(),
             width: 560.0,
             max_height: 400.0,
+            overscan_rows: 3,
             show_footer: true,
             show_categories_inline: true,
+            show_loading_skeletons: false,
             commands_section_title: Some("Commands".into()),
             results_section_title: Some("Search Results".into()),
             status_provider: None,
+            header: None,
+            header_height: 36.0,
+            quick_actions: Vec::new(),
+            escape_clears_first: false,
+            recent_ids: Vec::new(),
+            recent_section_title: Some("Recent".into()),
+            max_recent: 5,
+            selection_mode: SelectionMode::default(),
+            mode_prefixes: HashMap::new(),
+            secondary_action_hint: None,
         }
     }
 }
 
+impl CommandPaletteConfig {
+    /// Load async results in pages of `page_size`, requesting the next page from
+    /// [`CommandPaletteProvider::query_page`](super::CommandPaletteProvider::query_page)
+    /// when the list is scrolled near the bottom.
+    pub fn paginated(mut self, page_size: usize) -> Self {
+        self.page_size = Some(page_size.max(1));
+        self
+    }
+
+    /// Match the query on a background thread when there are at least `threshold`
+    /// candidate items, to keep typing responsive with large command sets.
+    ///
+    /// Smaller sets are still matched synchronously, so results show without delay.
+    /// The previous results stay visible until the background match completes.
+    pub fn background_match_threshold(mut self, threshold: usize) -> Self {
+        self.background_match_threshold = Some(threshold);
+        self
+    }
+
+    /// Wait `ms` milliseconds after the last keystroke before querying providers, so a
+    /// backend isn't queried for every character typed quickly. Use 0 to query at once.
+    ///
+    /// Static items are still matched on every keystroke.
+    pub fn query_debounce_ms(mut self, ms: u64) -> Self {
+        self.query_debounce_ms = ms;
+        self
+    }
+
+    /// Enable the cheatsheet, toggled by `shortcut` (e.g. `"secondary-/"`).
+    ///
+    /// The cheatsheet lists every command grouped by category regardless of the query,
+    /// as a reference of what the palette can do. Typing a new query leaves it.
+    pub fn cheatsheet(mut self, shortcut: impl Into<SharedString>) -> Self {
+        self.cheatsheet_shortcut = Some(shortcut.into());
+        self
+    }
+
+    /// Copy the selected item's id or title to the clipboard with `shortcut`
+    /// (e.g. `"secondary-shift-c"`), keeping the palette open.
+    ///
+    /// The footer briefly confirms what was copied.
+    pub fn copy_selected(
+        mut self,
+        shortcut: impl Into<SharedString>,
+        field: CommandPaletteCopyField,
+    ) -> Self {
+        self.copy_shortcut = Some(shortcut.into());
+        self.copy_field = field;
+        self
+    }
+
+    /// Set the content rendered above the search input.
+    pub fn header(
+        mut self,
+        header: impl Fn(&mut Window, &mut App) -> AnyElement + 'static,
+    ) -> Self {
+        self.header = Some(Arc::new(header));
+        self
+    }
+
+    /// Set the actions pinned in a row under the search input, e.g. "New" or "Settings".
+    ///
+    /// Clicking one confirms it like a result, but unlike results they are always
+    /// visible and never filtered by the query.
+    pub fn quick_actions(mut self, actions: Vec<CommandPaletteItem>) -> Self {
+        self.quick_actions = actions;
+        self
+    }
+
+    /// Make the first Escape clear the query and keep the palette open, like a browser's
+    /// find bar. Escape on an empty query dismisses the palette.
+    pub fn escape_clears_first(mut self, escape_clears_first: bool) -> Self {
+        self.escape_clears_first = escape_clears_first;
+        self
+    }
+
+    /// Set the ids of recently used items, most recent first.
+    ///
+    /// While the query is empty, the matching items are listed first under the
+    /// recent section title, followed by the rest under the commands section title.
+    /// Ids without an item are skipped.
+    pub fn recent(mut self, ids: impl IntoIterator<Item = impl Into<SharedString>>) -> Self {
+        self.recent_ids = ids.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Move `id` to the front of the recent items, keeping at most `max_recent`.
+    ///
+    /// Call this with the id of a selected item to list it first next time,
+    /// see also [`super::CommandPalette::record_recent`].
+    pub fn record_recent(&mut self, id: impl Into<SharedString>) {
+        let id = id.into();
+        self.recent_ids.retain(|recent| recent != &id);
+        self.recent_ids.insert(0, id);
+        self.recent_ids.truncate(self.max_recent);
+    }
+
+    /// Set how items are confirmed.
+    ///
+    /// In [`SelectionMode::Multi`], Space checks or unchecks the highlighted item and
+    /// clicking an item toggles it, so queries can't contain spaces. Enter confirms
+    /// the checked items with
+    /// [`CommandPaletteEvent::SelectedMany`](super::CommandPaletteEvent::SelectedMany),
+    /// or the highlighted one if none is checked.
+    pub fn selection_mode(mut self, mode: SelectionMode) -> Self {
+        self.selection_mode = mode;
+        self
+    }
+
+    /// Query `provider` instead of the palette's own when the query starts with
+    /// `prefix`, like `'>'` for commands or `'#'` for tags.
+    ///
+    /// The prefix is stripped from the query the items are matched against and shown
+    /// next to the input while active. Deleting it returns to the default provider.
+    pub fn mode_prefix(mut self, prefix: char, provider: Arc<dyn CommandPaletteProvider>) -> Self {
+        self.mode_prefixes.insert(prefix, provider);
+        self
+    }
+
+    /// Describe the secondary confirm action in the footer, e.g. "to open in split".
+    ///
+    /// `cmd-enter` / `ctrl-enter` and `shift-enter` always confirm with `secondary: true`
+    /// in [`CommandPaletteEvent::Selected`](super::CommandPaletteEvent::Selected), this
+    /// only adds the hint.
+    pub fn secondary_action(mut self, hint: impl Into<SharedString>) -> Self {
+        self.secondary_action_hint = Some(hint.into());
+        self
+    }
+}
+
+/// How items are confirmed, see [`CommandPaletteConfig::selection_mode`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SelectionMode {
+    /// Enter confirms the highlighted item.
+    #[default]
+    Single,
+    /// Items are checked with Space and Enter confirms all of them at once.
+    Multi,
+}
+
+impl SelectionMode {
+    /// Returns true for [`SelectionMode::Multi`].
+    pub fn is_multi(&self) -> bool {
+        matches!(self, Self::Multi)
+    }
+}
+
+/// The part of the selected item copied by [`CommandPaletteConfig::copy_selected`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CommandPaletteCopyField {
+    /// The item id, e.g. a command identifier.
+    #[default]
+    Id,
+    /// The item title, as shown in the list.
+    Title,
+}
+
 /// The type of matcher to use for fuzzy matching.
 #[derive(Clone, Default)]
 pub enum CommandMatcherKind {
@@ -86,8 +319,14 @@ pub struct CommandPaletteItem {
     pub keywords: Vec<SharedString>,
     /// Whether the item is disabled.
     pub disabled: bool,
-    /// Optional payload for custom data.
+    /// Optional payload for custom data, read back with [`CommandPaletteItem::data`].
     pub payload: Option<Arc<dyn Any + Send + Sync>>,
+    /// Byte ranges of the title to highlight, overriding the local matcher ranges.
+    /// Default: None.
+    pub title_ranges: Option<Vec<(usize, usize)>>,
+    /// Provider of a sub-palette opened when the item is confirmed, see
+    /// [`CommandPaletteItem::children`]. Default: None.
+    pub children_provider: Option<Arc<dyn CommandPaletteProvider>>,
 }
 
 impl CommandPaletteItem {
@@ -103,9 +342,20 @@ impl CommandPaletteItem {
             keywords: Vec::new(),
             disabled: false,
             payload: None,
+            title_ranges: None,
+            children_provider: None,
         }
     }
 
+    /// Create a new item carrying `data`, e.g. an enum or a path to act on when selected.
+    pub fn new_with_data<T: Any + Send + Sync>(
+        id: impl Into<SharedString>,
+        title: impl Into<SharedString>,
+        data: T,
+    ) -> Self {
+        Self::new(id, title).payload(data)
+    }
+
     /// Set the subtitle.
     pub fn subtitle(mut self, subtitle: impl Into<SharedString>) -> Self {
         self.subtitle = Some(subtitle.into());
@@ -153,10 +403,51 @@ impl CommandPaletteItem {
         self.payload = Some(Arc::new(payload));
         self
     }
+
+    /// Returns the payload if it is of type `T`.
+    ///
+    /// The payload is shared behind an `Arc`, so cloning an item does not clone it.
+    pub fn data<T: Any>(&self) -> Option<&T> {
+        self.payload.as_ref()?.downcast_ref::<T>()
+    }
+
+    /// Set the byte ranges of the title to highlight, e.g. from a server-side search.
+    ///
+    /// These take precedence over the ranges computed by the local matcher.
+    pub fn title_ranges(mut self, ranges: Vec<(usize, usize)>) -> Self {
+        self.title_ranges = Some(ranges);
+        self
+    }
+
+    /// Open a sub-palette listing the items of `provider` when this item is confirmed,
+    /// instead of selecting it, e.g. "Go to Symbol..." listing the symbols.
+    ///
+    /// The query is cleared and the item title shown as a breadcrumb. Escape returns
+    /// to the parent items.
+    pub fn children(mut self, provider: Arc<dyn CommandPaletteProvider>) -> Self {
+        self.children_provider = Some(provider);
+        self
+    }
+
+    /// Returns true if confirming the item opens a sub-palette.
+    pub fn has_children(&self) -> bool {
+        self.children_provider.is_some()
+    }
+}
+
+/// The item field that produced a match.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CommandPaletteMatchField {
+    /// Matched the visible title.
+    Title,
+    /// Matched the subtitle.
+    Subtitle,
+    /// Matched a hidden keyword, e.g. an alias of the command.
+    Keyword(SharedString),
 }
 
 /// A match result from the command matcher.
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, PartialEq)]
 pub struct CommandPaletteMatch {
     /// The match score (higher is better).
     pub score: i64,
@@ -164,6 +455,9 @@ pub struct CommandPaletteMatch {
     pub title_ranges: Vec<(usize, usize)>,
     /// Highlight ranges in the subtitle (start, end).
     pub subtitle_ranges: Vec<(usize, usize)>,
+    /// The field that produced the best match, `None` for an empty query or
+    /// when the matcher doesn't report it.
+    pub matched_field: Option<CommandPaletteMatchField>,
 }
 
 impl CommandPaletteMatch {
@@ -173,9 +467,16 @@ impl CommandPaletteMatch {
             score,
             title_ranges: Vec::new(),
             subtitle_ranges: Vec::new(),
+            matched_field: None,
         }
     }
 
+    /// Set the field that produced the match.
+    pub fn with_matched_field(mut self, field: CommandPaletteMatchField) -> Self {
+        self.matched_field = Some(field);
+        self
+    }
+
     /// Set the title highlight ranges.
     pub fn with_title_ranges(mut self, ranges: Vec<(usize, usize)>) -> Self {
         self.title_ranges = ranges;
@@ -202,10 +503,17 @@ pub struct MatchedItem {
     pub item: CommandPaletteItem,
     /// Match information including score and highlight ranges.
     pub match_info: CommandPaletteMatch,
+    /// The index of the provider the item came from in a combined palette, see
+    /// [`super::CommandPalette::open_combined`]. Always 0 otherwise.
+    pub source: usize,
 }
 
 impl MatchedItem {
     pub fn new(item: CommandPaletteItem, match_info: CommandPaletteMatch) -> Self {
-        Self { item, match_info }
+        Self {
+            item,
+            match_info,
+            source: 0,
+        }
     }
 }
@@ -2,32 +2,76 @@
 
 use super::provider::CommandPaletteProvider;
 use super::state::{CommandPaletteEvent, CommandPaletteState};
-use super::types::{CommandPaletteConfig, MatchedItem};
-use super::{reveal_animation_duration, reveal_delay};
+use super::types::{
+    CommandPaletteConfig, CommandPaletteCopyField, CommandPaletteItem, CommandPaletteMatchField,
+    MatchedItem,
+};
+use super::{
+    CopySelected, CycleCategory, ToggleCheatsheet, ToggleChecked, reveal_animation_duration,
+    reveal_delay,
+};
 use crate::actions::{Cancel, Confirm, SelectDown, SelectUp};
 use crate::animation::spring_invoke_animation;
 use crate::global_state::GlobalState;
 use crate::input::{Input, InputEvent, InputState};
 use crate::kbd::Kbd;
+use crate::skeleton::Skeleton;
 use crate::{
-    ActiveTheme, Icon, IconName, Sizable, Size, SurfaceContext, SurfacePreset,
+    ActiveTheme, Icon, IconName, Sizable, Size, StyledExt as _, SurfaceContext, SurfacePreset,
     VirtualListScrollHandle, WindowExt as _, h_flex, v_flex, v_virtual_list,
 };
 use gpui::{
-    Animation, AnimationExt, App, AppContext as _, Context, ElementId, Entity, FocusHandle,
-    Focusable, InteractiveElement, IntoElement, KeyBinding, ParentElement, Pixels, Render,
-    ScrollStrategy, SharedString, Size as GpuiSize, Styled, Subscription, Task, Window, div,
-    prelude::FluentBuilder, px,
+    Animation, AnimationExt, App, AppContext as _, ClipboardItem, Context, Div, ElementId, Entity,
+    FocusHandle, Focusable, InteractiveElement, IntoElement, KeyBinding, ParentElement, Pixels,
+    Render, ScrollStrategy, SharedString, Size as GpuiSize, Stateful, Styled, Subscription, Task,
+    Window, div, prelude::FluentBuilder, px,
 };
 use std::rc::Rc;
 use std::sync::Arc;
+use std::time::Duration;
 
 const CONTEXT: &str = "CommandPalette";
+/// Key context of a palette in [`SelectionMode::Multi`](super::SelectionMode::Multi),
+/// where Space checks items.
+const MULTI_SELECT_CONTEXT: &str = "CommandPalette multi_select";
 
 // Height constants for layout calculations
 const HEADER_HEIGHT: f32 = 52.0;
 const FOOTER_HEIGHT: f32 = 36.0;
 const SECTION_HEADER_HEIGHT: f32 = 28.0;
+const QUICK_ACTIONS_HEIGHT: f32 = 40.0;
+/// How long the footer confirms a copied item.
+const COPIED_NOTICE_DURATION: Duration = Duration::from_millis(1500);
+/// Rows from the end of the list at which a paginated palette loads its next page.
+const PAGE_LOAD_THRESHOLD_ROWS: usize = 5;
+/// Number of placeholder rows shown while async results load.
+const LOADING_SKELETON_ROWS: usize = 3;
+
+/// Height of everything except the results list: the optional header area,
+/// the search input, the optional quick actions row and the optional footer.
+pub(super) fn chrome_height(config: &CommandPaletteConfig) -> Pixels {
+    let footer_height = if config.show_footer {
+        px(FOOTER_HEIGHT)
+    } else {
+        px(0.)
+    };
+    collapsed_chrome_height(config) + footer_height
+}
+
+/// Height of the chrome shown before the results list is revealed.
+fn collapsed_chrome_height(config: &CommandPaletteConfig) -> Pixels {
+    let header_height = if config.header.is_some() {
+        px(config.header_height)
+    } else {
+        px(0.)
+    };
+    let quick_actions_height = if config.quick_actions.is_empty() {
+        px(0.)
+    } else {
+        px(QUICK_ACTIONS_HEIGHT)
+    };
+    header_height + px(HEADER_HEIGHT) + quick_actions_height
+}
 const EMPTY_STATE_HEIGHT: f32 = 120.0;
 
 /// Monotonic spring-like easing (critically damped) to avoid bounce oscillation.
@@ -38,20 +82,76 @@ fn gentle_spring(delta: f32) -> f32 {
     value.clamp(0.0, 1.0)
 }
 
+/// Where the palette is placed relative to the window.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum CommandPalettePlacement {
+    /// Centered dialog overlay, growing downward as results arrive.
+    #[default]
+    Centered,
+    /// Anchored below a trigger, growing downward as results arrive.
+    Below,
+    /// Anchored above a trigger, kept at its full height so the bottom edge
+    /// stays attached to the trigger.
+    Above,
+}
+
 /// A render row for the command palette list.
 #[derive(Clone)]
 enum CommandPaletteRow {
     Header(SharedString),
     Item(usize),
+    /// A placeholder for an async result still loading.
+    Skeleton(usize),
 }
 
-pub(crate) fn init(cx: &mut App) {
+pub(crate) fn init(cx: &mut App, config: &CommandPaletteConfig) {
     cx.bind_keys([
         KeyBinding::new("escape", Cancel, Some(CONTEXT)),
         KeyBinding::new("enter", Confirm { secondary: false }, Some(CONTEXT)),
+        KeyBinding::new(
+            "secondary-enter",
+            Confirm { secondary: true },
+            Some(CONTEXT),
+        ),
+        KeyBinding::new("shift-enter", Confirm { secondary: true }, Some(CONTEXT)),
         KeyBinding::new("up", SelectUp, Some(CONTEXT)),
         KeyBinding::new("down", SelectDown, Some(CONTEXT)),
+        KeyBinding::new(
+            "space",
+            ToggleChecked,
+            Some("CommandPalette && multi_select"),
+        ),
     ]);
+
+    if let Some(shortcut) = &config.category_filter_shortcut {
+        if gpui::Keystroke::parse(shortcut).is_ok() {
+            cx.bind_keys([KeyBinding::new(
+                shortcut.as_ref(),
+                CycleCategory,
+                Some(CONTEXT),
+            )]);
+        }
+    }
+
+    if let Some(shortcut) = &config.cheatsheet_shortcut {
+        if gpui::Keystroke::parse(shortcut).is_ok() {
+            cx.bind_keys([KeyBinding::new(
+                shortcut.as_ref(),
+                ToggleCheatsheet,
+                Some(CONTEXT),
+            )]);
+        }
+    }
+
+    if let Some(shortcut) = &config.copy_shortcut {
+        if gpui::Keystroke::parse(shortcut).is_ok() {
+            cx.bind_keys([KeyBinding::new(
+                shortcut.as_ref(),
+                CopySelected,
+                Some(CONTEXT),
+            )]);
+        }
+    }
 }
 
 /// The Command Palette view component.
@@ -64,14 +164,24 @@ pub struct CommandPaletteView {
     focus_handle: FocusHandle,
     /// Scroll handle for the list.
     scroll_handle: VirtualListScrollHandle,
-    /// Item height for virtualization.
+    /// Height of items with a subtitle, for virtualization.
     item_height: Pixels,
+    /// Height of items with a title only.
+    single_line_item_height: Pixels,
+    /// Placement of the palette surface.
+    placement: CommandPalettePlacement,
     /// Tracks whether we've focused the input once after open.
     did_focus: bool,
     /// Whether the results list has been revealed.
     list_revealed: bool,
     /// Task for delayed reveal.
     _reveal_task: Option<Task<()>>,
+    /// Footer confirmation after copying the selected item.
+    copied_notice: Option<SharedString>,
+    /// Task clearing `copied_notice`.
+    _copied_task: Option<Task<()>>,
+    /// Number of parent palettes when the input was last synced to the state.
+    depth: usize,
     /// Subscriptions.
     _subscriptions: Vec<Subscription>,
 }
@@ -83,32 +193,109 @@ impl CommandPaletteView {
         provider: Arc<dyn CommandPaletteProvider>,
         window: &mut Window,
         cx: &mut Context<Self>,
+    ) -> Self {
+        Self::new_with_scopes(
+            config,
+            vec![(SharedString::default(), provider)],
+            0,
+            window,
+            cx,
+        )
+    }
+
+    /// Create a new command palette view with a scope selector to switch between providers.
+    ///
+    /// See [`CommandPaletteState::new_with_scopes`].
+    pub fn new_with_scopes(
+        config: CommandPaletteConfig,
+        scopes: Vec<(SharedString, Arc<dyn CommandPaletteProvider>)>,
+        initial: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
     ) -> Self {
         let placeholder = config.placeholder.clone();
+        let state =
+            cx.new(|cx| CommandPaletteState::new_with_scopes(config, scopes, initial, window, cx));
+        Self::with_state(state, placeholder, window, cx)
+    }
 
-        let state = cx.new(|cx| CommandPaletteState::new(config, provider, window, cx));
+    /// Create a new command palette view showing the results of all `providers` at once.
+    ///
+    /// See [`CommandPaletteState::new_combined`].
+    pub fn new_combined(
+        config: CommandPaletteConfig,
+        providers: Vec<Arc<dyn CommandPaletteProvider>>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let placeholder = config.placeholder.clone();
+        let state = cx.new(|cx| CommandPaletteState::new_combined(config, providers, window, cx));
+        Self::with_state(state, placeholder, window, cx)
+    }
 
+    fn with_state(
+        state: Entity<CommandPaletteState>,
+        placeholder: SharedString,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
         let input_state = cx.new(|cx| InputState::new(window, cx).placeholder(placeholder));
 
         let focus_handle = cx.focus_handle();
+        // Two-line rows: a control height with a gap above and below
+        let metrics = cx.theme().size_metrics(Size::Medium);
+        let item_height = metrics.control_height + metrics.gap * 2.;
+        let single_line_item_height = metrics.control_height + metrics.gap;
 
         // Subscribe to input changes
         let input_subscription = cx.subscribe_in(&input_state, window, Self::on_input_event);
 
         // Subscribe to state events
         let state_subscription = cx.subscribe_in(&state, window, Self::on_state_event);
+        let depth_subscription = cx.observe_in(&state, window, Self::on_state_changed);
 
         Self {
             state,
             input_state,
             focus_handle,
             scroll_handle: VirtualListScrollHandle::new(),
-            item_height: px(48.),
+            item_height,
+            single_line_item_height,
+            placement: CommandPalettePlacement::default(),
             did_focus: false,
             list_revealed: false,
             _reveal_task: None,
-            _subscriptions: vec![input_subscription, state_subscription],
+            copied_notice: None,
+            _copied_task: None,
+            depth: 0,
+            _subscriptions: vec![input_subscription, state_subscription, depth_subscription],
+        }
+    }
+
+    /// Set the placement of the palette surface.
+    pub(crate) fn placement(mut self, placement: CommandPalettePlacement) -> Self {
+        self.placement = placement;
+        self
+    }
+
+    /// Replace the search input text and run `query`.
+    pub(crate) fn set_query(&mut self, query: String, window: &mut Window, cx: &mut Context<Self>) {
+        self.input_state.update(cx, |input, cx| {
+            input.set_value(query.clone(), window, cx);
+        });
+        self.state.update(cx, |state, cx| {
+            state.set_query(query, window, cx);
+        });
+        self.scroll_handle.scroll_to_item(0, ScrollStrategy::Top);
+    }
+
+    /// Select the matched item with `id` and scroll it into view.
+    pub(crate) fn select_id(&mut self, id: &SharedString, cx: &mut Context<Self>) -> bool {
+        let selected = self.state.update(cx, |state, cx| state.select_id(id, cx));
+        if selected {
+            self.scroll_to_selected(cx);
         }
+        selected
     }
 
     fn schedule_reveal(&mut self, cx: &mut Context<Self>) {
@@ -147,9 +334,7 @@ impl CommandPaletteView {
                 });
             }
             InputEvent::PressEnter { .. } => {
-                self.state.update(cx, |state, cx| {
-                    state.confirm(cx);
-                });
+                self.confirm(false, window, cx);
             }
             _ => {}
         }
@@ -162,25 +347,95 @@ impl CommandPaletteView {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        // Close the dialog on selection or dismissal
+        // Close the dialog on selection or dismissal; secondary selections keep it open
         match event {
-            CommandPaletteEvent::Selected { .. } | CommandPaletteEvent::Dismissed => {
+            CommandPaletteEvent::Selected {
+                secondary: false, ..
+            }
+            | CommandPaletteEvent::SelectedMany { .. }
+            | CommandPaletteEvent::Dismissed => {
                 window.close_dialog(cx);
             }
+            CommandPaletteEvent::Selected {
+                secondary: true, ..
+            } => {}
         }
         // Forward events
         cx.emit(event.clone());
     }
 
-    fn on_action_cancel(&mut self, _: &Cancel, _: &mut Window, cx: &mut Context<Self>) {
+    /// Show the query of the palette entered or returned to, however it was pushed
+    /// or popped.
+    fn on_state_changed(
+        &mut self,
+        state: Entity<CommandPaletteState>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let state = state.read(cx);
+        let depth = state.breadcrumbs().count();
+        if depth == self.depth {
+            return;
+        }
+        self.depth = depth;
+
+        let query = state.input_query();
+        self.input_state.update(cx, |input, cx| {
+            input.set_value(query, window, cx);
+        });
+        self.scroll_handle.scroll_to_item(0, ScrollStrategy::Top);
+    }
+
+    fn on_action_cancel(&mut self, _: &Cancel, window: &mut Window, cx: &mut Context<Self>) {
+        let state = self.state.read(cx);
+        if state.config.escape_clears_first && (!state.query.is_empty() || state.mode().is_some()) {
+            self.input_state.update(cx, |input, cx| {
+                input.set_value("", window, cx);
+            });
+            self.state.update(cx, |state, cx| {
+                state.set_query(String::new(), window, cx);
+            });
+            return;
+        }
+
+        // A drilled-in palette returns to its parent instead of dismissing
+        let parent_query = self
+            .state
+            .update(cx, |state, cx| state.pop_provider(window, cx));
+        if parent_query.is_some() {
+            return;
+        }
+
         self.state.update(cx, |state, cx| {
             state.dismiss(cx);
         });
     }
 
-    fn on_action_confirm(&mut self, _: &Confirm, _: &mut Window, cx: &mut Context<Self>) {
+    fn on_action_confirm(&mut self, action: &Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        self.confirm(action.secondary, window, cx);
+    }
+
+    /// Confirm the selected item, or open its sub-palette if it has children.
+    ///
+    /// Checked items in multi-select mode are confirmed as they are.
+    fn confirm(&mut self, secondary: bool, window: &mut Window, cx: &mut Context<Self>) {
+        self.state.update(cx, |state, cx| {
+            if secondary {
+                state.confirm_secondary(cx);
+            } else {
+                state.confirm(window, cx);
+            }
+        });
+    }
+
+    fn on_action_toggle_checked(
+        &mut self,
+        _: &ToggleChecked,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
         self.state.update(cx, |state, cx| {
-            state.confirm(cx);
+            state.toggle_checked(cx);
         });
     }
 
@@ -198,6 +453,82 @@ impl CommandPaletteView {
         self.scroll_to_selected(cx);
     }
 
+    fn on_action_cycle_category(
+        &mut self,
+        _: &CycleCategory,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.state.update(cx, |state, cx| {
+            state.cycle_category(window, cx);
+        });
+        self.scroll_handle.scroll_to_item(0, ScrollStrategy::Top);
+    }
+
+    fn on_action_toggle_cheatsheet(
+        &mut self,
+        _: &ToggleCheatsheet,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.state.read(cx).config.cheatsheet_shortcut.is_none() {
+            return;
+        }
+
+        self.state.update(cx, |state, cx| {
+            state.toggle_cheatsheet(window, cx);
+        });
+        self.scroll_handle.scroll_to_item(0, ScrollStrategy::Top);
+    }
+
+    fn on_action_copy_selected(
+        &mut self,
+        _: &CopySelected,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let state = self.state.read(cx);
+        if state.config.copy_shortcut.is_none() {
+            return;
+        }
+        let Some(matched) = state.selected_item() else {
+            return;
+        };
+
+        let text = match state.config.copy_field {
+            CommandPaletteCopyField::Id => matched.item.id.clone(),
+            CommandPaletteCopyField::Title => matched.item.title.clone(),
+        };
+        cx.write_to_clipboard(ClipboardItem::new_string(text.to_string()));
+
+        self.copied_notice = Some(format!("Copied {}", text).into());
+        self._copied_task = Some(cx.spawn(async move |this, cx| {
+            cx.background_executor().timer(COPIED_NOTICE_DURATION).await;
+
+            if let Some(this) = this.upgrade() {
+                this.update(cx, |this, cx| {
+                    this.copied_notice = None;
+                    this._copied_task = None;
+                    cx.notify();
+                });
+            }
+        }));
+        cx.notify();
+    }
+
+    /// Load the next page of results after this frame, when paginated.
+    fn request_next_page(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if !self.state.read(cx).has_more_pages() {
+            return;
+        }
+
+        cx.defer_in(window, |view, window, cx| {
+            view.state.update(cx, |state, cx| {
+                state.load_next_page(window, cx);
+            });
+        });
+    }
+
     fn scroll_to_selected(&mut self, cx: &App) {
         let state = self.state.read(cx);
         if let Some(index) = state.selected_index {
@@ -207,6 +538,15 @@ impl CommandPaletteView {
         }
     }
 
+    /// Row height of an item, taller when it shows a subtitle line.
+    fn item_height_for(&self, item: &CommandPaletteItem) -> Pixels {
+        if item.subtitle.is_some() {
+            self.item_height
+        } else {
+            self.single_line_item_height
+        }
+    }
+
     fn render_item(
         &self,
         item: &MatchedItem,
@@ -219,19 +559,33 @@ impl CommandPaletteView {
         let item_data = item.item.clone();
         let match_info = item.match_info.clone();
         let disabled = item_data.disabled;
+        let has_children = item_data.has_children();
         let show_inline_category = show_category && !item_data.category.is_empty();
+        // Explain matches that came from a hidden keyword rather than the visible text
+        let matched_keyword = match &match_info.matched_field {
+            Some(CommandPaletteMatchField::Keyword(keyword)) => Some(keyword.clone()),
+            _ => None,
+        };
 
         let shortcut_element = item_data
             .shortcut
             .as_ref()
             .and_then(|s| gpui::Keystroke::parse(s).ok().map(|k| Kbd::new(k)));
         let has_shortcut = shortcut_element.is_some();
+        let title_ranges = item_data
+            .title_ranges
+            .as_deref()
+            .unwrap_or(&match_info.title_ranges);
+        let metrics = cx.theme().size_metrics(Size::Medium);
+        let state = self.state.read(cx);
+        let multi_select = state.config.selection_mode.is_multi();
+        let checked = multi_select && state.is_checked(&item_data.id);
 
         h_flex()
             .id(SharedString::from(format!("cmd-item-{}", item_index)))
             .w_full()
-            .h(self.item_height)
-            .px_3()
+            .h(self.item_height_for(&item_data))
+            .px(metrics.padding_x)
             .gap_3()
             .items_center()
             .rounded(cx.theme().radius)
@@ -241,6 +595,7 @@ impl CommandPaletteView {
             .when(selected && !disabled, |this| {
                 this.bg(cx.theme().list_active)
                     .text_color(cx.theme().accent_foreground)
+                    .focus_ring(cx)
             })
             .when(!selected && !disabled, |this| {
                 this.hover(|this| this.bg(cx.theme().list_hover))
@@ -249,14 +604,44 @@ impl CommandPaletteView {
                 let index = item_index;
                 this.on_mouse_down(
                     gpui::MouseButton::Left,
-                    cx.listener(move |view, _, _, cx| {
+                    cx.listener(move |view, _, window, cx| {
                         view.state.update(cx, |state, cx| {
                             state.select_index(index, cx);
-                            state.confirm(cx);
+                            if multi_select {
+                                state.toggle_checked(cx);
+                            }
                         });
+                        if !multi_select {
+                            view.confirm(false, window, cx);
+                        }
                     }),
                 )
             })
+            // Checkbox, reserved on every row so titles stay aligned
+            .when(multi_select, |this| {
+                this.child(
+                    div()
+                        .flex()
+                        .flex_shrink_0()
+                        .size_4()
+                        .items_center()
+                        .justify_center()
+                        .rounded(cx.theme().radius / 2.)
+                        .border_1()
+                        .border_color(if checked {
+                            cx.theme().primary
+                        } else {
+                            cx.theme().input
+                        })
+                        .when(checked, |this| {
+                            this.bg(cx.theme().primary).child(
+                                Icon::new(IconName::Check)
+                                    .size_3()
+                                    .text_color(cx.theme().primary_foreground),
+                            )
+                        }),
+                )
+            })
             // Icon
             .when_some(item_data.icon, |this, icon| {
                 this.child(
@@ -271,20 +656,38 @@ impl CommandPaletteView {
                     .flex_1()
                     .overflow_hidden()
                     .child(
-                        div()
-                            .text_sm()
-                            .font_weight(gpui::FontWeight::MEDIUM)
-                            .text_color(if selected {
-                                cx.theme().accent_foreground
-                            } else {
-                                cx.theme().foreground
-                            })
-                            .truncate()
-                            .child(self.render_highlighted_text(
-                                &item_data.title,
-                                &match_info.title_ranges,
-                                cx,
-                            )),
+                        h_flex()
+                            .gap_2()
+                            .child(
+                                div()
+                                    .min_w_0()
+                                    .text_sm()
+                                    .font_weight(gpui::FontWeight::MEDIUM)
+                                    .text_color(if selected {
+                                        cx.theme().accent_foreground
+                                    } else {
+                                        cx.theme().foreground
+                                    })
+                                    .truncate()
+                                    .child(self.render_highlighted_text(
+                                        &item_data.title,
+                                        title_ranges,
+                                        cx,
+                                    )),
+                            )
+                            .when_some(matched_keyword, |this, keyword| {
+                                this.child(
+                                    div()
+                                        .flex_shrink_0()
+                                        .text_xs()
+                                        .text_color(if selected {
+                                            cx.theme().accent_foreground.opacity(0.6)
+                                        } else {
+                                            cx.theme().muted_foreground.opacity(0.8)
+                                        })
+                                        .child(format!("matched: {}", keyword)),
+                                )
+                            }),
                     )
                     .when_some(item_data.subtitle.clone(), |this, subtitle| {
                         this.child(
@@ -316,6 +719,224 @@ impl CommandPaletteView {
                         .when_some(shortcut_element, |this, kbd| this.child(kbd)),
                 )
             })
+            .when(has_children, |this| {
+                this.child(
+                    Icon::new(IconName::ChevronRight)
+                        .size_4()
+                        .text_color(cx.theme().muted_foreground),
+                )
+            })
+    }
+
+    fn render_mode_chip(&self, prefix: char, cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .flex_shrink_0()
+            .px_2()
+            .py_0p5()
+            .rounded(cx.theme().radius)
+            .bg(cx.theme().secondary)
+            .text_xs()
+            .font_weight(gpui::FontWeight::SEMIBOLD)
+            .text_color(cx.theme().secondary_foreground)
+            .child(prefix.to_string())
+    }
+
+    fn render_breadcrumbs(
+        &self,
+        breadcrumbs: Vec<SharedString>,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        h_flex()
+            .id("command-palette-breadcrumbs")
+            .flex_shrink_0()
+            .gap_1()
+            .items_center()
+            .text_xs()
+            .text_color(cx.theme().muted_foreground)
+            .children(breadcrumbs.into_iter().map(|title| {
+                h_flex()
+                    .gap_1()
+                    .items_center()
+                    .child(
+                        div()
+                            .px_2()
+                            .py_0p5()
+                            .rounded(cx.theme().radius)
+                            .bg(cx.theme().secondary)
+                            .text_color(cx.theme().secondary_foreground)
+                            .child(title),
+                    )
+                    .child(Icon::new(IconName::ChevronRight).size_3())
+            }))
+    }
+
+    /// A placeholder row the height of a two-line item, shown while async results load.
+    fn render_skeleton_row(&self, ix: usize, cx: &App) -> impl IntoElement {
+        let metrics = cx.theme().size_metrics(Size::Medium);
+        // Vary the title widths so the rows don't look like a single block
+        let title_width = [0.6, 0.45, 0.7][ix % 3];
+
+        h_flex()
+            .w_full()
+            .h(self.item_height)
+            .px(metrics.padding_x)
+            .gap_3()
+            .items_center()
+            .my_1()
+            .child(Skeleton::new().size_4().rounded(cx.theme().radius))
+            .child(
+                v_flex()
+                    .flex_1()
+                    .gap_1p5()
+                    .child(
+                        Skeleton::new()
+                            .w(gpui::relative(title_width))
+                            .h_3()
+                            .rounded(cx.theme().radius),
+                    )
+                    .child(
+                        Skeleton::new()
+                            .secondary()
+                            .w(gpui::relative(title_width / 2.))
+                            .h_2p5()
+                            .rounded(cx.theme().radius),
+                    ),
+            )
+    }
+
+    /// A label chip of the header, highlighted when `active`.
+    fn render_chip(
+        id: impl Into<ElementId>,
+        label: SharedString,
+        active: bool,
+        cx: &App,
+    ) -> Stateful<Div> {
+        h_flex()
+            .id(id)
+            .flex_shrink_0()
+            .gap_1()
+            .px_2()
+            .py_0p5()
+            .items_center()
+            .rounded(cx.theme().radius)
+            .text_xs()
+            .map(|this| {
+                if active {
+                    this.bg(cx.theme().secondary)
+                        .text_color(cx.theme().secondary_foreground)
+                } else {
+                    this.text_color(cx.theme().muted_foreground)
+                }
+            })
+            .child(label)
+    }
+
+    fn render_category_chip(
+        &self,
+        category: SharedString,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        Self::render_chip("command-palette-category-chip", category, true, cx)
+            .child(
+                Icon::new(IconName::Close)
+                    .size_3()
+                    .text_color(cx.theme().muted_foreground),
+            )
+            .on_mouse_down(
+                gpui::MouseButton::Left,
+                cx.listener(|view, _, window, cx| {
+                    cx.stop_propagation();
+                    view.state.update(cx, |state, cx| {
+                        state.set_category_filter(None, window, cx);
+                    });
+                }),
+            )
+    }
+
+    fn render_scope_chips(
+        &self,
+        scopes: Vec<SharedString>,
+        active_scope: usize,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        h_flex()
+            .id("command-palette-scopes")
+            .flex_shrink_0()
+            .gap_1()
+            .children(scopes.into_iter().enumerate().map(|(ix, scope)| {
+                let active = ix == active_scope;
+                Self::render_chip(("command-palette-scope", ix), scope, active, cx).when(
+                    !active,
+                    |this| {
+                        this.cursor_pointer()
+                            .hover(|this| this.bg(cx.theme().list_hover))
+                            .on_mouse_down(
+                                gpui::MouseButton::Left,
+                                cx.listener(move |view, _, window, cx| {
+                                    cx.stop_propagation();
+                                    view.state.update(cx, |state, cx| {
+                                        state.set_scope(ix, window, cx);
+                                    });
+                                }),
+                            )
+                    },
+                )
+            }))
+    }
+
+    fn render_quick_actions(
+        &self,
+        actions: &[CommandPaletteItem],
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        h_flex()
+            .id("command-palette-quick-actions")
+            .w_full()
+            .h(px(QUICK_ACTIONS_HEIGHT))
+            .flex_shrink_0()
+            .px_3()
+            .gap_1()
+            .items_center()
+            .overflow_x_scroll()
+            .border_b_1()
+            .border_color(cx.theme().border)
+            .children(actions.iter().enumerate().map(|(ix, action)| {
+                let disabled = action.disabled;
+                h_flex()
+                    .id(("command-palette-quick-action", ix))
+                    .flex_shrink_0()
+                    .gap_1p5()
+                    .px_2()
+                    .py_1()
+                    .items_center()
+                    .rounded(cx.theme().radius)
+                    .text_xs()
+                    .text_color(cx.theme().foreground)
+                    .when(disabled, |this| this.opacity(0.5).cursor_not_allowed())
+                    .when(!disabled, |this| {
+                        let action = action.clone();
+                        this.cursor_pointer()
+                            .hover(|this| this.bg(cx.theme().list_hover))
+                            .on_mouse_down(
+                                gpui::MouseButton::Left,
+                                cx.listener(move |view, _, _, cx| {
+                                    cx.stop_propagation();
+                                    let action = action.clone();
+                                    view.state.update(cx, |state, cx| {
+                                        state.confirm_item(action, cx);
+                                    });
+                                }),
+                            )
+                    })
+                    .when_some(action.icon.clone(), |this, icon| {
+                        this.child(
+                            Icon::new(icon)
+                                .size_3p5()
+                                .text_color(cx.theme().muted_foreground),
+                        )
+                    })
+                    .child(action.title.clone())
+            }))
     }
 
     fn render_section_header(&self, title: SharedString, cx: &App) -> impl IntoElement {
@@ -343,6 +964,11 @@ impl CommandPaletteView {
         let mut last_end = 0;
 
         for &(start, end) in ranges {
+            // Provider-supplied ranges may be unsorted or split a character
+            let end = end.min(text.len());
+            if start < last_end || !text.is_char_boundary(start) || !text.is_char_boundary(end) {
+                continue;
+            }
             // Text before the highlight
             if start > last_end {
                 elements.push(
@@ -352,7 +978,6 @@ impl CommandPaletteView {
                 );
             }
             // Highlighted text
-            let end = end.min(text.len());
             if start < end {
                 elements.push(
                     div()
@@ -374,6 +999,17 @@ impl CommandPaletteView {
     }
 
     fn render_footer(&self, status_text: Option<SharedString>, cx: &App) -> impl IntoElement {
+        let state = self.state.read(cx);
+        let multi_select = state.config.selection_mode.is_multi();
+        let checked_len = state.checked_items().len();
+        let secondary_action_hint = state.config.secondary_action_hint.clone();
+        let cheatsheet_kbd = state
+            .config
+            .cheatsheet_shortcut
+            .as_ref()
+            .and_then(|shortcut| gpui::Keystroke::parse(shortcut).ok())
+            .map(Kbd::new);
+
         h_flex()
             .w_full()
             .px_3()
@@ -406,6 +1042,28 @@ impl CommandPaletteView {
                             )
                             .child("to select"),
                     )
+                    .when_some(secondary_action_hint, |this, hint| {
+                        this.child(
+                            h_flex()
+                                .gap_1()
+                                .child(
+                                    Kbd::new(gpui::Keystroke::parse("secondary-enter").unwrap())
+                                        .appearance(false),
+                                )
+                                .child(hint),
+                        )
+                    })
+                    .when(multi_select, |this| {
+                        this.child(
+                            h_flex()
+                                .gap_1()
+                                .child(
+                                    Kbd::new(gpui::Keystroke::parse("space").unwrap())
+                                        .appearance(false),
+                                )
+                                .child("to check"),
+                        )
+                    })
                     .child(
                         h_flex()
                             .gap_1()
@@ -414,17 +1072,40 @@ impl CommandPaletteView {
                                     .appearance(false),
                             )
                             .child("to close"),
-                    ),
+                    )
+                    .when_some(cheatsheet_kbd, |this, kbd| {
+                        this.child(
+                            h_flex()
+                                .gap_1()
+                                .child(kbd.appearance(false))
+                                .child("for all commands"),
+                        )
+                    }),
             )
-            .when_some(status_text, |this, status| {
-                this.child(
+            .map(|this| match (self.copied_notice.clone(), status_text) {
+                (Some(notice), _) => this.child(
+                    h_flex()
+                        .gap_2()
+                        .items_center()
+                        .min_w_0()
+                        .child(Icon::new(IconName::Check).size_4())
+                        .child(div().truncate().child(notice)),
+                ),
+                (None, Some(status)) => this.child(
                     h_flex()
                         .gap_2()
                         .items_center()
                         .text_color(cx.theme().muted_foreground)
                         .child(Icon::new(IconName::LoaderCircle).size_4())
                         .child(status),
-                )
+                ),
+                (None, None) => this.when(checked_len > 0, |this| {
+                    this.child(
+                        div()
+                            .text_color(cx.theme().foreground)
+                            .child(format!("{} selected", checked_len)),
+                    )
+                }),
             })
     }
 
@@ -449,8 +1130,38 @@ impl CommandPaletteView {
         let query_empty = state.query.is_empty();
 
         let mut rows = Vec::new();
+        if state.is_cheatsheet_open() {
+            // Items are already grouped by category, start a section at each new one
+            let mut category = None;
+            for (ix, matched) in matched_items.iter().enumerate() {
+                if category != Some(&matched.item.category) {
+                    category = Some(&matched.item.category);
+                    let title = if matched.item.category.is_empty() {
+                        state.config.commands_section_title.clone()
+                    } else {
+                        Some(matched.item.category.clone())
+                    };
+                    rows.extend(title.map(CommandPaletteRow::Header));
+                }
+                rows.push(CommandPaletteRow::Item(ix));
+            }
+            return rows;
+        }
+
         if query_empty {
-            rows.extend((0..static_len).map(CommandPaletteRow::Item));
+            let recent_len = state.matched_recent_len.min(static_len);
+            if recent_len > 0 {
+                if let Some(title) = state.config.recent_section_title.clone() {
+                    rows.push(CommandPaletteRow::Header(title));
+                }
+                rows.extend((0..recent_len).map(CommandPaletteRow::Item));
+                if recent_len < static_len {
+                    if let Some(title) = state.config.commands_section_title.clone() {
+                        rows.push(CommandPaletteRow::Header(title));
+                    }
+                }
+            }
+            rows.extend((recent_len..static_len).map(CommandPaletteRow::Item));
             return rows;
         }
 
@@ -461,12 +1172,16 @@ impl CommandPaletteView {
             rows.extend((0..static_len).map(CommandPaletteRow::Item));
         }
 
-        if async_len > 0 {
+        let loading = state.config.show_loading_skeletons && state.is_loading();
+        if async_len > 0 || loading {
             if let Some(title) = state.config.results_section_title.clone() {
                 rows.push(CommandPaletteRow::Header(title));
             }
             rows.extend((static_len..matched_items.len()).map(CommandPaletteRow::Item));
         }
+        if loading {
+            rows.extend((0..LOADING_SKELETON_ROWS).map(CommandPaletteRow::Skeleton));
+        }
 
         rows
     }
@@ -476,6 +1191,14 @@ impl CommandPaletteView {
         let async_len = state.matched_items.len().saturating_sub(static_len);
         let query_empty = state.query.is_empty();
 
+        if state.is_cheatsheet_open() || (query_empty && state.matched_recent_len > 0) {
+            return self
+                .build_rows(state, &state.matched_items)
+                .iter()
+                .position(|row| matches!(row, CommandPaletteRow::Item(ix) if *ix == item_index))
+                .unwrap_or(item_index);
+        }
+
         if query_empty {
             return item_index;
         }
@@ -502,19 +1225,30 @@ impl Render for CommandPaletteView {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let state = self.state.read(cx);
         let config = state.config.clone();
-        let matched_items = state.matched_items.clone();
         let selected_index = state.selected_index;
-        let rows = Rc::new(self.build_rows(&state, &matched_items));
+        let category_filter = state.category_filter().cloned();
+        let scopes: Vec<SharedString> = state.scopes().cloned().collect();
+        let breadcrumbs: Vec<SharedString> = state.breadcrumbs().cloned().collect();
+        let drilled_in = !breadcrumbs.is_empty();
+        let mode = state.mode();
+        let active_scope = state.active_scope();
+        let rows = Rc::new(self.build_rows(state, &state.matched_items));
         let row_count = rows.len();
 
-        // Prepare item sizes for virtual list
+        // Prepare item sizes for virtual list, rows without a subtitle are shorter
         let item_sizes: Rc<Vec<GpuiSize<Pixels>>> = Rc::new(
             rows.iter()
                 .map(|row| GpuiSize {
                     width: px(0.),
                     height: match row {
                         CommandPaletteRow::Header(_) => px(SECTION_HEADER_HEIGHT),
-                        CommandPaletteRow::Item(_) => self.item_height,
+                        CommandPaletteRow::Skeleton(_) => self.item_height,
+                        CommandPaletteRow::Item(ix) => state
+                            .matched_items
+                            .get(*ix)
+                            .map_or(self.item_height, |matched| {
+                                self.item_height_for(&matched.item)
+                            }),
                     },
                 })
                 .collect(),
@@ -522,11 +1256,20 @@ impl Render for CommandPaletteView {
 
         let show_categories = config.show_categories_inline;
         let show_footer = config.show_footer;
+        let overscan_rows = config.overscan_rows;
         let footer_status = config
             .status_provider
             .as_ref()
             .and_then(|provider| provider(&state.query));
         let max_height = px(config.max_height);
+        let chrome_height = chrome_height(&config);
+        let header_area = config.header.as_ref().map(|header| header(window, cx));
+        let header_area_height = if header_area.is_some() {
+            px(config.header_height)
+        } else {
+            px(0.)
+        };
+        let metrics = cx.theme().size_metrics(Size::Medium);
         let reduced_motion = GlobalState::global(cx).reduced_motion();
         let motion = cx.theme().motion.clone();
         let reveal_animation_duration = reveal_animation_duration(cx);
@@ -554,34 +1297,51 @@ impl Render for CommandPaletteView {
         let list_content_height = if row_count == 0 {
             px(EMPTY_STATE_HEIGHT)
         } else {
-            rows.iter().fold(px(0.0), |sum, row| {
-                sum + match row {
-                    CommandPaletteRow::Header(_) => px(SECTION_HEADER_HEIGHT),
-                    CommandPaletteRow::Item(_) => self.item_height,
-                }
-            })
+            item_sizes
+                .iter()
+                .fold(px(0.0), |sum, size| sum + size.height)
+        };
+        let anchored = self.placement != CommandPalettePlacement::Centered;
+        let opens_upward = self.placement == CommandPalettePlacement::Above;
+        let list_height = if opens_upward {
+            max_height
+        } else {
+            max_height.min(list_content_height)
+        };
+        let expanded_height = chrome_height + list_height;
+        let collapsed_height = if opens_upward {
+            expanded_height
+        } else {
+            collapsed_chrome_height(&config)
         };
-        let list_height = max_height.min(list_content_height);
-        let expanded_height = px(HEADER_HEIGHT)
-            + list_height
-            + if show_footer {
-                px(FOOTER_HEIGHT)
-            } else {
-                px(0.0)
-            };
-        let collapsed_height = px(HEADER_HEIGHT);
 
         let surface_ctx = SurfaceContext {
             blur_enabled: GlobalState::global(cx).blur_enabled(),
         };
 
         let content = v_flex()
-            .key_context(CONTEXT)
+            .key_context(if config.selection_mode.is_multi() {
+                MULTI_SELECT_CONTEXT
+            } else {
+                CONTEXT
+            })
             .track_focus(&self.focus_handle)
             .on_action(cx.listener(Self::on_action_cancel))
             .on_action(cx.listener(Self::on_action_confirm))
             .on_action(cx.listener(Self::on_action_select_up))
             .on_action(cx.listener(Self::on_action_select_down))
+            .on_action(cx.listener(Self::on_action_cycle_category))
+            .on_action(cx.listener(Self::on_action_toggle_cheatsheet))
+            .on_action(cx.listener(Self::on_action_copy_selected))
+            .on_action(cx.listener(Self::on_action_toggle_checked))
+            .when(anchored, |this| {
+                // Anchored palettes have no overlay, so dismiss on outside clicks here
+                this.on_mouse_down_out(cx.listener(|view, _, _, cx| {
+                    view.state.update(cx, |state, cx| {
+                        state.dismiss(cx);
+                    });
+                }))
+            })
             .h(if self.list_revealed {
                 expanded_height
             } else {
@@ -589,6 +1349,19 @@ impl Render for CommandPaletteView {
             })
             .w_full()
             .overflow_hidden()
+            // Header area
+            .when_some(header_area, |this, header| {
+                this.child(
+                    div()
+                        .w_full()
+                        .h(header_area_height)
+                        .flex_shrink_0()
+                        .flex()
+                        .items_center()
+                        .px_3()
+                        .child(header),
+                )
+            })
             // Search input
             .child(
                 div()
@@ -596,9 +1369,16 @@ impl Render for CommandPaletteView {
                     .h(px(HEADER_HEIGHT))
                     .flex()
                     .items_center()
-                    .px_3()
+                    .px(metrics.padding_x)
+                    .gap(metrics.gap)
                     .border_b_1()
                     .border_color(cx.theme().border)
+                    .when_some(mode, |this, prefix| {
+                        this.child(self.render_mode_chip(prefix, cx))
+                    })
+                    .when(drilled_in, |this| {
+                        this.child(self.render_breadcrumbs(breadcrumbs, cx))
+                    })
                     .child(
                         Input::new(&self.input_state)
                             .with_size(Size::Medium)
@@ -609,8 +1389,18 @@ impl Render for CommandPaletteView {
                             )
                             .appearance(false)
                             .cleanable(true),
-                    ),
+                    )
+                    .when(scopes.len() > 1 && !drilled_in && mode.is_none(), |this| {
+                        this.child(self.render_scope_chips(scopes, active_scope, cx))
+                    })
+                    .when_some(category_filter, |this, category| {
+                        this.child(self.render_category_chip(category, cx))
+                    }),
             )
+            // Quick actions
+            .when(!config.quick_actions.is_empty(), |this| {
+                this.child(self.render_quick_actions(&config.quick_actions, cx))
+            })
             // Results list
             .when(self.list_revealed, |this| {
                 this.child({
@@ -622,9 +1412,14 @@ impl Render for CommandPaletteView {
                         .when(row_count > 0, |this| {
                             this.child(
                                 v_virtual_list(cx.entity(), "command-palette-list", item_sizes, {
-                                    let matched_items = matched_items.clone();
                                     let rows = rows.clone();
                                     move |view, visible_range, window, cx| {
+                                        if visible_range.end + PAGE_LOAD_THRESHOLD_ROWS
+                                            >= rows.len()
+                                        {
+                                            view.request_next_page(window, cx);
+                                        }
+
                                         visible_range
                                             .filter_map(|ix| {
                                                 let row = rows.get(ix)?;
@@ -636,18 +1431,29 @@ impl Render for CommandPaletteView {
                                                         )
                                                         .into_any_element(),
                                                     ),
+                                                    CommandPaletteRow::Skeleton(ix) => Some(
+                                                        view.render_skeleton_row(*ix, cx)
+                                                            .into_any_element(),
+                                                    ),
                                                     CommandPaletteRow::Item(item_index) => {
-                                                        matched_items.get(*item_index).map(|item| {
+                                                        // Clone only the visible items
+                                                        let item = view
+                                                            .state
+                                                            .read(cx)
+                                                            .matched_items
+                                                            .get(*item_index)
+                                                            .cloned()?;
+                                                        Some(
                                                             view.render_item(
-                                                                item,
+                                                                &item,
                                                                 *item_index,
                                                                 selected_index == Some(*item_index),
                                                                 show_categories,
                                                                 window,
                                                                 cx,
                                                             )
-                                                            .into_any_element()
-                                                        })
+                                                            .into_any_element(),
+                                                        )
                                                     }
                                                 }
                                             })
@@ -655,6 +1461,7 @@ impl Render for CommandPaletteView {
                                     }
                                 })
                                 .track_scroll(&self.scroll_handle)
+                                .overscan(overscan_rows)
                                 .py_1(),
                             )
                         });
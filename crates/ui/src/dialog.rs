@@ -120,6 +120,7 @@ pub struct Dialog {
     width: Pixels,
     max_width: Option<Pixels>,
     margin_top: Option<Pixels>,
+    position: Option<Point<Pixels>>,
 
     on_close: Rc<dyn Fn(&ClickEvent, &mut Window, &mut App) + 'static>,
     on_ok: Option<Rc<dyn Fn(&ClickEvent, &mut Window, &mut App) -> bool + 'static>>,
@@ -157,6 +158,7 @@ impl Dialog {
             footer: None,
             children: Vec::new(),
             margin_top: None,
+            position: None,
             width: px(480.),
             max_width: None,
             overlay: true,
@@ -276,6 +278,14 @@ impl Dialog {
         self
     }
 
+    /// Place the dialog's top-left corner at the given window position, defaults to `None`.
+    ///
+    /// When set, this takes precedence over the centered placement and [`Self::margin_top`].
+    pub fn position(mut self, position: Point<Pixels>) -> Self {
+        self.position = Some(position);
+        self
+    }
+
     /// Sets the width of the dialog, defaults to 480px.
     ///
     /// See also [`Self::width`]
@@ -425,9 +435,19 @@ impl RenderOnce for Dialog {
             origin: Point::default(),
             size: view_size,
         };
-        let offset_top = px(layer_ix as f32 * 16.);
-        let y = self.margin_top.unwrap_or(view_size.height / 10.) + offset_top;
-        let x = bounds.center().x - self.width / 2.;
+        let (x, y) = match self.position {
+            Some(position) => (
+                position.x - window_paddings.left,
+                position.y - window_paddings.top,
+            ),
+            None => {
+                let offset_top = px(layer_ix as f32 * 16.);
+                (
+                    bounds.center().x - self.width / 2.,
+                    self.margin_top.unwrap_or(view_size.height / 10.) + offset_top,
+                )
+            }
+        };
 
         let base_size = window.text_style().font_size;
         let rem_size = window.rem_size();
@@ -1,9 +1,22 @@
-use gpui::{App, Entity, Global, Pixels, px};
+use gpui::{App, Entity, Global, Pixels, Window, px};
 
-use crate::text::TextViewState;
+use crate::{ElevationToken, text::TextViewState};
 
 pub(crate) fn init(cx: &mut App) {
     cx.set_global(GlobalState::new());
+
+    cx.observe_keystrokes(|_, window, cx| {
+        GlobalState::global_mut(cx).set_input_modality(InputModality::Keyboard, window);
+    })
+    .detach();
+}
+
+/// The kind of input the user interacted with last, see [`GlobalState::focus_visible`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum InputModality {
+    #[default]
+    Keyboard,
+    Pointer,
 }
 
 impl Global for GlobalState {}
@@ -16,6 +29,14 @@ pub struct GlobalState {
     reduced_motion_stack: Vec<bool>,
     /// Stack for floating inset values.
     floating_inset_stack: Vec<Pixels>,
+    /// Stack for elevation overrides, empty when no scope provides a value.
+    elevation_stack: Vec<ElevationToken>,
+    /// Updated by keystrokes and by mouse downs in `Root`.
+    input_modality: InputModality,
+    /// Whether the app asked to save power, e.g. while on battery or backgrounded.
+    power_saving: bool,
+    /// Whether glass surfaces render their noise texture.
+    noise_enabled: bool,
 }
 
 impl GlobalState {
@@ -25,6 +46,10 @@ impl GlobalState {
             blur_enabled_stack: vec![true],    // Default to enabled
             reduced_motion_stack: vec![false], // Default to not reduced
             floating_inset_stack: vec![px(4.0)],
+            elevation_stack: Vec::new(),
+            input_modality: InputModality::default(),
+            power_saving: false,
+            noise_enabled: true,
         }
     }
 
@@ -91,6 +116,32 @@ impl GlobalState {
         }
     }
 
+    /// Returns whether power saving is on, see [`GlobalState::set_power_saving`].
+    pub fn power_saving(&self) -> bool {
+        self.power_saving
+    }
+
+    /// Turn power saving on or off, e.g. when the app is backgrounded or on battery.
+    ///
+    /// While on, `ActiveMotion::should_animate` returns false so components skip
+    /// their animations.
+    pub fn set_power_saving(&mut self, power_saving: bool) {
+        self.power_saving = power_saving;
+    }
+
+    /// Returns whether glass surfaces render a noise texture, see [`GlobalState::set_noise_enabled`].
+    pub fn noise_enabled(&self) -> bool {
+        self.noise_enabled
+    }
+
+    /// Turn the noise texture of glass surfaces on or off app-wide.
+    ///
+    /// Default: true. Turn it off when the app doesn't bundle `NoiseAsset_256.png`
+    /// or prefers flat surfaces.
+    pub fn set_noise_enabled(&mut self, enabled: bool) {
+        self.noise_enabled = enabled;
+    }
+
     /// Returns the current floating inset from the context stack.
     pub fn floating_inset(&self) -> Pixels {
         self.floating_inset_stack.last().copied().unwrap_or(px(4.0))
@@ -107,4 +158,41 @@ impl GlobalState {
             self.floating_inset_stack.pop();
         }
     }
+
+    /// Returns the elevation override from the context stack, if any.
+    pub fn elevation(&self) -> Option<ElevationToken> {
+        self.elevation_stack.last().copied()
+    }
+
+    /// Push an elevation value onto the context stack.
+    pub fn push_elevation(&mut self, elevation: ElevationToken) {
+        self.elevation_stack.push(elevation);
+    }
+
+    /// Pop an elevation value from the context stack.
+    pub fn pop_elevation(&mut self) {
+        self.elevation_stack.pop();
+    }
+
+    /// Returns the kind of input the user interacted with last.
+    pub fn input_modality(&self) -> InputModality {
+        self.input_modality
+    }
+
+    /// Returns whether focus indicators should be shown, like the web's `:focus-visible`.
+    ///
+    /// True until the user clicks, and again after the next keystroke, so focus rings
+    /// appear for keyboard navigation but not after a mouse click.
+    pub fn focus_visible(&self) -> bool {
+        self.input_modality == InputModality::Keyboard
+    }
+
+    /// Record the last input modality, refreshing `window` when it changes so focus
+    /// rings update.
+    pub(crate) fn set_input_modality(&mut self, modality: InputModality, window: &mut Window) {
+        if self.input_modality != modality {
+            self.input_modality = modality;
+            window.refresh();
+        }
+    }
 }
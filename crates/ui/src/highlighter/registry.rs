@@ -1,3 +1,4 @@
+use anyhow::{Result, anyhow};
 use gpui::{App, FontWeight, HighlightStyle, Hsla, SharedString};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -56,6 +57,10 @@ pub(super) const HIGHLIGHT_NAMES: [&str; 40] = [
     "variant",
 ];
 
+/// Syntax scopes a [`HighlightTheme`] must define to be accepted by [`HighlightTheme::from_json`].
+pub const REQUIRED_HIGHLIGHT_SCOPES: [&str; 5] =
+    ["comment", "function", "keyword", "string", "type"];
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct LanguageConfig {
     pub name: SharedString,
@@ -458,6 +463,48 @@ impl HighlightTheme {
     pub fn default_light() -> Arc<Self> {
         DEFAULT_THEME_COLORS[&ThemeMode::Light].1.clone()
     }
+
+    /// Parse a highlight theme from a Zed-compatible JSON theme, e.g.:
+    ///
+    /// ```json
+    /// {
+    ///   "name": "My Theme",
+    ///   "appearance": "dark",
+    ///   "style": {
+    ///     "editor.background": "#1e1e1e",
+    ///     "syntax": {
+    ///       "keyword": { "color": "#c678dd" }
+    ///     }
+    ///   }
+    /// }
+    /// ```
+    ///
+    /// Returns an error if the JSON is invalid or the theme is missing any of
+    /// the [`REQUIRED_HIGHLIGHT_SCOPES`].
+    pub fn from_json(json: &str) -> Result<Self> {
+        let theme: Self = serde_json::from_str(json)?;
+        theme.validate()?;
+        Ok(theme)
+    }
+
+    /// Check that all of the [`REQUIRED_HIGHLIGHT_SCOPES`] have a style.
+    pub fn validate(&self) -> Result<()> {
+        let missing = REQUIRED_HIGHLIGHT_SCOPES
+            .iter()
+            .filter(|scope| self.style.syntax.style(scope).is_none())
+            .copied()
+            .collect::<Vec<_>>();
+
+        if !missing.is_empty() {
+            return Err(anyhow!(
+                "highlight theme `{}` is missing required scopes: {}",
+                self.name,
+                missing.join(", ")
+            ));
+        }
+
+        Ok(())
+    }
 }
 
 /// Registry for code highlighter languages.
@@ -523,4 +570,49 @@ mod tests {
         assert!(registry.language("javascript").is_some());
         assert!(registry.language("js").is_some());
     }
+
+    #[test]
+    fn test_highlight_theme_from_json() {
+        use super::HighlightTheme;
+        use crate::ThemeMode;
+
+        let theme = HighlightTheme::from_json(
+            r##"{
+                "name": "Test",
+                "appearance": "dark",
+                "style": {
+                    "syntax": {
+                        "comment": { "color": "#808080" },
+                        "function": { "color": "#61afef" },
+                        "keyword": { "color": "#c678dd" },
+                        "string": { "color": "#98c379" },
+                        "type": { "color": "#e5c07b" }
+                    }
+                }
+            }"##,
+        )
+        .unwrap();
+        assert_eq!(theme.name, "Test");
+        assert_eq!(theme.appearance, ThemeMode::Dark);
+        assert!(theme.keyword.is_some());
+
+        let err = HighlightTheme::from_json(
+            r##"{
+                "name": "Partial",
+                "style": {
+                    "syntax": {
+                        "keyword": { "color": "#c678dd" }
+                    }
+                }
+            }"##,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "highlight theme `Partial` is missing required scopes: comment, function, string, type"
+        );
+
+        assert!(HighlightTheme::default_light().validate().is_ok());
+        assert!(HighlightTheme::default_dark().validate().is_ok());
+    }
 }
@@ -84,6 +84,7 @@ pub use element_ext::ElementExt;
 pub use event::InteractiveElementExt;
 pub use focus_trap::FocusTrapElement;
 pub use geometry::*;
+pub use global_state::{GlobalState, InputModality};
 pub use icon::*;
 pub use index_path::IndexPath;
 pub use input::{Rope, RopeExt, RopeLines};
@@ -126,6 +127,7 @@ pub fn init(cx: &mut App) {
     table::init(cx);
     text::init(cx);
     tree::init(cx);
+    window_shell::init(cx);
 }
 
 #[inline]
@@ -1,16 +1,20 @@
-use std::{cell::RefCell, rc::Rc};
+use std::{cell::RefCell, rc::Rc, time::Duration};
 
 use gpui::{
-    AnimationExt as _, AnyElement, App, Context, Corner, DismissEvent, Element, ElementId, Entity,
-    Focusable, GlobalElementId, Hitbox, HitboxBehavior, InspectorElementId, InteractiveElement,
-    IntoElement, MouseButton, MouseDownEvent, ParentElement, Pixels, Point, StyleRefinement,
-    Styled, Subscription, Window, anchored, deferred, div, prelude::FluentBuilder, px,
+    AnimationExt as _, AnyElement, App, Axis, Bounds, Context, Corner, DismissEvent, Element,
+    ElementId, Entity, Focusable, GlobalElementId, Hitbox, HitboxBehavior, InspectorElementId,
+    InteractiveElement, IntoElement, MouseButton, MouseDownEvent, MouseMoveEvent, MouseUpEvent,
+    ParentElement, Pixels, Point, StyleRefinement, Styled, Subscription, Task, Window, anchored,
+    deferred, div, prelude::FluentBuilder, px,
 };
 
 use crate::{
     ActiveTheme, animation::fast_invoke_animation, global_state::GlobalState, menu::PopupMenu,
 };
 
+/// How far the pointer may move during a long-press before it is treated as a drag.
+const LONG_PRESS_TOLERANCE: Pixels = px(6.);
+
 /// A extension trait for adding a context menu to an element.
 pub trait ContextMenuExt: ParentElement + Styled {
     /// Add a context menu to the element.
@@ -33,7 +37,7 @@ pub trait ContextMenuExt: ParentElement + Styled {
 
 impl<E: ParentElement + Styled> ContextMenuExt for E {}
 
-/// A context menu that can be shown on right-click.
+/// A context menu that can be shown on right-click, or on long-press, see [`ContextMenu::long_press`].
 pub struct ContextMenu<E: ParentElement + Styled + Sized> {
     id: ElementId,
     element: Option<E>,
@@ -41,6 +45,8 @@ pub struct ContextMenu<E: ParentElement + Styled + Sized> {
     // This is not in use, just for style refinement forwarding.
     _ignore_style: StyleRefinement,
     anchor: Corner,
+    long_press: Option<Duration>,
+    anchor_to_element: bool,
 }
 
 impl<E: ParentElement + Styled> ContextMenu<E> {
@@ -51,6 +57,8 @@ impl<E: ParentElement + Styled> ContextMenu<E> {
             element: Some(element),
             menu: None,
             anchor: Corner::TopLeft,
+            long_press: None,
+            anchor_to_element: false,
             _ignore_style: StyleRefinement::default(),
         }
     }
@@ -65,6 +73,23 @@ impl<E: ParentElement + Styled> ContextMenu<E> {
         self
     }
 
+    /// Also open the menu when the left button is held for `threshold`, for touch
+    /// screens and trackpads without a secondary click.
+    ///
+    /// Moving the pointer or releasing the button before the threshold cancels it,
+    /// so drags and normal clicks are not affected.
+    pub fn long_press(mut self, threshold: Duration) -> Self {
+        self.long_press = Some(threshold);
+        self
+    }
+
+    /// Open the menu below the element's bounds instead of at the cursor, like an
+    /// overflow button, default: false
+    pub fn anchor_to_element(mut self, anchor_to_element: bool) -> Self {
+        self.anchor_to_element = anchor_to_element;
+        self
+    }
+
     fn with_element_state<R>(
         &mut self,
         id: &GlobalElementId,
@@ -113,7 +138,62 @@ struct ContextMenuSharedState {
     menu_view: Option<Entity<PopupMenu>>,
     open: bool,
     position: Point<Pixels>,
+    /// The element bounds from the last prepaint, used by [`ContextMenu::anchor_to_element`].
+    bounds: Bounds<Pixels>,
     _subscription: Option<Subscription>,
+    /// The position and timer of a pending long-press.
+    long_press: Option<(Point<Pixels>, Task<()>)>,
+}
+
+impl ContextMenuSharedState {
+    /// Open the menu at `position`, building it on the next frame.
+    fn open(
+        shared_state: &Rc<RefCell<Self>>,
+        builder: Option<Rc<dyn Fn(PopupMenu, &mut Window, &mut Context<PopupMenu>) -> PopupMenu>>,
+        position: Point<Pixels>,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        {
+            let mut shared_state = shared_state.borrow_mut();
+            // Clear any existing menu view to allow immediate replacement
+            // Set the new position and open the menu
+            shared_state.menu_view = None;
+            shared_state._subscription = None;
+            shared_state.position = position;
+            shared_state.open = true;
+        }
+
+        // Use defer to build the menu in the next frame, avoiding race conditions
+        window.defer(cx, {
+            let shared_state = shared_state.clone();
+            move |window, cx| {
+                let menu = PopupMenu::build(window, cx, move |menu, window, cx| {
+                    let Some(build) = &builder else {
+                        return menu;
+                    };
+                    build(menu, window, cx)
+                });
+
+                // Set up the subscription for dismiss handling
+                let _subscription = window.subscribe(&menu, cx, {
+                    let shared_state = shared_state.clone();
+                    move |_, _: &DismissEvent, window, _cx| {
+                        shared_state.borrow_mut().open = false;
+                        window.refresh();
+                    }
+                });
+
+                // Update the shared state with the built menu and subscription
+                {
+                    let mut state = shared_state.borrow_mut();
+                    state.menu_view = Some(menu.clone());
+                    state._subscription = Some(_subscription);
+                    window.refresh();
+                }
+            }
+        });
+    }
 }
 
 pub struct ContextMenuState {
@@ -129,7 +209,9 @@ impl Default for ContextMenuState {
                 menu_view: None,
                 open: false,
                 position: Default::default(),
+                bounds: Default::default(),
                 _subscription: None,
+                long_press: None,
             })),
         }
     }
@@ -246,7 +328,7 @@ impl<E: ParentElement + Styled + IntoElement + 'static> Element for ContextMenu<
 
     fn prepaint(
         &mut self,
-        _: Option<&gpui::GlobalElementId>,
+        id: Option<&gpui::GlobalElementId>,
         _: Option<&InspectorElementId>,
         bounds: gpui::Bounds<gpui::Pixels>,
         request_layout: &mut Self::RequestLayoutState,
@@ -256,6 +338,11 @@ impl<E: ParentElement + Styled + IntoElement + 'static> Element for ContextMenu<
         if let Some(element) = &mut request_layout.element {
             element.prepaint(window, cx);
         }
+        if self.anchor_to_element {
+            self.with_element_state(id.unwrap(), window, cx, |_, state, _, _| {
+                state.shared_state.borrow_mut().bounds = bounds;
+            });
+        }
         window.insert_hitbox(bounds, HitboxBehavior::Normal)
     }
 
@@ -275,6 +362,9 @@ impl<E: ParentElement + Styled + IntoElement + 'static> Element for ContextMenu<
 
         // Take the builder before setting up element state to avoid borrow issues
         let builder = self.menu.clone();
+        let long_press = self.long_press;
+        let anchor_to_element = self.anchor_to_element;
+        let anchor = self.anchor;
 
         self.with_element_state(
             id.unwrap(),
@@ -282,54 +372,98 @@ impl<E: ParentElement + Styled + IntoElement + 'static> Element for ContextMenu<
             cx,
             |_view, state: &mut ContextMenuState, window, _| {
                 let shared_state = state.shared_state.clone();
+                // The menu opens at the element corner facing away from the anchor, or at the cursor.
+                let menu_position = {
+                    let shared_state = shared_state.clone();
+                    move |position: Point<Pixels>| {
+                        if anchor_to_element {
+                            shared_state
+                                .borrow()
+                                .bounds
+                                .corner(anchor.other_side_corner_along(Axis::Vertical))
+                        } else {
+                            position
+                        }
+                    }
+                };
 
-                let hitbox = hitbox.clone();
                 // When right mouse click, to build content menu, and show it at the mouse position.
-                window.on_mouse_event(move |event: &MouseDownEvent, phase, window, cx| {
-                    if phase.bubble()
-                        && event.button == MouseButton::Right
-                        && hitbox.is_hovered(window)
-                    {
-                        {
-                            let mut shared_state = shared_state.borrow_mut();
-                            // Clear any existing menu view to allow immediate replacement
-                            // Set the new position and open the menu
-                            shared_state.menu_view = None;
-                            shared_state._subscription = None;
-                            shared_state.position = event.position;
-                            shared_state.open = true;
+                window.on_mouse_event({
+                    let shared_state = shared_state.clone();
+                    let builder = builder.clone();
+                    let hitbox = hitbox.clone();
+                    move |event: &MouseDownEvent, phase, window, cx| {
+                        if !phase.bubble() || !hitbox.is_hovered(window) {
+                            return;
                         }
 
-                        // Use defer to build the menu in the next frame, avoiding race conditions
-                        window.defer(cx, {
+                        if event.button == MouseButton::Right {
+                            ContextMenuSharedState::open(
+                                &shared_state,
+                                builder.clone(),
+                                menu_position(event.position),
+                                window,
+                                cx,
+                            );
+                            return;
+                        }
+
+                        let Some(threshold) = long_press else {
+                            return;
+                        };
+                        if event.button != MouseButton::Left {
+                            return;
+                        }
+
+                        let origin = event.position;
+                        let position = menu_position(origin);
+                        let task = window.spawn(cx, {
                             let shared_state = shared_state.clone();
                             let builder = builder.clone();
-                            move |window, cx| {
-                                let menu = PopupMenu::build(window, cx, move |menu, window, cx| {
-                                    let Some(build) = &builder else {
-                                        return menu;
-                                    };
-                                    build(menu, window, cx)
-                                });
-
-                                // Set up the subscription for dismiss handling
-                                let _subscription = window.subscribe(&menu, cx, {
-                                    let shared_state = shared_state.clone();
-                                    move |_, _: &DismissEvent, window, _cx| {
-                                        shared_state.borrow_mut().open = false;
-                                        window.refresh();
+                            async move |cx| {
+                                cx.background_executor().timer(threshold).await;
+                                _ = cx.update(|window, cx| {
+                                    if shared_state.borrow_mut().long_press.take().is_some() {
+                                        ContextMenuSharedState::open(
+                                            &shared_state,
+                                            builder,
+                                            position,
+                                            window,
+                                            cx,
+                                        );
                                     }
                                 });
-
-                                // Update the shared state with the built menu and subscription
-                                {
-                                    let mut state = shared_state.borrow_mut();
-                                    state.menu_view = Some(menu.clone());
-                                    state._subscription = Some(_subscription);
-                                    window.refresh();
-                                }
                             }
                         });
+                        shared_state.borrow_mut().long_press = Some((origin, task));
+                    }
+                });
+
+                if long_press.is_none() {
+                    return;
+                }
+
+                // Cancel the pending long-press once the pointer moves away or is released.
+                window.on_mouse_event({
+                    let shared_state = shared_state.clone();
+                    move |event: &MouseMoveEvent, phase, _, _| {
+                        if !phase.bubble() {
+                            return;
+                        }
+                        let mut shared_state = shared_state.borrow_mut();
+                        if let Some((origin, _)) = &shared_state.long_press {
+                            let delta = event.position - *origin;
+                            if delta.x.abs() > LONG_PRESS_TOLERANCE
+                                || delta.y.abs() > LONG_PRESS_TOLERANCE
+                            {
+                                shared_state.long_press = None;
+                            }
+                        }
+                    }
+                });
+                window.on_mouse_event(move |_: &MouseUpEvent, phase, _, _| {
+                    if phase.bubble() {
+                        shared_state.borrow_mut().long_press = None;
                     }
                 });
             },
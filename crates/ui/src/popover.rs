@@ -1,24 +1,32 @@
 use gpui::{
     AnimationExt as _, AnyElement, App, Bounds, Context, Deferred, DismissEvent, Div, ElementId,
     EventEmitter, FocusHandle, Focusable, Half, InteractiveElement as _, IntoElement, KeyBinding,
-    MouseButton, ParentElement, Pixels, Point, Render, RenderOnce, SharedString, Stateful,
-    StyleRefinement, Styled, Subscription, Window, deferred, div, prelude::FluentBuilder as _, px,
+    MouseButton, ParentElement, PathBuilder, Pixels, Point, Render, RenderOnce, SharedString,
+    Stateful, StatefulInteractiveElement as _, StyleRefinement, Styled, Subscription, Task, Window,
+    canvas, deferred, div, point, prelude::FluentBuilder as _, px,
 };
-use std::rc::Rc;
+use std::{rc::Rc, time::Duration};
 
 use crate::{
     ActiveTheme, Anchor, ElementExt, Selectable, StyledExt as _,
     actions::Cancel,
     anchored,
     animation::{
-        PresenceOptions, PresencePhase, SpringPreset, keyed_presence, point_to_point_animation,
-        spring_preset_animation, spring_preset_duration_ms,
+        ActiveMotion, PresenceOptions, PresencePhase, SpringPreset, keyed_presence,
+        point_to_point_animation, spring_preset_animation, spring_preset_duration_ms,
     },
-    global_state::GlobalState,
     v_flex,
 };
 
 const CONTEXT: &str = "Popover";
+/// Minimum distance kept between the content and the window edges.
+const WINDOW_MARGIN: f32 = 8.;
+/// Width of the arrow base, see [`Popover::with_arrow`].
+const ARROW_WIDTH: f32 = 12.;
+/// Height of the arrow from its base to its tip.
+const ARROW_HEIGHT: f32 = 6.;
+/// Minimum distance between the arrow and the content corners, to clear the radius.
+const ARROW_INSET: f32 = 12.;
 pub(crate) fn init(cx: &mut App) {
     cx.bind_keys([KeyBinding::new("escape", Cancel, Some(CONTEXT))])
 }
@@ -44,9 +52,16 @@ pub struct Popover {
     /// This is used for hotfix the trigger element style to support w_full.
     trigger_style: Option<StyleRefinement>,
     mouse_button: MouseButton,
+    /// The show and hide delays when opened by hovering, see [`Popover::trigger_on_hover`].
+    hover_delays: Option<(Duration, Duration)>,
     appearance: bool,
+    arrow: bool,
+    flip: bool,
+    modal: bool,
     overlay_closable: bool,
     on_open_change: Option<Rc<dyn Fn(&bool, &mut Window, &mut App)>>,
+    on_shown: Option<Rc<dyn Fn(&mut Window, &mut App)>>,
+    on_hidden: Option<Rc<dyn Fn(&mut Window, &mut App)>>,
 }
 
 impl Popover {
@@ -62,11 +77,17 @@ impl Popover {
             tracked_focus_handle: None,
             children: vec![],
             mouse_button: MouseButton::Left,
+            hover_delays: None,
             appearance: true,
+            arrow: false,
+            flip: true,
+            modal: false,
             overlay_closable: true,
             default_open: false,
             open: None,
             on_open_change: None,
+            on_shown: None,
+            on_hidden: None,
         }
     }
 
@@ -85,6 +106,17 @@ impl Popover {
         self
     }
 
+    /// Open the popover by hovering the trigger instead of clicking it, like a hover card.
+    ///
+    /// The popover opens once the trigger has been hovered for `show_delay`, and closes
+    /// `hide_delay` after neither the trigger nor the content is hovered, leaving time
+    /// to move the cursor from the trigger to the content. The content doesn't take
+    /// focus when opened this way.
+    pub fn trigger_on_hover(mut self, show_delay: Duration, hide_delay: Duration) -> Self {
+        self.hover_delays = Some((show_delay, hide_delay));
+        self
+    }
+
     /// Set the trigger element of the popover.
     pub fn trigger<T>(mut self, trigger: T) -> Self
     where
@@ -130,6 +162,31 @@ impl Popover {
         self
     }
 
+    /// Add a callback to be called once the popover content is shown, as its open
+    /// animation starts.
+    ///
+    /// Unlike `on_open_change`, this fires once per visible transition, including when
+    /// the popover is opened by the `open` prop, e.g. to focus an input in the content.
+    pub fn on_shown<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&mut Window, &mut App) + 'static,
+    {
+        self.on_shown = Some(Rc::new(callback));
+        self
+    }
+
+    /// Add a callback to be called once the popover content is hidden, after its close
+    /// animation completes.
+    ///
+    /// Reopening the popover while it is closing does not fire this.
+    pub fn on_hidden<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&mut Window, &mut App) + 'static,
+    {
+        self.on_hidden = Some(Rc::new(callback));
+        self
+    }
+
     /// Set the style for the trigger element.
     pub fn trigger_style(mut self, style: StyleRefinement) -> Self {
         self.trigger_style = Some(style);
@@ -168,6 +225,36 @@ impl Popover {
         self
     }
 
+    /// Set whether to draw an arrow on the edge of the content facing the trigger,
+    /// pointing at the middle of the trigger, default is `false`.
+    ///
+    /// The arrow takes the popover background and border colors, so it merges with the
+    /// content. It is not drawn when [`Popover::appearance`] is off.
+    pub fn with_arrow(mut self, arrow: bool) -> Self {
+        self.arrow = arrow;
+        self
+    }
+
+    /// Set whether the content opens on the other side of the trigger when it doesn't fit
+    /// in the window on the side of the anchor but fits better on the other,
+    /// default is `true`.
+    ///
+    /// Turn this off to keep the placement fixed, shrinking the content into the window.
+    pub fn flip(mut self, flip: bool) -> Self {
+        self.flip = flip;
+        self
+    }
+
+    /// Set whether the popover blocks interaction with the rest of the window, default is `false`.
+    ///
+    /// A modal popover renders a transparent backdrop behind it while open, so
+    /// clicks outside the popover can't reach the elements underneath. Those
+    /// clicks still dismiss the popover if [`Popover::overlay_closable`] is set.
+    pub fn modal(mut self, modal: bool) -> Self {
+        self.modal = modal;
+        self
+    }
+
     /// Bind the focus handle to receive focus when the popover is opened.
     /// If you not set this, a new focus handle will be created for the popover to
     ///
@@ -177,6 +264,36 @@ impl Popover {
         self
     }
 
+    /// Returns `anchor` flipped to the other vertical side of the trigger if the content
+    /// overflows the window on its side and the other side has more room.
+    ///
+    /// A `content_height` of zero, before the content is measured, never flips.
+    fn flipped_anchor(
+        anchor: Anchor,
+        trigger_bounds: Bounds<Pixels>,
+        content_height: Pixels,
+        viewport_height: Pixels,
+    ) -> Anchor {
+        if content_height <= px(0.) {
+            return anchor;
+        }
+
+        let margin = px(WINDOW_MARGIN);
+        let space_below = viewport_height - trigger_bounds.bottom() - margin;
+        let space_above = trigger_bounds.top() - margin;
+        let (space, other_space) = if anchor.is_top() {
+            (space_below, space_above)
+        } else {
+            (space_above, space_below)
+        };
+
+        if content_height > space && other_space > space {
+            anchor.swap_vertical()
+        } else {
+            anchor
+        }
+    }
+
     fn resolved_corner(anchor: Anchor, trigger_bounds: Bounds<Pixels>) -> Point<Pixels> {
         let offset = if anchor.is_center() {
             gpui::point(trigger_bounds.size.width.half(), px(0.))
@@ -209,8 +326,18 @@ pub struct PopoverState {
     focus_handle: FocusHandle,
     pub(crate) tracked_focus_handle: Option<FocusHandle>,
     trigger_bounds: Bounds<Pixels>,
+    /// Height of the content when last laid out, to flip the placement by.
+    content_height: Pixels,
+    placement: Anchor,
     open: bool,
+    /// Whether the content is shown, as last reported to `on_shown`/`on_hidden`.
+    shown: bool,
     on_open_change: Option<Rc<dyn Fn(&bool, &mut Window, &mut App)>>,
+    /// Whether the trigger and the content are hovered, for hover-triggered popovers.
+    trigger_hovered: bool,
+    content_hovered: bool,
+    /// The pending delayed open or close of a hover-triggered popover.
+    hover_task: Option<Task<()>>,
 
     _dismiss_subscription: Option<Subscription>,
 }
@@ -221,8 +348,14 @@ impl PopoverState {
             focus_handle: cx.focus_handle(),
             tracked_focus_handle: None,
             trigger_bounds: Bounds::default(),
+            content_height: px(0.),
+            placement: Anchor::TopLeft,
             open: default_open,
+            shown: false,
             on_open_change: None,
+            trigger_hovered: false,
+            content_hovered: false,
+            hover_task: None,
             _dismiss_subscription: None,
         }
     }
@@ -232,6 +365,15 @@ impl PopoverState {
         self.open
     }
 
+    /// Returns the resolved placement of the popover content.
+    ///
+    /// This is updated on every render before the content builder runs. A top anchor
+    /// means the content opened below the trigger, a bottom anchor means above it,
+    /// which can differ from the popover's anchor when it flipped, see [`Popover::flip`].
+    pub fn placement(&self) -> Anchor {
+        self.placement
+    }
+
     /// Dismiss the popover if it is open.
     pub fn dismiss(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         if self.open {
@@ -247,16 +389,24 @@ impl PopoverState {
     }
 
     fn toggle_open(&mut self, window: &mut Window, cx: &mut Context<Self>) {
-        self.open = !self.open;
+        self.set_open(!self.open, true, window, cx);
+    }
+
+    /// Set the open state, moving focus into the popover on open if `focus` is set.
+    fn set_open(&mut self, open: bool, focus: bool, window: &mut Window, cx: &mut Context<Self>) {
+        self.open = open;
+        self.hover_task = None;
         if self.open {
             let state = cx.entity();
-            let focus_handle = if let Some(tracked_focus_handle) = self.tracked_focus_handle.clone()
-            {
-                tracked_focus_handle
-            } else {
-                self.focus_handle.clone()
-            };
-            focus_handle.focus(window, cx);
+            if focus {
+                let focus_handle =
+                    if let Some(tracked_focus_handle) = self.tracked_focus_handle.clone() {
+                        tracked_focus_handle
+                    } else {
+                        self.focus_handle.clone()
+                    };
+                focus_handle.focus(window, cx);
+            }
 
             self._dismiss_subscription =
                 Some(
@@ -269,6 +419,9 @@ impl PopoverState {
                 );
         } else {
             self._dismiss_subscription = None;
+            // The content is gone, a stale hover would keep the next open alive
+            self.trigger_hovered = false;
+            self.content_hovered = false;
         }
 
         if let Some(callback) = self.on_open_change.as_ref() {
@@ -280,6 +433,40 @@ impl PopoverState {
     fn on_action_cancel(&mut self, _: &Cancel, window: &mut Window, cx: &mut Context<Self>) {
         self.dismiss(window, cx);
     }
+
+    /// Track the hover of the trigger or the content, scheduling the open or close
+    /// of a hover-triggered popover after the matching delay.
+    fn set_hovered(
+        &mut self,
+        content: bool,
+        hovered: bool,
+        (show_delay, hide_delay): (Duration, Duration),
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if content {
+            self.content_hovered = hovered;
+        } else {
+            self.trigger_hovered = hovered;
+        }
+
+        let open = self.trigger_hovered || self.content_hovered;
+        if open == self.open {
+            self.hover_task = None;
+            return;
+        }
+
+        let delay = if open { show_delay } else { hide_delay };
+        self.hover_task = Some(cx.spawn_in(window, async move |this, cx| {
+            cx.background_executor().timer(delay).await;
+            _ = this.update_in(cx, |state, window, cx| {
+                if state.open != open {
+                    state.set_open(open, false, window, cx);
+                    window.refresh();
+                }
+            });
+        }));
+    }
 }
 
 impl Focusable for PopoverState {
@@ -309,7 +496,7 @@ impl Popover {
     {
         deferred(
             anchored()
-                .snap_to_window_with_margin(px(8.))
+                .snap_to_window_with_margin(px(WINDOW_MARGIN))
                 .anchor(anchor)
                 .position(Self::resolved_corner(anchor, trigger_bounds))
                 .child(div().relative().child(content)),
@@ -317,6 +504,96 @@ impl Popover {
         .with_priority(1)
     }
 
+    /// Render the arrow of [`Popover::with_arrow`], absolutely positioned on the edge of
+    /// the content facing the trigger.
+    fn render_arrow(anchor: Anchor, trigger_bounds: Bounds<Pixels>, cx: &App) -> AnyElement {
+        let background = cx.theme().popover;
+        let border = cx.theme().border;
+        let points_up = anchor.is_top();
+        // The content is aligned with the trigger edge of the anchor, so its middle
+        // is half the trigger width from that edge
+        let offset = (trigger_bounds.size.width.half() - px(ARROW_WIDTH / 2.))
+            .max(px(ARROW_INSET - ARROW_WIDTH / 2.));
+
+        div()
+            .absolute()
+            .w(px(ARROW_WIDTH))
+            // One more pixel to cover the content border under the arrow base
+            .h(px(ARROW_HEIGHT + 1.))
+            .map(|this| {
+                if points_up {
+                    this.top(px(-ARROW_HEIGHT - 1.))
+                } else {
+                    this.bottom(px(-ARROW_HEIGHT - 1.))
+                }
+            })
+            .map(|this| {
+                if anchor.is_left() {
+                    this.left(offset)
+                } else if anchor.is_right() {
+                    this.right(offset)
+                } else {
+                    this.left_1_2().ml(px(-ARROW_WIDTH / 2.))
+                }
+            })
+            .child(
+                canvas(
+                    |_, _, _| {},
+                    move |bounds, _, window, _| {
+                        let (base_y, tip_y) = if points_up {
+                            (bounds.bottom(), bounds.top() + px(1.))
+                        } else {
+                            (bounds.top(), bounds.bottom() - px(1.))
+                        };
+                        let base_left = point(bounds.left(), base_y);
+                        let base_right = point(bounds.right(), base_y);
+                        let tip = point(bounds.center().x, tip_y);
+
+                        let mut fill = PathBuilder::fill();
+                        fill.move_to(base_left);
+                        fill.line_to(tip);
+                        fill.line_to(base_right);
+                        fill.close();
+                        if let Ok(path) = fill.build() {
+                            window.paint_path(path, background);
+                        }
+
+                        let mut stroke = PathBuilder::stroke(px(1.));
+                        stroke.move_to(base_left);
+                        stroke.line_to(tip);
+                        stroke.line_to(base_right);
+                        if let Ok(path) = stroke.build() {
+                            window.paint_path(path, border);
+                        }
+                    },
+                )
+                .size_full(),
+            )
+            .into_any_element()
+    }
+
+    /// Render the transparent full-window layer behind a modal popover.
+    ///
+    /// It is deferred at the same priority as [`Self::render_popover`] and must be
+    /// added before it, so the popover paints above the backdrop.
+    fn render_backdrop(window: &mut Window) -> Deferred {
+        let view_size = window.viewport_size();
+
+        deferred(
+            anchored().position(point(px(0.), px(0.))).child(
+                div()
+                    .id("popover-backdrop")
+                    .occlude()
+                    .w(view_size.width)
+                    .h(view_size.height)
+                    // Dismissal is handled by the content's `on_mouse_down_out`,
+                    // the backdrop only keeps the click from the elements below.
+                    .on_any_mouse_down(|_, _, cx| cx.stop_propagation()),
+            ),
+        )
+        .with_priority(1)
+    }
+
     pub(crate) fn render_popover_content(
         anchor: Anchor,
         appearance: bool,
@@ -344,10 +621,22 @@ impl RenderOnce for Popover {
             PopoverState::new(default_open, cx)
         });
 
+        let flip = self.flip;
+        let viewport_height = window.viewport_size().height;
         state.update(cx, |state, _| {
             if let Some(tracked_focus_handle) = tracked_focus_handle {
                 state.tracked_focus_handle = Some(tracked_focus_handle);
             }
+            state.placement = if flip {
+                Self::flipped_anchor(
+                    self.anchor,
+                    state.trigger_bounds,
+                    state.content_height,
+                    viewport_height,
+                )
+            } else {
+                self.anchor
+            };
             state.on_open_change = self.on_open_change.clone();
             if let Some(force_open) = force_open {
                 state.open = force_open;
@@ -355,6 +644,7 @@ impl RenderOnce for Popover {
         });
 
         let open = state.read(cx).open;
+        let anchor = state.read(cx).placement;
         let focus_handle = state.read(cx).focus_handle.clone();
         let trigger_bounds = state.read(cx).trigger_bounds;
 
@@ -364,22 +654,33 @@ impl RenderOnce for Popover {
 
         let parent_view_id = window.current_view();
         let popover_id = self.id.clone();
+        let hover_delays = self.hover_delays;
 
         let el = div()
             .id(popover_id.clone())
             .child((trigger)(open, window, cx))
-            .on_mouse_down(self.mouse_button, {
-                let state = state.clone();
-                move |_, window, cx| {
-                    cx.stop_propagation();
-                    state.update(cx, |state, cx| {
-                        // We force set open to false to toggle it correctly.
-                        // Because if the mouse down out will toggle open first.
-                        state.open = open;
-                        state.toggle_open(window, cx);
-                    });
-                    cx.notify(parent_view_id);
-                }
+            .map(|this| match hover_delays {
+                Some(delays) => this.on_hover({
+                    let state = state.clone();
+                    move |hovered, window, cx| {
+                        state.update(cx, |state, cx| {
+                            state.set_hovered(false, *hovered, delays, window, cx);
+                        });
+                    }
+                }),
+                None => this.on_mouse_down(self.mouse_button, {
+                    let state = state.clone();
+                    move |_, window, cx| {
+                        cx.stop_propagation();
+                        state.update(cx, |state, cx| {
+                            // We force set open to false to toggle it correctly.
+                            // Because if the mouse down out will toggle open first.
+                            state.open = open;
+                            state.toggle_open(window, cx);
+                        });
+                        cx.notify(parent_view_id);
+                    }
+                }),
             })
             .on_prepaint({
                 let state = state.clone();
@@ -391,7 +692,7 @@ impl RenderOnce for Popover {
             });
 
         let motion = cx.theme().motion.clone();
-        let reduced_motion = GlobalState::global(cx).reduced_motion();
+        let reduced_motion = !cx.should_animate();
         let open_duration_ms = if reduced_motion {
             motion.fast_duration_ms
         } else {
@@ -410,6 +711,22 @@ impl RenderOnce for Popover {
             window,
             cx,
         );
+
+        let was_shown = state.read(cx).shown;
+        let lifecycle = match (was_shown, presence.phase) {
+            (false, PresencePhase::Entering | PresencePhase::Entered) => {
+                Some((true, self.on_shown.clone()))
+            }
+            (true, PresencePhase::Exited) => Some((false, self.on_hidden.clone())),
+            _ => None,
+        };
+        if let Some((shown, callback)) = lifecycle {
+            state.update(cx, |state, _| state.shown = shown);
+            if let Some(callback) = callback {
+                window.defer(cx, move |window, cx| callback(window, cx));
+            }
+        }
+
         if !presence.should_render() {
             return el;
         }
@@ -418,97 +735,128 @@ impl RenderOnce for Popover {
         let open_transform_anim =
             spring_preset_animation(&motion, reduced_motion, SpringPreset::Medium);
         let close_anim = point_to_point_animation(&motion, reduced_motion);
-        let vertical_direction = if matches!(
-            self.anchor,
-            Anchor::TopLeft | Anchor::TopCenter | Anchor::TopRight
-        ) {
-            1.0
-        } else {
-            -1.0
-        };
-
-        let popover_content =
-            Self::render_popover_content(self.anchor, self.appearance, window, cx)
-                .track_focus(&focus_handle)
-                .key_context(CONTEXT)
-                .on_action(window.listener_for(&state, PopoverState::on_action_cancel))
-                .when_some(self.content, |this, content| {
-                    this.child(state.update(cx, |state, cx| (content)(state, window, cx)))
+        let vertical_direction = if anchor.is_top() { 1.0 } else { -1.0 };
+
+        let arrow =
+            (self.arrow && self.appearance).then(|| Self::render_arrow(anchor, trigger_bounds, cx));
+        let popover_content = Self::render_popover_content(anchor, self.appearance, window, cx)
+            .when(arrow.is_some(), |this| {
+                // Leave room for the arrow between the trigger and the content
+                let gap = px(ARROW_HEIGHT + 4.);
+                if anchor.is_top() {
+                    this.top(gap)
+                } else {
+                    this.bottom(gap)
+                }
+            })
+            .on_prepaint({
+                let state = state.clone();
+                move |bounds, window, cx| {
+                    let changed = state.update(cx, |state, _| {
+                        let changed = state.content_height != bounds.size.height;
+                        state.content_height = bounds.size.height;
+                        changed
+                    });
+                    // Re-render to flip with the measured height
+                    if changed && flip {
+                        window.refresh();
+                    }
+                }
+            })
+            .track_focus(&focus_handle)
+            .key_context(CONTEXT)
+            .on_action(window.listener_for(&state, PopoverState::on_action_cancel))
+            .when_some(self.content, |this, content| {
+                this.child(state.update(cx, |state, cx| (content)(state, window, cx)))
+            })
+            .children(self.children)
+            .children(arrow)
+            .when_some(hover_delays, |this, delays| {
+                this.on_hover({
+                    let state = state.clone();
+                    move |hovered, window, cx| {
+                        state.update(cx, |state, cx| {
+                            state.set_hovered(true, *hovered, delays, window, cx);
+                        });
+                    }
                 })
-                .children(self.children)
-                .when(self.overlay_closable, |this| {
-                    this.on_mouse_down_out({
-                        let state = state.clone();
-                        move |_, window, cx| {
-                            state.update(cx, |state, cx| {
-                                state.dismiss(window, cx);
-                            });
-                            cx.notify(parent_view_id);
-                        }
-                    })
+            })
+            .when(self.overlay_closable, |this| {
+                this.on_mouse_down_out({
+                    let state = state.clone();
+                    move |_, window, cx| {
+                        state.update(cx, |state, cx| {
+                            state.dismiss(window, cx);
+                        });
+                        cx.notify(parent_view_id);
+                    }
                 })
-                .refine_style(&self.style)
-                .map(move |el| {
-                    if !presence.transition_active() {
-                        el.opacity(presence.progress(1.0))
-                            .translate_y(px(0.0))
+            })
+            .refine_style(&self.style)
+            .map(move |el| {
+                if !presence.transition_active() {
+                    el.opacity(presence.progress(1.0))
+                        .translate_y(px(0.0))
+                        .into_any_element()
+                } else if matches!(presence.phase, PresencePhase::Entering) {
+                    let translated = if let Some(anim) = open_transform_anim {
+                        div()
+                            .child(el)
+                            .with_animation(
+                                SharedString::from("popover-open-transform"),
+                                anim,
+                                move |el, delta| {
+                                    el.translate_y(px(6.0 * (1.0 - delta) * vertical_direction))
+                                },
+                            )
                             .into_any_element()
-                    } else if matches!(presence.phase, PresencePhase::Entering) {
-                        let translated = if let Some(anim) = open_transform_anim {
-                            div()
-                                .child(el)
-                                .with_animation(
-                                    SharedString::from("popover-open-transform"),
-                                    anim,
-                                    move |el, delta| {
-                                        el.translate_y(px(6.0 * (1.0 - delta) * vertical_direction))
-                                    },
-                                )
-                                .into_any_element()
-                        } else {
-                            el.into_any_element()
-                        };
-                        if let Some(anim) = open_fade_anim {
-                            div()
-                                .child(translated)
-                                .with_animation(
-                                    SharedString::from("popover-open-fade"),
-                                    anim,
-                                    move |el, delta| {
-                                        let opacity = presence.progress(delta).clamp(0.0, 1.0);
-                                        el.opacity(opacity)
-                                    },
-                                )
-                                .into_any_element()
-                        } else {
-                            div()
-                                .child(translated)
-                                .opacity(presence.progress(1.0))
-                                .into_any_element()
-                        }
                     } else {
-                        if let Some(anim) = close_anim {
-                            el.with_animation(
-                                SharedString::from(format!(
-                                    "popover-close-motion-{}",
-                                    u8::from(matches!(presence.phase, PresencePhase::Entering))
-                                )),
+                        el.into_any_element()
+                    };
+                    if let Some(anim) = open_fade_anim {
+                        div()
+                            .child(translated)
+                            .with_animation(
+                                SharedString::from("popover-open-fade"),
                                 anim,
                                 move |el, delta| {
-                                    let progress = presence.progress(delta).clamp(0.0, 1.0);
-                                    let offset = px(6.0 * (1.0 - progress) * vertical_direction);
-                                    el.opacity(progress).translate_y(offset)
+                                    let opacity = presence.progress(delta).clamp(0.0, 1.0);
+                                    el.opacity(opacity)
                                 },
                             )
                             .into_any_element()
-                        } else {
-                            el.into_any_element()
-                        }
+                    } else {
+                        div()
+                            .child(translated)
+                            .opacity(presence.progress(1.0))
+                            .into_any_element()
                     }
-                });
+                } else {
+                    if let Some(anim) = close_anim {
+                        el.with_animation(
+                            SharedString::from(format!(
+                                "popover-close-motion-{}",
+                                u8::from(matches!(presence.phase, PresencePhase::Entering))
+                            )),
+                            anim,
+                            move |el, delta| {
+                                let progress = presence.progress(delta).clamp(0.0, 1.0);
+                                let offset = px(6.0 * (1.0 - progress) * vertical_direction);
+                                el.opacity(progress).translate_y(offset)
+                            },
+                        )
+                        .into_any_element()
+                    } else {
+                        el.into_any_element()
+                    }
+                }
+            });
 
-        el.child(Self::render_popover(
-            self.anchor,
+        el.when(self.modal && open, |this| {
+            this.child(Self::render_backdrop(window))
+        })
+        .child(Self::render_popover(
+            anchor,
             trigger_bounds,
             popover_content,
             window,
@@ -529,13 +877,22 @@ mod tests {
             .mouse_button(MouseButton::Right)
             .default_open(true)
             .appearance(false)
-            .overlay_closable(false);
+            .with_arrow(true)
+            .modal(true)
+            .overlay_closable(false)
+            .trigger_on_hover(Duration::from_millis(300), Duration::from_millis(100));
 
         assert_eq!(popover.anchor, Anchor::BottomCenter);
         assert_eq!(popover.mouse_button, MouseButton::Right);
         assert!(popover.default_open);
         assert!(!popover.appearance);
+        assert!(popover.arrow);
+        assert!(popover.modal);
         assert!(!popover.overlay_closable);
+        assert_eq!(
+            popover.hover_delays,
+            Some((Duration::from_millis(300), Duration::from_millis(100)))
+        );
     }
 
     #[test]
@@ -577,4 +934,45 @@ mod tests {
         assert_eq!(pos.x, px(300.));
         assert_eq!(pos.y, px(50.));
     }
+
+    #[test]
+    fn test_flipped_anchor() {
+        let viewport_height = px(600.);
+        let trigger_at = |y: f32| Bounds {
+            origin: Point {
+                x: px(100.),
+                y: px(y),
+            },
+            size: gpui::Size {
+                width: px(80.),
+                height: px(20.),
+            },
+        };
+
+        // Near the bottom of the window, a popover opening below flips above
+        assert_eq!(
+            Popover::flipped_anchor(Anchor::TopLeft, trigger_at(500.), px(200.), viewport_height),
+            Anchor::BottomLeft
+        );
+        // It fits below, keep it
+        assert_eq!(
+            Popover::flipped_anchor(Anchor::TopLeft, trigger_at(100.), px(200.), viewport_height),
+            Anchor::TopLeft
+        );
+        // Near the top, a popover opening above flips below
+        assert_eq!(
+            Popover::flipped_anchor(
+                Anchor::BottomRight,
+                trigger_at(40.),
+                px(200.),
+                viewport_height
+            ),
+            Anchor::TopRight
+        );
+        // Not measured yet
+        assert_eq!(
+            Popover::flipped_anchor(Anchor::TopCenter, trigger_at(500.), px(0.), viewport_height),
+            Anchor::TopCenter
+        );
+    }
 }
@@ -162,7 +162,7 @@ impl RenderOnce for Radio {
                 .items_start()
                 .line_height(relative(1.))
                 .rounded(cx.theme().radius * 0.5)
-                .focus_ring(is_focused, px(2.), window, cx)
+                .outer_focus_ring(is_focused, px(2.), window, cx)
                 .map(|this| match self.size {
                     Size::XSmall => this.text_xs(),
                     Size::Small => this.text_sm(),
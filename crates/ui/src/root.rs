@@ -2,6 +2,7 @@ use crate::{
     ActiveTheme, Anchor, ElementExt, Placement, StyledExt,
     dialog::{Dialog, close_animation_duration},
     focus_trap::FocusTrapManager,
+    global_state::{GlobalState, InputModality},
     input::InputState,
     notification::{Notification, NotificationList},
     sheet::Sheet,
@@ -478,7 +479,7 @@ impl Styled for Root {
 
 impl Render for Root {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
-        window.set_rem_size(cx.theme().font_size);
+        window.set_rem_size(cx.theme().scaled_font_size());
 
         window_border().child(
             div()
@@ -486,6 +487,9 @@ impl Render for Root {
                 .key_context(CONTEXT)
                 .on_action(cx.listener(Self::on_action_tab))
                 .on_action(cx.listener(Self::on_action_tab_prev))
+                .capture_any_mouse_down(|_, window, cx| {
+                    GlobalState::global_mut(cx).set_input_modality(InputModality::Pointer, window);
+                })
                 .relative()
                 .size_full()
                 .font_family(cx.theme().font_family.clone())
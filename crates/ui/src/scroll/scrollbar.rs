@@ -19,7 +19,6 @@ use serde::{Deserialize, Serialize};
 
 /// The width of the scrollbar (THUMB_ACTIVE_INSET * 2 + THUMB_ACTIVE_WIDTH)
 const WIDTH: Pixels = px(4. * 2. + 8.);
-const MIN_THUMB_SIZE: f32 = 48.;
 
 const THUMB_WIDTH: Pixels = px(6.);
 const THUMB_RADIUS: Pixels = px(6. / 2.);
@@ -396,7 +395,7 @@ impl Scrollbar {
         (
             cx.theme().scrollbar_thumb_hover,
             cx.theme().scrollbar,
-            cx.theme().border,
+            cx.theme().scrollbar_track,
             THUMB_ACTIVE_WIDTH,
             THUMB_ACTIVE_INSET,
             THUMB_ACTIVE_RADIUS,
@@ -407,7 +406,7 @@ impl Scrollbar {
         (
             cx.theme().scrollbar_thumb_hover,
             cx.theme().scrollbar,
-            cx.theme().border,
+            cx.theme().scrollbar_track,
             THUMB_ACTIVE_WIDTH,
             THUMB_ACTIVE_INSET,
             THUMB_ACTIVE_RADIUS,
@@ -575,8 +574,12 @@ impl Element for Scrollbar {
                 continue;
             }
 
+            let min_thumb_size = cx
+                .theme()
+                .scrollbar_min_thumb_size
+                .min(container_size - margin_end);
             let thumb_length =
-                (container_size / scroll_area_size * container_size).max(px(MIN_THUMB_SIZE));
+                (container_size / scroll_area_size * container_size).max(min_thumb_size);
             let thumb_start = -(scroll_position / (scroll_area_size - container_size)
                 * (container_size - margin_end - thumb_length));
             let thumb_end = (thumb_start + thumb_length).min(container_size - margin_end);
@@ -0,0 +1,156 @@
+use crate::{ActiveTheme, Collapsible, h_flex, sidebar::SidebarItem};
+use gpui::{
+    App, ElementId, IntoElement, ParentElement, SharedString, Styled as _, Window, div,
+    prelude::FluentBuilder as _, px,
+};
+
+/// A non-interactive section label in the [`super::Sidebar`].
+///
+/// Unlike [`super::SidebarGroup`], a label has no children, so it can be
+/// placed between groups to title a run of them. When the sidebar is
+/// collapsed the text is replaced by a short rule.
+#[derive(Clone)]
+pub struct SidebarGroupLabel {
+    label: SharedString,
+    collapsed: bool,
+}
+
+impl SidebarGroupLabel {
+    /// Create a new [`SidebarGroupLabel`] with the given text.
+    pub fn new(label: impl Into<SharedString>) -> Self {
+        Self {
+            label: label.into(),
+            collapsed: false,
+        }
+    }
+}
+
+impl Collapsible for SidebarGroupLabel {
+    fn is_collapsed(&self) -> bool {
+        self.collapsed
+    }
+
+    fn collapsed(mut self, collapsed: bool) -> Self {
+        self.collapsed = collapsed;
+        self
+    }
+}
+
+impl SidebarItem for SidebarGroupLabel {
+    fn render(self, _: impl Into<ElementId>, _: &mut Window, cx: &mut App) -> impl IntoElement {
+        h_flex()
+            .flex_shrink_0()
+            .when(self.collapsed, |this| {
+                this.h_4()
+                    .justify_center()
+                    .child(div().w_4().h(px(1.)).bg(cx.theme().sidebar_border))
+            })
+            .when(!self.collapsed, |this| {
+                this.h_8()
+                    .px_2()
+                    .text_xs()
+                    .text_color(cx.theme().sidebar_foreground.opacity(0.7))
+                    .child(self.label)
+            })
+    }
+}
+
+/// A horizontal separator line between items in the [`super::Sidebar`].
+#[derive(Clone, Default)]
+pub struct SidebarDivider {
+    collapsed: bool,
+}
+
+impl SidebarDivider {
+    /// Create a new [`SidebarDivider`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Collapsible for SidebarDivider {
+    fn is_collapsed(&self) -> bool {
+        self.collapsed
+    }
+
+    fn collapsed(mut self, collapsed: bool) -> Self {
+        self.collapsed = collapsed;
+        self
+    }
+}
+
+impl SidebarItem for SidebarDivider {
+    fn render(self, _: impl Into<ElementId>, _: &mut Window, cx: &mut App) -> impl IntoElement {
+        div()
+            .flex_shrink_0()
+            .h(px(1.))
+            .mx_2()
+            .my_1()
+            .bg(cx.theme().sidebar_border)
+    }
+}
+
+/// An item in the [`super::Sidebar`] that is either `E`, a [`SidebarGroupLabel`]
+/// or a [`SidebarDivider`].
+///
+/// [`super::Sidebar`] holds a single item type, use this to mix labels and
+/// dividers with groups, e.g. `Sidebar<SidebarEntry<SidebarGroup<SidebarMenu>>>`.
+#[derive(Clone)]
+pub enum SidebarEntry<E: SidebarItem + 'static> {
+    Item(E),
+    Label(SidebarGroupLabel),
+    Divider(SidebarDivider),
+}
+
+impl<E: SidebarItem> From<SidebarGroupLabel> for SidebarEntry<E> {
+    fn from(label: SidebarGroupLabel) -> Self {
+        Self::Label(label)
+    }
+}
+
+impl<E: SidebarItem> From<SidebarDivider> for SidebarEntry<E> {
+    fn from(divider: SidebarDivider) -> Self {
+        Self::Divider(divider)
+    }
+}
+
+impl<E: SidebarItem> Collapsible for SidebarEntry<E> {
+    fn is_collapsed(&self) -> bool {
+        match self {
+            Self::Item(item) => item.is_collapsed(),
+            Self::Label(label) => label.is_collapsed(),
+            Self::Divider(divider) => divider.is_collapsed(),
+        }
+    }
+
+    fn collapsed(self, collapsed: bool) -> Self {
+        match self {
+            Self::Item(item) => Self::Item(item.collapsed(collapsed)),
+            Self::Label(label) => Self::Label(label.collapsed(collapsed)),
+            Self::Divider(divider) => Self::Divider(divider.collapsed(collapsed)),
+        }
+    }
+}
+
+impl<E: SidebarItem> SidebarItem for SidebarEntry<E> {
+    fn render(
+        self,
+        id: impl Into<ElementId>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> impl IntoElement {
+        match self {
+            Self::Item(item) => item.render(id, window, cx).into_any_element(),
+            Self::Label(label) => label.render(id, window, cx).into_any_element(),
+            Self::Divider(divider) => divider.render(id, window, cx).into_any_element(),
+        }
+    }
+
+    fn rail(self, rail: bool) -> Self {
+        match self {
+            Self::Item(item) => Self::Item(item.rail(rail)),
+            Self::Label(label) => Self::Label(label.rail(rail)),
+            Self::Divider(divider) => Self::Divider(divider.rail(rail)),
+        }
+    }
+}
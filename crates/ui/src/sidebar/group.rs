@@ -1,14 +1,26 @@
-use crate::{ActiveTheme, Collapsible, h_flex, sidebar::SidebarItem, v_flex};
+use std::time::Duration;
+
+use crate::{
+    ActiveTheme, Anchor, Collapsible, StyledExt as _, h_flex, popover::Popover,
+    sidebar::SidebarItem, v_flex,
+};
 use gpui::{
-    App, ElementId, IntoElement, ParentElement, SharedString, Styled as _, Window, div,
-    prelude::FluentBuilder as _,
+    App, ElementId, Entity, InteractiveElement as _, IntoElement, ParentElement, SharedString,
+    StatefulInteractiveElement as _, Styled as _, Window, div, prelude::FluentBuilder as _, px,
 };
 
+use super::menu::SidebarCollapsedSubmenuTrigger;
+
+/// Delay before a rail flyout closes once the mouse leaves it, to allow moving
+/// from the rail to the flyout.
+const RAIL_FLYOUT_CLOSE_DELAY: Duration = Duration::from_millis(120);
+
 /// A group of items in the [`super::Sidebar`].
 #[derive(Clone)]
 pub struct SidebarGroup<E: SidebarItem + 'static> {
     label: SharedString,
     collapsed: bool,
+    rail: bool,
     children: Vec<E>,
 }
 
@@ -18,6 +30,7 @@ impl<E: SidebarItem> SidebarGroup<E> {
         Self {
             label: label.into(),
             collapsed: false,
+            rail: false,
             children: Vec::new(),
         }
     }
@@ -35,6 +48,124 @@ impl<E: SidebarItem> SidebarGroup<E> {
         self.children.extend(children);
         self
     }
+
+    /// Render the collapsed items, with a flyout of the label and expanded items
+    /// while hovered.
+    fn render_rail(self, id: ElementId, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let hover = window.use_keyed_state(
+            SharedString::from(format!("{}-rail-hover", id)),
+            cx,
+            |_, _| RailHoverState::default(),
+        );
+        let open = hover.read(cx).open;
+        let label = self.label.clone();
+        let children = self.children.clone();
+        let flyout_id = SharedString::from(format!("{}-rail-flyout", id));
+
+        let rail_items = div()
+            .id(SharedString::from(format!("{}-rail", id)))
+            .gap_2()
+            .flex_col()
+            .on_hover({
+                let hover = hover.clone();
+                move |hovered, _, cx| RailHoverState::set(&hover, false, *hovered, cx)
+            })
+            .children(self.children.into_iter().enumerate().map(|(ix, child)| {
+                child
+                    .collapsed(true)
+                    .render(format!("{}-{}", id, ix), window, cx)
+                    .into_any_element()
+            }));
+
+        Popover::new(SharedString::from(format!("{}-rail-popover", id)))
+            .appearance(false)
+            .anchor(Anchor::TopRight)
+            .open(open)
+            .on_open_change({
+                let hover = hover.clone();
+                move |open, _, cx| {
+                    if !*open {
+                        hover.update(cx, |state, cx| {
+                            *state = RailHoverState::default();
+                            cx.notify();
+                        });
+                    }
+                }
+            })
+            .trigger(SidebarCollapsedSubmenuTrigger::new(
+                rail_items.into_any_element(),
+            ))
+            .content(move |_, window, cx| {
+                v_flex()
+                    .id(flyout_id.clone())
+                    .popover_style(cx)
+                    .p_1()
+                    .gap_1()
+                    .w(px(220.))
+                    .on_hover({
+                        let hover = hover.clone();
+                        move |hovered, _, cx| RailHoverState::set(&hover, true, *hovered, cx)
+                    })
+                    .child(
+                        h_flex()
+                            .px_2()
+                            .h_8()
+                            .text_xs()
+                            .text_color(cx.theme().popover_foreground.opacity(0.7))
+                            .child(label.clone()),
+                    )
+                    .children(children.clone().into_iter().enumerate().map(|(ix, child)| {
+                        child
+                            .collapsed(false)
+                            .render(format!("{}-{}", flyout_id, ix), window, cx)
+                            .into_any_element()
+                    }))
+                    .into_any_element()
+            })
+    }
+}
+
+/// Hover state of a rail group and its flyout.
+#[derive(Default)]
+struct RailHoverState {
+    rail_hovered: bool,
+    flyout_hovered: bool,
+    open: bool,
+}
+
+impl RailHoverState {
+    /// Open the flyout on hover, and close it shortly after neither the rail
+    /// group nor the flyout is hovered.
+    fn set(state: &Entity<Self>, flyout: bool, hovered: bool, cx: &mut App) {
+        state.update(cx, |state, cx| {
+            if flyout {
+                state.flyout_hovered = hovered;
+            } else {
+                state.rail_hovered = hovered;
+            }
+            if hovered && !state.open {
+                state.open = true;
+                cx.notify();
+            }
+        });
+        if hovered {
+            return;
+        }
+
+        let state = state.clone();
+        cx.spawn(async move |cx| {
+            cx.background_executor()
+                .timer(RAIL_FLYOUT_CLOSE_DELAY)
+                .await;
+            _ = state.update(cx, |state, cx| {
+                if state.open && !state.rail_hovered && !state.flyout_hovered {
+                    state.open = false;
+                    cx.notify();
+                }
+            });
+        })
+        .detach();
+    }
 }
 
 impl<E: SidebarItem> Collapsible for SidebarGroup<E> {
@@ -56,6 +187,9 @@ impl<E: SidebarItem> SidebarItem for SidebarGroup<E> {
         cx: &mut App,
     ) -> impl IntoElement {
         let id = id.into();
+        if self.rail && !self.children.is_empty() {
+            return self.render_rail(id, window, cx).into_any_element();
+        }
 
         v_flex()
             .relative()
@@ -82,5 +216,11 @@ impl<E: SidebarItem> SidebarItem for SidebarGroup<E> {
                             .into_any_element()
                     })),
             )
+            .into_any_element()
+    }
+
+    fn rail(mut self, rail: bool) -> Self {
+        self.rail = rail;
+        self
     }
 }
@@ -1,11 +1,11 @@
 use crate::{
     ActiveTheme as _, Anchor, Collapsible, Icon, IconName, Selectable, Sizable as _, StyledExt,
     animation::{
-        PresenceOptions, PresencePhase, SpringPreset, keyed_presence, point_to_point_animation,
-        spring_preset_animation, spring_preset_duration_ms,
+        ActiveMotion, PresenceOptions, PresencePhase, SpringPreset, keyed_presence,
+        point_to_point_animation, spring_invoke_animation, spring_preset_animation,
+        spring_preset_duration_ms,
     },
     button::{Button, ButtonVariants as _},
-    global_state::GlobalState,
     h_flex,
     menu::{ContextMenuExt, PopupMenu, PopupMenuItem},
     popover::Popover,
@@ -13,34 +13,92 @@ use crate::{
     v_flex,
 };
 use gpui::{
-    AnimationExt as _, AnyElement, App, ClickEvent, Context, DismissEvent, ElementId, Entity,
-    Focusable, InteractiveElement as _, IntoElement, MouseButton, ParentElement as _, RenderOnce,
-    SharedString, StatefulInteractiveElement as _, StyleRefinement, Styled, Window, div,
-    percentage, prelude::FluentBuilder, px,
+    Animation, AnimationExt as _, AnyElement, App, ClickEvent, Context, DismissEvent, ElementId,
+    Entity, Focusable, InteractiveElement as _, IntoElement, MouseButton, ParentElement as _,
+    RenderOnce, SharedString, StatefulInteractiveElement as _, StyleRefinement, Styled, Window,
+    div, percentage, prelude::FluentBuilder, px,
 };
 use std::rc::Rc;
 use std::time::Duration;
 
 /// Generous max for animated submenu reveal.
 const SUBMENU_CONTENT_MAX_H: f32 = 1200.0;
+/// Extra scale a badge pulses from when its value changes.
+const BADGE_PULSE_SCALE: f32 = 0.3;
 
 fn submenu_height_progress(progress: f32) -> f32 {
     progress.clamp(0.0, 1.0).powf(3.0)
 }
 
+/// Last rendered badge value, to pulse the badge when it changes.
+struct SidebarBadgeState {
+    value: SharedString,
+    changes: u64,
+}
+
+/// Render a menu item badge, a pill with `value` or a dot on the icon corner
+/// when collapsed.
+///
+/// After `changes` > 0 value changes, the badge pulses with `animation`.
+fn render_badge(
+    value: SharedString,
+    collapsed: bool,
+    changes: u64,
+    animation: Option<Animation>,
+    cx: &App,
+) -> AnyElement {
+    let (text_size, dot_size) = (px(11.), px(6.));
+    let badge = h_flex()
+        .id("badge")
+        .flex_shrink_0()
+        .justify_center()
+        .rounded_full()
+        .bg(cx.theme().sidebar_primary)
+        .map(|this| {
+            if collapsed {
+                this.absolute().top_1().right_1().size(dot_size)
+            } else {
+                this.min_w_4()
+                    .px_1p5()
+                    .text_size(text_size)
+                    .text_color(cx.theme().sidebar_primary_foreground)
+                    .child(value)
+            }
+        });
+
+    match animation {
+        Some(animation) if changes > 0 => badge
+            .with_animation(
+                ElementId::NamedInteger("badge-pulse".into(), changes),
+                animation,
+                move |this, delta| {
+                    let scale = 1. + BADGE_PULSE_SCALE * (1. - delta);
+                    if collapsed {
+                        this.size(dot_size * scale)
+                    } else {
+                        this.text_size(text_size * scale)
+                    }
+                },
+            )
+            .into_any_element(),
+        _ => badge.into_any_element(),
+    }
+}
+
 #[derive(Default)]
 struct SidebarCollapsedSubmenuState {
     menu: Option<Entity<PopupMenu>>,
 }
 
+/// Popover trigger for flyouts of a collapsed sidebar, also used by rail groups.
 #[derive(IntoElement)]
-struct SidebarCollapsedSubmenuTrigger {
+pub(super) struct SidebarCollapsedSubmenuTrigger {
     selected: bool,
     element: AnyElement,
 }
 
 impl SidebarCollapsedSubmenuTrigger {
-    fn new(element: AnyElement) -> Self {
+    pub(super) fn new(element: AnyElement) -> Self {
         Self {
             selected: false,
             element,
@@ -214,10 +272,14 @@ pub struct SidebarMenuItem {
     handler: Rc<dyn Fn(&ClickEvent, &mut Window, &mut App)>,
     active: bool,
     default_open: bool,
+    open: Option<bool>,
+    on_open_change: Option<Rc<dyn Fn(bool, &mut Window, &mut App)>>,
     click_to_open: bool,
     collapsed: bool,
     children: Vec<Self>,
     suffix: Option<Rc<dyn Fn(&mut Window, &mut App) -> AnyElement + 'static>>,
+    badge: Option<SharedString>,
+    action_button: Option<(Icon, Rc<dyn Fn(&ClickEvent, &mut Window, &mut App)>)>,
     disabled: bool,
     context_menu: Option<Rc<dyn Fn(PopupMenu, &mut Window, &mut App) -> PopupMenu + 'static>>,
 }
@@ -232,9 +294,13 @@ impl SidebarMenuItem {
             active: false,
             collapsed: false,
             default_open: false,
+            open: None,
+            on_open_change: None,
             click_to_open: false,
             children: Vec::new(),
             suffix: None,
+            badge: None,
+            action_button: None,
             disabled: false,
             context_menu: None,
         }
@@ -275,6 +341,27 @@ impl SidebarMenuItem {
         self
     }
 
+    /// Force set the open state of the Submenu, e.g. to reveal the active item.
+    ///
+    /// If this is set, the submenu is controlled by this value and `default_open`
+    /// is ignored. Use it with [`SidebarMenuItem::on_open_change`] to handle changes.
+    pub fn open(mut self, open: bool) -> Self {
+        self.open = Some(open);
+        self
+    }
+
+    /// Add a callback to be called when the caret button or `click_to_open`
+    /// changes the open state of the Submenu.
+    ///
+    /// The callback receives the new open state.
+    pub fn on_open_change(
+        mut self,
+        handler: impl Fn(bool, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_open_change = Some(Rc::new(handler));
+        self
+    }
+
     /// Set whether clicking the menu item open the submenu.
     ///
     /// Default is `false`.
@@ -302,16 +389,63 @@ impl SidebarMenuItem {
         self
     }
 
+    /// Set a badge, such as an unread count, shown as a pill after the label.
+    ///
+    /// When collapsed, the badge is a dot on the corner of the icon. It pulses
+    /// when the value changes between renders.
+    pub fn badge(mut self, badge: impl Into<SharedString>) -> Self {
+        self.badge = Some(badge.into());
+        self
+    }
+
+    /// Set a secondary icon button shown at the right of the row on hover,
+    /// e.g. "open in new pane".
+    ///
+    /// Clicking it doesn't trigger the row's `on_click`. Hidden when collapsed.
+    pub fn action_button(
+        mut self,
+        icon: impl Into<Icon>,
+        on_click: impl Fn(&ClickEvent, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.action_button = Some((icon.into(), Rc::new(on_click)));
+        self
+    }
+
     /// Set disabled flat for menu item.
     pub fn disable(mut self, disable: bool) -> Self {
         self.disabled = disable;
         self
     }
 
+    /// Request a new open state, kept internally unless controlled by `open`.
+    fn set_open(
+        open_state: &Entity<bool>,
+        controlled: bool,
+        on_open_change: Option<&Rc<dyn Fn(bool, &mut Window, &mut App)>>,
+        open: bool,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        if !controlled {
+            open_state.update(cx, |is_open, cx| {
+                *is_open = open;
+                cx.notify();
+            });
+        }
+        if let Some(on_open_change) = on_open_change {
+            on_open_change(open, window, cx);
+        }
+    }
+
     fn is_submenu(&self) -> bool {
         self.children.len() > 0
     }
 
+    /// Whether this item or any of its descendants is active.
+    fn contains_active(&self) -> bool {
+        self.active || self.children.iter().any(Self::contains_active)
+    }
+
     /// Set the context menu for the menu item.
     pub fn context_menu(
         mut self,
@@ -353,9 +487,11 @@ impl SidebarItem for SidebarMenuItem {
         let is_hoverable = !is_active && !self.disabled;
         let is_disabled = self.disabled;
         let is_submenu = self.is_submenu();
-        let is_open = is_submenu && !is_collapsed && *open_state.read(cx);
+        let controlled_open = self.open.is_some();
+        let on_open_change = self.on_open_change.clone();
+        let is_open = is_submenu && !is_collapsed && self.open.unwrap_or(*open_state.read(cx));
         let show_collapsed_submenu = is_submenu && is_collapsed;
-        let reduced_motion = GlobalState::global(cx).reduced_motion();
+        let reduced_motion = !cx.should_animate();
         let motion = cx.theme().motion.clone();
         let open_duration = if reduced_motion {
             motion.fast_duration_ms
@@ -381,16 +517,54 @@ impl SidebarItem for SidebarMenuItem {
         let chevron_open_anim =
             spring_preset_animation(&motion, reduced_motion, SpringPreset::Mild);
         let chevron_close_anim = close_anim.clone();
+        let group_name = SharedString::from(format!("{}-item", state_key));
+        let focus_handle = window
+            .use_keyed_state(
+                SharedString::from(format!("{}-focus", state_key)),
+                cx,
+                |_, cx| cx.focus_handle().tab_stop(true),
+            )
+            .read(cx)
+            .clone();
+        let is_focused = focus_handle.is_focused(window);
+        let badge = self.badge.clone().map(|value| {
+            let state = window.use_keyed_state(
+                SharedString::from(format!("{}-badge", state_key)),
+                cx,
+                |_, _| SidebarBadgeState {
+                    value: value.clone(),
+                    changes: 0,
+                },
+            );
+            let changes = state.update(cx, |state, _| {
+                if state.value != value {
+                    state.value = value.clone();
+                    state.changes += 1;
+                }
+                state.changes
+            });
+            let animation = spring_invoke_animation(&motion, reduced_motion);
+            render_badge(value, is_collapsed, changes, animation, cx)
+        });
+        let (collapsed_badge, badge) = if is_collapsed {
+            (badge, None)
+        } else {
+            (None, badge)
+        };
 
         let item_element = h_flex()
             .size_full()
             .id("item")
+            .relative()
+            .group(group_name.clone())
+            .when(!is_disabled, |this| this.track_focus(&focus_handle))
             .overflow_x_hidden()
             .flex_shrink_0()
             .p_2()
             .gap_x_2()
             .rounded(cx.theme().radius)
             .text_sm()
+            .when(is_focused, |this| this.focus_ring(cx))
             .when(is_hoverable, |this| {
                 this.hover(|this| {
                     this.bg(cx.theme().sidebar_accent.opacity(0.8))
@@ -409,6 +583,7 @@ impl SidebarItem for SidebarMenuItem {
                         .text_color(cx.theme().sidebar_accent_foreground)
                 })
             })
+            .children(collapsed_badge)
             .when(!is_collapsed, |this| {
                 this.h_7()
                     .child(
@@ -423,10 +598,29 @@ impl SidebarItem for SidebarMenuItem {
                                     .overflow_x_hidden()
                                     .child(self.label.clone()),
                             )
+                            .children(badge)
                             .when_some(self.suffix.clone(), |this, suffix| {
                                 this.child(suffix(window, cx).into_any_element())
                             }),
                     )
+                    .when_some(
+                        self.action_button.clone().filter(|_| !is_disabled),
+                        |this, (icon, on_click)| {
+                            this.child(
+                                Button::new("action")
+                                    .xsmall()
+                                    .ghost()
+                                    .icon(icon)
+                                    .invisible()
+                                    .group_hover(group_name.clone(), |style| style.visible())
+                                    .on_click(move |ev, window, cx| {
+                                        // Avoid trigger item click
+                                        cx.stop_propagation();
+                                        on_click(ev, window, cx)
+                                    }),
+                            )
+                        },
+                    )
                     .when(is_submenu, |this| {
                         let caret_base = Icon::new(IconName::ChevronRight).size_4();
                         let caret_icon = if reduced_motion || !submenu_presence.transition_active()
@@ -477,13 +671,18 @@ impl SidebarItem for SidebarMenuItem {
                                 .child(caret_icon)
                                 .on_click({
                                     let open_state = open_state.clone();
-                                    move |_, _, cx| {
+                                    let on_open_change = on_open_change.clone();
+                                    move |_, window, cx| {
                                         // Avoid trigger item click, just expand/collapse submenu
                                         cx.stop_propagation();
-                                        open_state.update(cx, |is_open, cx| {
-                                            *is_open = !*is_open;
-                                            cx.notify();
-                                        })
+                                        Self::set_open(
+                                            &open_state,
+                                            controlled_open,
+                                            on_open_change.as_ref(),
+                                            !is_open,
+                                            window,
+                                            cx,
+                                        );
                                     }
                                 }),
                         )
@@ -496,11 +695,15 @@ impl SidebarItem for SidebarMenuItem {
                 this.on_click({
                     let open_state = open_state.clone();
                     move |ev, window, cx| {
-                        if click_to_open {
-                            open_state.update(cx, |is_open, cx| {
-                                *is_open = true;
-                                cx.notify();
-                            });
+                        if click_to_open && is_submenu && !is_open {
+                            Self::set_open(
+                                &open_state,
+                                controlled_open,
+                                on_open_change.as_ref(),
+                                true,
+                                window,
+                                cx,
+                            );
                         }
 
                         handler(ev, window, cx)
@@ -610,6 +813,10 @@ impl SidebarItem for SidebarMenuItem {
             item_element
         };
 
+        // Trace the connector line down to the child on the path to the active item
+        let active_branch_ix = self.children.iter().position(Self::contains_active);
+        let children_len = self.children.len();
+
         div()
             .id(id.clone())
             .w_full()
@@ -618,15 +825,27 @@ impl SidebarItem for SidebarMenuItem {
                 this.child(
                     v_flex()
                         .id("submenu")
-                        .border_l_1()
-                        .border_color(cx.theme().sidebar_border)
-                        .gap_1()
                         .ml_3p5()
-                        .pl_2p5()
-                        .py_0p5()
                         .children(self.children.into_iter().enumerate().map(|(ix, item)| {
                             let id = format!("{}-{}", id, ix);
-                            item.render(id, window, cx).into_any_element()
+                            let on_active_branch =
+                                active_branch_ix.is_some_and(|active_ix| ix <= active_ix);
+                            let is_last = ix + 1 == children_len;
+
+                            // Each child draws its own connector segment, padded instead of
+                            // gapped so the segments join into one line.
+                            div()
+                                .border_l_1()
+                                .border_color(if on_active_branch {
+                                    cx.theme().sidebar_primary
+                                } else {
+                                    cx.theme().sidebar_border
+                                })
+                                .pl_2p5()
+                                .when(ix == 0, |this| this.pt_0p5())
+                                .map(|this| if is_last { this.pb_0p5() } else { this.pb_1() })
+                                .child(item.render(id, window, cx))
+                                .into_any_element()
                         }))
                         .map(|el| {
                             if !submenu_presence.transition_active() {
@@ -1,28 +1,31 @@
 use crate::{
-    ActiveTheme, Collapsible, Icon, IconName, PixelsExt, Side, Sizable, StyledExt,
+    ActiveTheme, AxisExt, Collapsible, Icon, IconName, PixelsExt, Side, Sizable, StyledExt,
+    ThemeMotion,
     animation::{
-        PresenceOptions, PresencePhase, SpringPreset, keyed_presence, point_to_point_animation,
-        spring_preset_duration_ms,
+        ActiveMotion, PresenceOptions, PresencePhase, SpringPreset, keyed_presence,
+        point_to_point_animation, spring_preset_duration_ms,
     },
     button::{Button, ButtonVariants},
-    global_state::GlobalState,
     h_flex,
     scroll::ScrollableElement,
     v_flex,
 };
 use gpui::{
-    AnimationExt as _, AnyElement, App, ClickEvent, EdgesRefinement, ElementId,
-    InteractiveElement as _, IntoElement, ListAlignment, ListState, ParentElement, Pixels,
-    RenderOnce, SharedString, StyleRefinement, Styled, Window, div, list, prelude::FluentBuilder,
-    px,
+    AnimationExt as _, AnyElement, App, AppContext as _, Axis, ClickEvent, Context, Div,
+    DragMoveEvent, EdgesRefinement, ElementId, Empty, InteractiveElement as _, IntoElement,
+    ListAlignment, ListState, ParentElement, Pixels, Render, RenderOnce, SharedString, Stateful,
+    StatefulInteractiveElement as _, StyleRefinement, Styled, Window, div, list,
+    prelude::FluentBuilder, px,
 };
 use std::rc::Rc;
 use std::time::Duration;
 
+mod divider;
 mod footer;
 mod group;
 mod header;
 mod menu;
+pub use divider::*;
 pub use footer::*;
 pub use group::*;
 pub use header::*;
@@ -30,6 +33,11 @@ pub use menu::*;
 
 const DEFAULT_WIDTH: Pixels = px(255.);
 const COLLAPSED_WIDTH: Pixels = px(48.);
+const DEFAULT_HEIGHT: Pixels = px(120.);
+const COLLAPSED_HEIGHT: Pixels = px(48.);
+const DRAG_HANDLE_SIZE: Pixels = px(4.);
+const DEFAULT_MIN_WIDTH: Pixels = px(160.);
+const DEFAULT_MAX_WIDTH: Pixels = px(480.);
 
 pub trait SidebarItem: Collapsible + Clone {
     fn render(
@@ -38,6 +46,23 @@ pub trait SidebarItem: Collapsible + Clone {
         window: &mut Window,
         cx: &mut App,
     ) -> impl IntoElement;
+
+    /// Set whether the item is rendered in an icon rail, see [`Sidebar::rail`].
+    ///
+    /// Items are also collapsed in a rail. The default ignores it.
+    fn rail(self, _rail: bool) -> Self {
+        self
+    }
+}
+
+/// Drag payload for the sidebar edge, identifying the sidebar being dragged.
+#[derive(Clone)]
+struct SidebarEdgeDrag(ElementId);
+
+impl Render for SidebarEdgeDrag {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        Empty
+    }
 }
 
 enum SidebarSlot {
@@ -60,6 +85,15 @@ pub struct Sidebar<E: SidebarItem + 'static> {
     collapsible: bool,
     collapsed: bool,
     width: Pixels,
+    height: Pixels,
+    orientation: Axis,
+    collapse_threshold: Option<Pixels>,
+    on_collapse_change: Option<Rc<dyn Fn(bool, &mut Window, &mut App)>>,
+    rail: bool,
+    resizable: bool,
+    min_width: Pixels,
+    max_width: Pixels,
+    on_width_change: Option<Rc<dyn Fn(Pixels, &mut Window, &mut App)>>,
 }
 
 impl<E: SidebarItem> Sidebar<E> {
@@ -75,6 +109,15 @@ impl<E: SidebarItem> Sidebar<E> {
             collapsible: true,
             collapsed: false,
             width: DEFAULT_WIDTH,
+            height: DEFAULT_HEIGHT,
+            orientation: Axis::Vertical,
+            collapse_threshold: None,
+            on_collapse_change: None,
+            rail: false,
+            resizable: false,
+            min_width: DEFAULT_MIN_WIDTH,
+            max_width: DEFAULT_MAX_WIDTH,
+            on_width_change: None,
         }
     }
 
@@ -104,6 +147,86 @@ impl<E: SidebarItem> Sidebar<E> {
         self
     }
 
+    /// Set the expanded height of the sidebar, used in horizontal orientation.
+    pub fn height(mut self, height: impl Into<Pixels>) -> Self {
+        self.height = height.into();
+        self
+    }
+
+    /// Set the orientation of the sidebar.
+    ///
+    /// `Axis::Horizontal` lays items out in a row for top-docked navigation, and
+    /// collapsing reduces the height instead of the width.
+    ///
+    /// Default is `Axis::Vertical`.
+    pub fn orientation(mut self, orientation: Axis) -> Self {
+        self.orientation = orientation;
+        self
+    }
+
+    /// Collapse the sidebar when its edge is dragged narrower than `threshold`,
+    /// and expand it again when dragged back out past `threshold`.
+    ///
+    /// The new state is reported through [`Sidebar::on_collapse_change`].
+    pub fn collapse_on_drag(mut self, threshold: impl Into<Pixels>) -> Self {
+        self.collapse_threshold = Some(threshold.into());
+        self
+    }
+
+    /// Set the callback for when dragging the edge requests a collapsed state change.
+    ///
+    /// The callback receives the requested `collapsed` value.
+    pub fn on_collapse_change(
+        mut self,
+        handler: impl Fn(bool, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_collapse_change = Some(Rc::new(handler));
+        self
+    }
+
+    /// Render the sidebar as a permanent icon rail, default is false.
+    ///
+    /// A rail keeps the collapsed width regardless of [`Sidebar::collapsed`], and
+    /// hovering a group pops out a panel with its expanded items next to the rail.
+    /// Only applies in vertical orientation; dragging the edge is disabled.
+    pub fn rail(mut self, rail: bool) -> Self {
+        self.rail = rail;
+        self
+    }
+
+    /// Set the sidebar to be resizable by dragging its inner edge, default is false.
+    ///
+    /// Only applies in vertical orientation. The new width is reported through
+    /// [`Sidebar::on_width_change`].
+    pub fn resizable(mut self, resizable: bool) -> Self {
+        self.resizable = resizable;
+        self
+    }
+
+    /// Set the minimum width reachable by dragging the edge, default is 160px.
+    pub fn min_width(mut self, width: impl Into<Pixels>) -> Self {
+        self.min_width = width.into();
+        self
+    }
+
+    /// Set the maximum width reachable by dragging the edge, default is 480px.
+    pub fn max_width(mut self, width: impl Into<Pixels>) -> Self {
+        self.max_width = width.into();
+        self
+    }
+
+    /// Set the callback for when dragging the edge of a resizable sidebar.
+    ///
+    /// The callback receives the new width, clamped to `min_width`..=`max_width`;
+    /// pass it back with [`Sidebar::width`] on the next render.
+    pub fn on_width_change(
+        mut self,
+        handler: impl Fn(Pixels, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_width_change = Some(Rc::new(handler));
+        self
+    }
+
     /// Set the header of the sidebar.
     pub fn header(mut self, header: impl IntoElement) -> Self {
         self.header = Some(SidebarSlot::Static(header.into_any_element()));
@@ -235,13 +358,22 @@ impl<E: SidebarItem> Styled for Sidebar<E> {
 impl<E: SidebarItem> RenderOnce for Sidebar<E> {
     fn render(mut self, window: &mut Window, cx: &mut App) -> impl IntoElement {
         self.style.padding = EdgesRefinement::default();
+        let rail = self.rail && self.orientation.is_vertical();
+        if rail {
+            self.collapsed = true;
+        }
 
-        let reduced_motion = GlobalState::global(cx).reduced_motion();
+        let reduced_motion = !cx.should_animate();
         let motion = cx.theme().motion.clone();
         let target_collapsed = self.collapsed;
         let sidebar_id = self.id.clone();
-        let expanded_width = self.width;
-        let collapsed_width = COLLAPSED_WIDTH;
+        let horizontal = self.orientation.is_horizontal();
+        // The animated extent is the width when vertical and the height when horizontal.
+        let (expanded_extent, collapsed_extent) = if horizontal {
+            (self.height, COLLAPSED_HEIGHT)
+        } else {
+            (self.width, COLLAPSED_WIDTH)
+        };
         let width_spring_preset = SpringPreset::Medium;
         let width_spring_duration_ms = spring_preset_duration_ms(&motion, width_spring_preset);
         let open_duration_ms = if reduced_motion {
@@ -275,43 +407,24 @@ impl<E: SidebarItem> RenderOnce for Sidebar<E> {
             matches!(presence.phase, PresencePhase::Entering)
         };
         let transition_active = !reduced_motion && presence.transition_active();
-        let from_width = if target_collapsed {
-            expanded_width
+        let from_extent = if target_collapsed {
+            expanded_extent
         } else {
-            collapsed_width
+            collapsed_extent
         };
-        let to_width = if target_collapsed {
-            collapsed_width
+        let to_extent = if target_collapsed {
+            collapsed_extent
         } else {
-            expanded_width
+            expanded_extent
         };
-        let base_width = if transition_active {
-            from_width
+        let base_extent = if transition_active {
+            from_extent
         } else if target_collapsed {
-            collapsed_width
+            collapsed_extent
         } else {
-            expanded_width
+            expanded_extent
         };
 
-        let content_len = self.content.len();
-        let overdraw = px(window.viewport_size().height.as_f32() * 0.3);
-        // Keep separate virtual-list layout state for collapsed and expanded modes.
-        // Reusing measurements across modes causes visible snap/pop on toggle.
-        let list_state_key = SharedString::from(format!(
-            "{}-list-state-{}",
-            sidebar_id,
-            u8::from(visual_collapsed)
-        ));
-        let list_state = window
-            .use_keyed_state(list_state_key, cx, |_, _| {
-                ListState::new(content_len, ListAlignment::Top, overdraw)
-            })
-            .read(cx)
-            .clone();
-        if list_state.item_count() != content_len {
-            list_state.reset(content_len);
-        }
-
         let item_id_prefix = sidebar_id.clone();
         let header = match self.header.take() {
             Some(SidebarSlot::Dynamic(builder)) => Some(builder(visual_collapsed, window, cx)),
@@ -326,21 +439,115 @@ impl<E: SidebarItem> RenderOnce for Sidebar<E> {
 
         let sidebar = v_flex()
             .id(sidebar_id.clone())
-            .w(base_width)
             .flex_shrink_0()
-            .h_full()
             .overflow_hidden()
             .relative()
             .bg(cx.theme().sidebar)
             .text_color(cx.theme().sidebar_foreground)
             .border_color(cx.theme().sidebar_border)
-            .map(|this| match self.side {
-                Side::Left => this.border_r_1(),
-                Side::Right => this.border_l_1(),
+            .map(|this| {
+                if horizontal {
+                    this.flex_row().w_full().h(base_extent).border_b_1()
+                } else {
+                    this.w(base_extent).h_full().map(|this| match self.side {
+                        Side::Left => this.border_r_1(),
+                        Side::Right => this.border_l_1(),
+                    })
+                }
             })
             .refine_style(&self.style);
+        let collapse_on_drag = match (self.collapse_threshold, self.on_collapse_change.clone()) {
+            (Some(threshold), Some(on_collapse_change)) if self.collapsible && !rail => {
+                Some((threshold, on_collapse_change))
+            }
+            _ => None,
+        };
+        let resize_on_drag = self
+            .on_width_change
+            .clone()
+            .filter(|_| self.resizable && !horizontal && !rail)
+            .map(|on_width_change| (self.min_width, self.max_width, on_width_change));
+        let sidebar = if collapse_on_drag.is_some() || resize_on_drag.is_some() {
+            Self::drag_edge(
+                sidebar,
+                &sidebar_id,
+                self.side,
+                horizontal,
+                target_collapsed,
+                collapse_on_drag,
+                resize_on_drag,
+                cx,
+            )
+        } else {
+            sidebar
+        };
 
         let content = self.content;
+        if horizontal {
+            let inner = h_flex()
+                .id("sidebar-inner")
+                .size_full()
+                .px_3()
+                .gap_3()
+                .when(visual_collapsed, |this| this.px_2().gap_2())
+                .when_some(header, |this, header| {
+                    this.child(h_flex().id("header").flex_shrink_0().gap_2().child(header))
+                })
+                .child(
+                    h_flex()
+                        .id("content")
+                        .flex_1()
+                        .min_w_0()
+                        .h_full()
+                        .items_start()
+                        .gap_x_3()
+                        .overflow_x_scroll()
+                        .children(content.into_iter().enumerate().map(|(ix, item)| {
+                            div().id(ix).flex_shrink_0().py_2().child(
+                                item.collapsed(visual_collapsed).render(
+                                    SharedString::from(format!("{}-{}", item_id_prefix, ix)),
+                                    window,
+                                    cx,
+                                ),
+                            )
+                        })),
+                )
+                .when_some(footer, |this, footer| {
+                    this.child(h_flex().id("footer").flex_shrink_0().gap_2().child(footer))
+                });
+
+            return Self::animate_extent(
+                sidebar.child(inner),
+                transition_active,
+                horizontal,
+                from_extent,
+                to_extent,
+                &sidebar_id,
+                target_collapsed,
+                &motion,
+                reduced_motion,
+            );
+        }
+
+        let content_len = content.len();
+        let overdraw = px(window.viewport_size().height.as_f32() * 0.3);
+        // Keep separate virtual-list layout state for collapsed and expanded modes.
+        // Reusing measurements across modes causes visible snap/pop on toggle.
+        let list_state_key = SharedString::from(format!(
+            "{}-list-state-{}",
+            sidebar_id,
+            u8::from(visual_collapsed)
+        ));
+        let list_state = window
+            .use_keyed_state(list_state_key, cx, |_, _| {
+                ListState::new(content_len, ListAlignment::Top, overdraw)
+            })
+            .read(cx)
+            .clone();
+        if list_state.item_count() != content_len {
+            list_state.reset(content_len);
+        }
+
         let inner = v_flex()
             .id("sidebar-inner")
             .size_full()
@@ -377,6 +584,7 @@ impl<E: SidebarItem> RenderOnce for Sidebar<E> {
                                             this.child(
                                                 group
                                                     .collapsed(visual_collapsed)
+                                                    .rail(rail)
                                                     .render(
                                                         SharedString::from(format!(
                                                             "{}-{}",
@@ -410,27 +618,142 @@ impl<E: SidebarItem> RenderOnce for Sidebar<E> {
                 )
             });
 
-        let sidebar = sidebar.child(inner);
+        Self::animate_extent(
+            sidebar.child(inner),
+            transition_active,
+            horizontal,
+            from_extent,
+            to_extent,
+            &sidebar_id,
+            target_collapsed,
+            &motion,
+            reduced_motion,
+        )
+    }
+}
 
+impl<E: SidebarItem> Sidebar<E> {
+    /// Add an edge handle whose drag flips the collapsed state when crossing the
+    /// collapse threshold, and otherwise resizes the expanded sidebar.
+    #[allow(clippy::too_many_arguments)]
+    fn drag_edge(
+        sidebar: Stateful<Div>,
+        sidebar_id: &ElementId,
+        side: Side,
+        horizontal: bool,
+        collapsed: bool,
+        collapse_on_drag: Option<(Pixels, Rc<dyn Fn(bool, &mut Window, &mut App)>)>,
+        resize_on_drag: Option<(Pixels, Pixels, Rc<dyn Fn(Pixels, &mut Window, &mut App)>)>,
+        cx: &App,
+    ) -> Stateful<Div> {
+        let drag = SidebarEdgeDrag(sidebar_id.clone());
+        let drag_sidebar_id = sidebar_id.clone();
+        let resizable = resize_on_drag.is_some();
+        let hover_bg = cx.theme().foreground.alpha(0.20);
+
+        sidebar
+            .on_drag_move(move |event: &DragMoveEvent<SidebarEdgeDrag>, window, cx| {
+                if event.drag(cx).0 != drag_sidebar_id {
+                    return;
+                }
+
+                let position = event.event.position;
+                let extent = if horizontal {
+                    position.y - event.bounds.top()
+                } else {
+                    match side {
+                        Side::Left => position.x - event.bounds.left(),
+                        Side::Right => event.bounds.right() - position.x,
+                    }
+                };
+
+                if let Some((threshold, on_collapse_change)) = &collapse_on_drag {
+                    if !collapsed && extent < *threshold {
+                        on_collapse_change(true, window, cx);
+                        return;
+                    } else if collapsed && extent > *threshold {
+                        on_collapse_change(false, window, cx);
+                        return;
+                    }
+                }
+
+                if let Some((min_width, max_width, on_width_change)) = &resize_on_drag {
+                    if !collapsed {
+                        on_width_change(extent.min(*max_width).max(*min_width), window, cx);
+                    }
+                }
+            })
+            .child(
+                div()
+                    .id("sidebar-drag-handle")
+                    .absolute()
+                    .occlude()
+                    .map(|this| {
+                        if horizontal {
+                            this.cursor_row_resize()
+                                .left_0()
+                                .bottom_0()
+                                .w_full()
+                                .h(DRAG_HANDLE_SIZE)
+                        } else {
+                            let this = this
+                                .cursor_col_resize()
+                                .top_0()
+                                .h_full()
+                                .w(DRAG_HANDLE_SIZE);
+                            match side {
+                                Side::Left => this.right_0(),
+                                Side::Right => this.left_0(),
+                            }
+                        }
+                    })
+                    .when(resizable, |this| this.hover(move |this| this.bg(hover_bg)))
+                    .on_drag(drag, |drag, _, _, cx| {
+                        cx.stop_propagation();
+                        cx.new(|_| drag.clone())
+                    }),
+            )
+    }
+
+    /// Animate the collapsible extent (width when vertical, height when horizontal).
+    #[allow(clippy::too_many_arguments)]
+    fn animate_extent(
+        sidebar: Stateful<Div>,
+        transition_active: bool,
+        horizontal: bool,
+        from_extent: Pixels,
+        to_extent: Pixels,
+        sidebar_id: &ElementId,
+        target_collapsed: bool,
+        motion: &ThemeMotion,
+        reduced_motion: bool,
+    ) -> AnyElement {
         if !transition_active {
-            sidebar.into_any_element()
-        } else {
-            let width_anim = point_to_point_animation(&motion, reduced_motion);
-            if let Some(width_anim) = width_anim {
-                sidebar
-                    .with_animation(
-                        SharedString::from(format!(
-                            "{}-sidebar-width-{}",
-                            sidebar_id,
-                            u8::from(target_collapsed)
-                        )),
-                        width_anim,
-                        move |this, delta| this.w(from_width + (to_width - from_width) * delta),
-                    )
-                    .into_any_element()
-            } else {
-                sidebar.into_any_element()
-            }
+            return sidebar.into_any_element();
         }
+
+        let Some(extent_anim) = point_to_point_animation(motion, reduced_motion) else {
+            return sidebar.into_any_element();
+        };
+
+        sidebar
+            .with_animation(
+                SharedString::from(format!(
+                    "{}-sidebar-{}-{}",
+                    sidebar_id,
+                    if horizontal { "height" } else { "width" },
+                    u8::from(target_collapsed)
+                )),
+                extent_anim,
+                move |this, delta| {
+                    let extent = from_extent + (to_extent - from_extent) * delta;
+                    if horizontal {
+                        this.h(extent)
+                    } else {
+                        this.w(extent)
+                    }
+                },
+            )
+            .into_any_element()
     }
 }
@@ -4,8 +4,8 @@
 //! - Resizable panel with configurable min/max width constraints
 //! - Built-in shadow elevation effects
 //! - Glass surface effects via SurfacePreset
-//! - Draggable resize handle on the inner edge
-//! - Support for left/right placement
+//! - Draggable resize handle on the inner edge, or on both edges when centered
+//! - Support for left/right/center placement
 //!
 //! # Resize Model
 //!
@@ -21,6 +21,21 @@
 //! This model is necessary because GPUI's `RenderOnce` components cannot use
 //! `cx.listener()` or `window.listener_for()` patterns.
 //!
+//! Alternatively, set `on_resize` to receive the new width on every mouse move
+//! of the drag, already clamped to the min/max width, and only apply it.
+//!
+//! A `collapsible` panel asks to collapse with `on_collapse_request` when dragged
+//! below its min width. While its width is below the min width, only a thin peek
+//! handle is rendered on its window edge, and clicking it calls `on_expand_request`.
+//!
+//! # Drag and Dock Model
+//!
+//! Setting `on_drag_move` or `on_dock` adds a drag handle along the top of the
+//! panel. While it is dragged, `on_drag_move` reports where the panel would move
+//! and which window edge it is near, see [`SidebarShell::dock_target`]. Releasing
+//! near an edge calls `on_dock` with that edge. As with resizing, the consumer
+//! moves the panel and decides how to dock it.
+//!
 //! # Example
 //!
 //! ```rust,ignore
@@ -29,8 +44,8 @@
 //! SidebarShell::left(px(self.sidebar_width))
 //!     .min_width(px(200.0))
 //!     .max_width(px(400.0))
-//!     .on_resize_start(move |width, x, _window, cx| {
-//!         // Store resize start state: width, x position
+//!     .on_resize_start(move |edge, width, x, _window, cx| {
+//!         // Store resize start state: dragged edge, width, x position
 //!     })
 //!     .on_resize_end(move |_window, cx| {
 //!         // Clear resize state
@@ -41,8 +56,11 @@
 use std::rc::Rc;
 
 use gpui::{
-    AnyElement, App, BoxShadow, Hsla, InteractiveElement, IntoElement, ParentElement, Pixels,
-    RenderOnce, StyleRefinement, Styled, Window, div, hsla, point, prelude::FluentBuilder, px,
+    AnyElement, App, AppContext as _, BoxShadow, Context, DragMoveEvent, ElementId, Empty, Entity,
+    Hsla, InteractiveElement, IntoElement, MouseButton, MouseMoveEvent, MouseUpEvent,
+    ParentElement, Pixels, Point, Render, RenderOnce, SharedString,
+    StatefulInteractiveElement as _, StyleRefinement, Styled, Window, canvas, div, hsla, point,
+    prelude::FluentBuilder, px, relative,
 };
 use smallvec::SmallVec;
 
@@ -55,6 +73,11 @@ use crate::{
 const DEFAULT_MIN_WIDTH: f32 = 200.0;
 const DEFAULT_MAX_WIDTH: f32 = 400.0;
 const DEFAULT_RESIZER_WIDTH: f32 = 6.0;
+const DEFAULT_RESIZER_HIT_WIDTH: f32 = 12.0;
+const DEFAULT_DRAG_HANDLE_HEIGHT: f32 = 16.0;
+const DEFAULT_DOCK_THRESHOLD: f32 = 48.0;
+const PEEK_HANDLE_WIDTH: f32 = 8.0;
+const PEEK_HANDLE_HEIGHT: f32 = 48.0;
 
 /// Creates a 3-layer shadow effect for elevated sidebar panels.
 ///
@@ -62,7 +85,13 @@ const DEFAULT_RESIZER_WIDTH: f32 = 6.0;
 /// - Subtle near-edge shadow (4% opacity)
 /// - Medium distance shadow (8% opacity)
 /// - Far distance shadow (12% opacity)
-pub fn sidebar_shadow() -> Vec<BoxShadow> {
+///
+/// Returns no shadows when they are disabled via `Theme::shadow`.
+pub fn sidebar_shadow(cx: &App) -> Vec<BoxShadow> {
+    if !cx.theme().shadow {
+        return Vec::new();
+    }
+
     vec![
         BoxShadow {
             color: hsla(0., 0., 0., 0.04),
@@ -114,31 +143,56 @@ pub fn sidebar_shadow() -> Vec<BoxShadow> {
 /// |  |  |                                        | | |
 /// |  |  +----------------------------------------+ | |
 /// |  +--------------------------------------------+ |
-/// |  [Resizer Handle] (on inner edge)              |
+/// |  [Resizer Handle] (inner edge, or both)        |
 /// +------------------------------------------------+
 /// ```
 #[derive(IntoElement)]
 pub struct SidebarShell {
+    /// Identifies the shell's drag and resize state, unique per shell in a window.
+    id: ElementId,
     /// Current width of the sidebar in pixels.
     width: Pixels,
     /// Minimum width constraint for resizing.
     min_width: Pixels,
     /// Maximum width constraint for resizing.
     max_width: Pixels,
+    /// Maximum width as a fraction of the window width, less the inset.
+    max_width_fraction: Option<f32>,
     /// Width of the resize handle in pixels.
     resizer_width: Pixels,
+    /// Width of the interactive area around the resize handle in pixels.
+    resizer_hit_width: Pixels,
     /// Optional override for resizer hover background color.
     resizer_hover_bg: Option<Hsla>,
     /// Callback invoked when resize starts (mouse down on resizer).
-    /// Receives: (current_width, mouse_x, window, cx)
-    on_resize_start: Option<Rc<dyn Fn(Pixels, Pixels, &mut Window, &mut App)>>,
+    /// Receives: (dragged_edge, current_width, mouse_x, window, cx)
+    on_resize_start: Option<Rc<dyn Fn(Side, Pixels, Pixels, &mut Window, &mut App)>>,
     /// Callback invoked when resize ends (mouse up).
     on_resize_end: Option<Rc<dyn Fn(&mut Window, &mut App)>>,
-    /// Shadow elevation level for the sidebar panel.
-    /// Default: `ElevationToken::Lg` for a prominent floating appearance.
-    elevation: ElevationToken,
-    /// Placement side (left or right).
+    /// Callback invoked on every mouse move of a resize, with the clamped width.
+    on_resize: Option<Rc<dyn Fn(Pixels, &mut Window, &mut App)>>,
+    /// Whether the panel collapses to a peek handle below its min width.
+    collapsible: bool,
+    /// Callback invoked when the resizer is dragged below the min width.
+    on_collapse_request: Option<Rc<dyn Fn(&mut Window, &mut App)>>,
+    /// Callback invoked when the peek handle of a collapsed panel is clicked.
+    on_expand_request: Option<Rc<dyn Fn(&mut Window, &mut App)>>,
+    /// Callback invoked while the panel is dragged by its drag handle.
+    /// Receives: (panel_origin, dock_target, window, cx)
+    on_drag_move: Option<Rc<dyn Fn(Point<Pixels>, Option<Side>, &mut Window, &mut App)>>,
+    /// Callback invoked when the panel is released near a window edge.
+    on_dock: Option<Rc<dyn Fn(Side, &mut Window, &mut App)>>,
+    /// Height of the drag handle along the top of the panel.
+    drag_handle_height: Pixels,
+    /// Distance from a window edge within which a dragged panel docks to it.
+    dock_threshold: Pixels,
+    /// Shadow elevation level for the sidebar panel. If `None`, inherits from context,
+    /// falling back to `ElevationToken::Lg` for a prominent floating appearance.
+    elevation: Option<ElevationToken>,
+    /// Placement side (left or right), ignored when `centered`.
     side: Side,
+    /// Whether the panel is horizontally centered with resizers on both edges.
+    centered: bool,
     /// Inset from window edges in pixels. If `None`, inherits from context.
     inset: Option<Pixels>,
     /// Additional top inset applied above the inherited/explicit inset.
@@ -184,17 +238,52 @@ impl SidebarShell {
         Self::new(width, Side::Right)
     }
 
+    /// Creates a new horizontally centered panel shell with the specified width,
+    /// e.g. for a floating inspector.
+    ///
+    /// Resize handles are rendered on both edges; `on_resize_start` reports which one
+    /// is dragged. The panel stays centered, so it grows on both sides as it widens.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let inspector = SidebarShell::center(px(480.0));
+    /// ```
+    pub fn center(width: impl Into<Pixels>) -> Self {
+        Self {
+            id: "sidebar-shell-center".into(),
+            centered: true,
+            ..Self::new(width, Side::Left)
+        }
+    }
+
     fn new(width: impl Into<Pixels>, side: Side) -> Self {
         Self {
+            id: if side.is_left() {
+                "sidebar-shell-left".into()
+            } else {
+                "sidebar-shell-right".into()
+            },
             width: width.into(),
             min_width: px(DEFAULT_MIN_WIDTH),
             max_width: px(DEFAULT_MAX_WIDTH),
+            max_width_fraction: None,
             resizer_width: px(DEFAULT_RESIZER_WIDTH),
+            resizer_hit_width: px(DEFAULT_RESIZER_HIT_WIDTH),
             resizer_hover_bg: None,
             on_resize_start: None,
             on_resize_end: None,
-            elevation: ElevationToken::Lg,
+            on_resize: None,
+            collapsible: false,
+            on_collapse_request: None,
+            on_expand_request: None,
+            on_drag_move: None,
+            on_dock: None,
+            drag_handle_height: px(DEFAULT_DRAG_HANDLE_HEIGHT),
+            dock_threshold: px(DEFAULT_DOCK_THRESHOLD),
+            elevation: None,
             side,
+            centered: false,
             inset: None,
             top_inset: px(0.0),
             blur_enabled: None, // Inherit from context by default
@@ -203,6 +292,14 @@ impl SidebarShell {
         }
     }
 
+    /// Sets the id of the shell, needed when a window has several shells on the same side.
+    ///
+    /// Default: `sidebar-shell-left`, `sidebar-shell-right` or `sidebar-shell-center`.
+    pub fn id(mut self, id: impl Into<ElementId>) -> Self {
+        self.id = id.into();
+        self
+    }
+
     /// Sets the minimum width constraint for resizing.
     ///
     /// The sidebar cannot be resized smaller than this width.
@@ -221,6 +318,16 @@ impl SidebarShell {
         self
     }
 
+    /// Limits the width to a fraction of the window width, less the inset, so the
+    /// panel can't cover a small window.
+    ///
+    /// The effective maximum is the smaller of this and [`SidebarShell::max_width`],
+    /// and also caps the rendered width. Default: unset.
+    pub fn max_width_fraction(mut self, fraction: f32) -> Self {
+        self.max_width_fraction = Some(fraction.clamp(0.0, 1.0));
+        self
+    }
+
     /// Sets the width of the resize handle.
     ///
     /// Default: 6px.
@@ -229,6 +336,16 @@ impl SidebarShell {
         self
     }
 
+    /// Sets the width of the interactive area around the resize handle.
+    ///
+    /// The hit area extends symmetrically around the visible handle, which keeps
+    /// its `resizer_width`. Values below `resizer_width` are ignored.
+    /// Default: 12px.
+    pub fn resizer_hit_width(mut self, width: impl Into<Pixels>) -> Self {
+        self.resizer_hit_width = width.into();
+        self
+    }
+
     /// Sets the hover background color for the resize handle.
     ///
     /// If not set, defaults to theme foreground at 20% opacity.
@@ -239,20 +356,21 @@ impl SidebarShell {
 
     /// Sets the callback invoked when resize starts (mouse down on resizer).
     ///
-    /// The callback receives the current width and mouse X position.
+    /// The callback receives the dragged edge, the current width and mouse X position.
     /// The consumer should store these to calculate width delta during mouse move.
+    /// The edge is always the inner one, except for a [`SidebarShell::center`] panel.
     ///
     /// # Example
     ///
     /// ```rust,ignore
     /// SidebarShell::left(px(260.0))
-    ///     .on_resize_start(|width, x, window, cx| {
-    ///         // Store: resizing = true, start_width = width, start_x = x
+    ///     .on_resize_start(|edge, width, x, window, cx| {
+    ///         // Store: resizing = true, edge = edge, start_width = width, start_x = x
     ///     })
     /// ```
     pub fn on_resize_start(
         mut self,
-        callback: impl Fn(Pixels, Pixels, &mut Window, &mut App) + 'static,
+        callback: impl Fn(Side, Pixels, Pixels, &mut Window, &mut App) + 'static,
     ) -> Self {
         self.on_resize_start = Some(Rc::new(callback));
         self
@@ -260,6 +378,7 @@ impl SidebarShell {
 
     /// Sets the callback invoked when resize ends (mouse up).
     ///
+    /// A drag that collapses the panel ends right before `on_collapse_request`.
     /// The consumer should clear their resize state.
     ///
     /// # Example
@@ -275,6 +394,126 @@ impl SidebarShell {
         self
     }
 
+    /// Sets the callback invoked on every mouse move while the resizer is dragged.
+    ///
+    /// The callback receives the new width, clamped between the min width and the
+    /// effective max width, see [`SidebarShell::max_width_fraction`]. The consumer
+    /// only needs to apply it, without tracking the drag themselves.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// SidebarShell::left(px(self.sidebar_width))
+    ///     .on_resize(|width, window, cx| {
+    ///         // Store: sidebar_width = width
+    ///     })
+    /// ```
+    pub fn on_resize(mut self, callback: impl Fn(Pixels, &mut Window, &mut App) + 'static) -> Self {
+        self.on_resize = Some(Rc::new(callback));
+        self
+    }
+
+    /// Sets whether the panel can be collapsed, default: false
+    ///
+    /// A collapsible panel whose width is below its min width, e.g. zero, renders
+    /// only a thin peek handle on its window edge instead of disappearing, see
+    /// [`SidebarShell::on_expand_request`]. A centered panel uses the left edge.
+    pub fn collapsible(mut self, collapsible: bool) -> Self {
+        self.collapsible = collapsible;
+        self
+    }
+
+    /// Sets the callback invoked when a collapsible panel's resizer is dragged
+    /// below its min width.
+    ///
+    /// The consumer should collapse the panel, e.g. by setting its width to zero.
+    pub fn on_collapse_request(
+        mut self,
+        callback: impl Fn(&mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_collapse_request = Some(Rc::new(callback));
+        self
+    }
+
+    /// Sets the callback invoked when the peek handle of a collapsed panel is clicked.
+    ///
+    /// The consumer should restore the panel's width.
+    pub fn on_expand_request(mut self, callback: impl Fn(&mut Window, &mut App) + 'static) -> Self {
+        self.on_expand_request = Some(Rc::new(callback));
+        self
+    }
+
+    /// Sets the callback invoked while the panel is dragged by its drag handle.
+    ///
+    /// The callback receives the window position the panel's top-left corner would
+    /// move to, keeping the grabbed point under the cursor, and the window edge the
+    /// cursor is near, if any. The consumer should move the panel there, or preview
+    /// docking to the edge.
+    ///
+    /// Setting this or [`SidebarShell::on_dock`] makes the panel draggable.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// SidebarShell::left(px(260.0))
+    ///     .on_drag_move(|origin, dock_target, window, cx| {
+    ///         // Store: panel_origin = origin, dock_preview = dock_target
+    ///     })
+    /// ```
+    pub fn on_drag_move(
+        mut self,
+        callback: impl Fn(Point<Pixels>, Option<Side>, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_drag_move = Some(Rc::new(callback));
+        self
+    }
+
+    /// Sets the callback invoked when a dragged panel is released near a window edge.
+    ///
+    /// Releasing it anywhere else only ends the drag.
+    pub fn on_dock(mut self, callback: impl Fn(Side, &mut Window, &mut App) + 'static) -> Self {
+        self.on_dock = Some(Rc::new(callback));
+        self
+    }
+
+    /// Sets the height of the drag handle along the top of a draggable panel.
+    ///
+    /// Default: 16px.
+    pub fn drag_handle_height(mut self, height: impl Into<Pixels>) -> Self {
+        self.drag_handle_height = height.into();
+        self
+    }
+
+    /// Sets the distance from a window edge within which a dragged panel docks to it.
+    ///
+    /// Default: 48px.
+    pub fn dock_threshold(mut self, threshold: impl Into<Pixels>) -> Self {
+        self.dock_threshold = threshold.into();
+        self
+    }
+
+    /// Returns the window edge that `position` is within `threshold` of, if any.
+    ///
+    /// `position` is in window coordinates, as in mouse events. When the window is
+    /// narrow enough for both edges to be in range, the closer one wins.
+    pub fn dock_target(
+        position: Point<Pixels>,
+        threshold: Pixels,
+        window: &Window,
+    ) -> Option<Side> {
+        let width = window.window_bounds().get_bounds().size.width;
+        let to_left = position.x;
+        let to_right = width - position.x;
+
+        if to_left <= threshold && to_left <= to_right {
+            Some(Side::Left)
+        } else if to_right <= threshold {
+            Some(Side::Right)
+        } else {
+            None
+        }
+    }
+
     /// Sets the inset from window edges.
     ///
     /// This creates space between the sidebar and the window bounds.
@@ -323,7 +562,8 @@ impl SidebarShell {
     ///
     /// Controls the shadow depth and intensity using the theme's elevation
     /// system. Higher values create more pronounced floating appearance.
-    /// Default: `ElevationToken::Lg`.
+    /// When unset, inherits from the surrounding `ElevationScope` (e.g. a
+    /// `WindowShell` per-sidebar override), otherwise `ElevationToken::Lg`.
     ///
     /// # Example
     ///
@@ -333,11 +573,251 @@ impl SidebarShell {
     ///     .child(content)
     /// ```
     pub fn elevation(mut self, elevation: ElevationToken) -> Self {
-        self.elevation = elevation;
+        self.elevation = Some(elevation);
         self
     }
 }
 
+impl SidebarShell {
+    /// The max width in effect for a window `window_width` wide.
+    fn effective_max_width(&self, window_width: Pixels, inset: Pixels) -> Pixels {
+        match self.max_width_fraction {
+            Some(fraction) => self
+                .max_width
+                .min(window_width * fraction - inset)
+                .max(px(0.0)),
+            None => self.max_width,
+        }
+    }
+
+    /// Track the mouse while a resizer is dragged, reporting the clamped width
+    /// to `on_resize`, or ending the drag and requesting to collapse once below
+    /// the min width.
+    fn render_resize_tracker(
+        state: Entity<SidebarShellResizeState>,
+        centered: bool,
+        min_width: Pixels,
+        max_width: Pixels,
+        on_resize: Option<Rc<dyn Fn(Pixels, &mut Window, &mut App)>>,
+        on_resize_end: Option<Rc<dyn Fn(&mut Window, &mut App)>>,
+        on_collapse: Option<Rc<dyn Fn(&mut Window, &mut App)>>,
+    ) -> impl IntoElement {
+        canvas(
+            |_, _, _| {},
+            move |_, _, window, _| {
+                window.on_mouse_event({
+                    let state = state.clone();
+                    let on_resize_end = on_resize_end.clone();
+                    move |event: &MouseMoveEvent, phase, window, cx| {
+                        if !phase.bubble() {
+                            return;
+                        }
+                        let Some(resize) = state.read(cx).resize else {
+                            return;
+                        };
+
+                        let mut delta = event.position.x - resize.start_x;
+                        if resize.edge.is_left() {
+                            delta = -delta;
+                        }
+                        // A centered panel grows on both sides
+                        if centered {
+                            delta = delta * 2.0;
+                        }
+                        let width = resize.start_width + delta;
+                        if width < min_width {
+                            if let Some(on_collapse) = &on_collapse {
+                                // End the drag first, once per drag
+                                state.update(cx, |state, _| state.resize = None);
+                                if let Some(on_resize_end) = &on_resize_end {
+                                    on_resize_end(window, cx);
+                                }
+                                on_collapse(window, cx);
+                                return;
+                            }
+                        }
+                        if let Some(on_resize) = &on_resize {
+                            on_resize(width.max(min_width).min(max_width), window, cx);
+                        }
+                    }
+                });
+                window.on_mouse_event(move |_: &MouseUpEvent, phase, window, cx| {
+                    if !phase.bubble() {
+                        return;
+                    }
+                    if state.update(cx, |state, _| state.resize.take()).is_none() {
+                        return;
+                    }
+                    if let Some(on_resize_end) = &on_resize_end {
+                        on_resize_end(window, cx);
+                    }
+                });
+            },
+        )
+        .absolute()
+        .size_0()
+    }
+
+    /// Render the thin handle of a collapsed panel on its window edge.
+    fn render_peek_handle(
+        id: &ElementId,
+        side: Side,
+        top: Pixels,
+        bottom: Pixels,
+        hover_bg: Hsla,
+        on_expand_request: Option<Rc<dyn Fn(&mut Window, &mut App)>>,
+        cx: &App,
+    ) -> impl IntoElement {
+        div()
+            .id(SharedString::from(format!("{}-peek", id)))
+            .absolute()
+            .top(top)
+            .bottom(bottom)
+            .map(|el| {
+                if side.is_left() {
+                    el.left_0()
+                } else {
+                    el.right_0()
+                }
+            })
+            .w(px(PEEK_HANDLE_WIDTH))
+            .flex()
+            .items_center()
+            .justify_center()
+            .group("sidebar-shell-peek")
+            .cursor_pointer()
+            .child(
+                div()
+                    .w(px(PEEK_HANDLE_WIDTH / 2.0))
+                    .h(px(PEEK_HANDLE_HEIGHT))
+                    .rounded(px(999.0))
+                    .bg(cx.theme().foreground.alpha(0.10))
+                    .group_hover("sidebar-shell-peek", move |s| s.bg(hover_bg)),
+            )
+            .when_some(on_expand_request, |el, callback| {
+                el.on_click(move |_, window, cx| callback(window, cx))
+            })
+    }
+
+    /// Render the drag handle along the top of the panel, clear of the resizers.
+    fn render_drag_handle(
+        id: &ElementId,
+        height: Pixels,
+        resizer_half: Pixels,
+        dock_threshold: Pixels,
+        on_drag_move: Option<Rc<dyn Fn(Point<Pixels>, Option<Side>, &mut Window, &mut App)>>,
+        on_dock: Option<Rc<dyn Fn(Side, &mut Window, &mut App)>>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> impl IntoElement {
+        let state =
+            window.use_keyed_state(SharedString::from(format!("{}-drag", id)), cx, |_, _| {
+                SidebarShellDragState::default()
+            });
+        let drag_id = id.clone();
+
+        div()
+            .id("sidebar-shell-drag-handle")
+            .absolute()
+            .top_0()
+            .left(resizer_half)
+            .right(resizer_half)
+            .h(height)
+            .cursor_grab()
+            .on_drag(SidebarShellDrag(id.clone()), {
+                let state = state.clone();
+                move |drag, offset, _, cx| {
+                    cx.stop_propagation();
+                    // The handle starts `resizer_half` into the panel
+                    let grab_offset = offset + point(resizer_half, px(0.));
+                    state.update(cx, |state, _| {
+                        *state = SidebarShellDragState {
+                            grab_offset,
+                            dragging: true,
+                            dock_target: None,
+                        }
+                    });
+                    cx.new(|_| drag.clone())
+                }
+            })
+            .on_drag_move({
+                let state = state.clone();
+                move |event: &DragMoveEvent<SidebarShellDrag>, window, cx| {
+                    // Other shells see the drag too
+                    if event.drag(cx).0 != drag_id || !state.read(cx).dragging {
+                        return;
+                    }
+
+                    let position = event.event.position;
+                    let dock_target = Self::dock_target(position, dock_threshold, window);
+                    let grab_offset = state.update(cx, |state, _| {
+                        state.dock_target = dock_target;
+                        state.grab_offset
+                    });
+                    if let Some(on_drag_move) = &on_drag_move {
+                        on_drag_move(position - grab_offset, dock_target, window, cx);
+                    }
+                }
+            })
+            .on_mouse_up(gpui::MouseButton::Left, {
+                let state = state.clone();
+                let on_dock = on_dock.clone();
+                move |_, window, cx| Self::end_drag(&state, on_dock.as_ref(), window, cx)
+            })
+            .on_mouse_up_out(gpui::MouseButton::Left, move |_, window, cx| {
+                Self::end_drag(&state, on_dock.as_ref(), window, cx)
+            })
+    }
+
+    fn end_drag(
+        state: &gpui::Entity<SidebarShellDragState>,
+        on_dock: Option<&Rc<dyn Fn(Side, &mut Window, &mut App)>>,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        let drag = state.update(cx, |state, _| std::mem::take(state));
+        if !drag.dragging {
+            return;
+        }
+        if let (Some(side), Some(on_dock)) = (drag.dock_target, on_dock) {
+            on_dock(side, window, cx);
+        }
+    }
+}
+
+/// Drag progress of a [`SidebarShell`] moved by its drag handle.
+#[derive(Clone, Copy, Default)]
+struct SidebarShellDragState {
+    /// The grabbed point, relative to the panel's top-left corner.
+    grab_offset: Point<Pixels>,
+    dragging: bool,
+    /// The window edge the cursor was last near.
+    dock_target: Option<Side>,
+}
+
+/// A resize of a [`SidebarShell`] in progress, tracked for `on_resize`.
+#[derive(Clone, Copy)]
+struct SidebarShellResize {
+    edge: Side,
+    start_x: Pixels,
+    start_width: Pixels,
+}
+
+#[derive(Default)]
+struct SidebarShellResizeState {
+    resize: Option<SidebarShellResize>,
+}
+
+/// Drag payload of the [`SidebarShell`] drag handle, identifying the dragged shell.
+#[derive(Clone)]
+struct SidebarShellDrag(ElementId);
+
+impl Render for SidebarShellDrag {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        Empty
+    }
+}
+
 impl ParentElement for SidebarShell {
     fn extend(&mut self, elements: impl IntoIterator<Item = AnyElement>) {
         self.children.extend(elements);
@@ -364,12 +844,30 @@ impl RenderOnce for SidebarShell {
         let top = inset + self.top_inset;
         let bottom = inset;
         let sidebar_height = (window_height - (top + bottom)).max(px(0.0));
-        let sidebar_width = self.width;
+        let max_width = self.effective_max_width(window_bounds.size.width, inset);
+        let sidebar_width = self.width.min(max_width);
+
+        if self.collapsible && self.width < self.min_width {
+            return Self::render_peek_handle(
+                &self.id,
+                self.side,
+                top,
+                bottom,
+                resizer_hover_bg,
+                self.on_expand_request,
+                cx,
+            )
+            .into_any_element();
+        }
 
         // Use explicit value if set, otherwise inherit from context
         let blur_enabled = self
             .blur_enabled
             .unwrap_or_else(|| GlobalState::global(cx).blur_enabled());
+        let elevation = self
+            .elevation
+            .or_else(|| GlobalState::global(cx).elevation())
+            .unwrap_or(ElevationToken::Lg);
 
         let sidebar_surface = SurfacePreset::panel()
             .wrap_with_bounds(
@@ -384,54 +882,126 @@ impl RenderOnce for SidebarShell {
             .id("sidebar-shell-surface")
             .size_full();
 
-        let resizer_half = self.resizer_width / 2.0;
-        let resizer_left = if self.side.is_left() {
-            self.width - resizer_half
+        let resizer_hit_width = self.resizer_hit_width.max(self.resizer_width);
+        let resizer_half = resizer_hit_width / 2.0;
+        // Edges of the panel that get a resizer
+        let edges: SmallVec<[Side; 2]> = if self.centered {
+            SmallVec::from_buf([Side::Left, Side::Right])
+        } else if self.side.is_left() {
+            SmallVec::from_slice(&[Side::Right])
         } else {
-            -resizer_half
+            SmallVec::from_slice(&[Side::Left])
         };
 
         let is_left = self.side.is_left();
+        let centered = self.centered;
+        let resizer_width = self.resizer_width;
         let on_resize_start = self.on_resize_start.clone();
         let on_resize_end = self.on_resize_end.clone();
+        let on_collapse_request = self
+            .on_collapse_request
+            .clone()
+            .filter(|_| self.collapsible);
+        let resize_state = (self.on_resize.is_some() || on_collapse_request.is_some()).then(|| {
+            window.use_keyed_state(
+                SharedString::from(format!("{}-resize", self.id)),
+                cx,
+                |_, _| SidebarShellResizeState::default(),
+            )
+        });
+        let resize_tracker = resize_state.clone().map(|state| {
+            Self::render_resize_tracker(
+                state,
+                centered,
+                self.min_width,
+                max_width,
+                self.on_resize.clone(),
+                on_resize_end.clone(),
+                on_collapse_request,
+            )
+        });
+        // A tracked drag ends in the tracker, once even if it collapsed the panel
+        let on_resize_end = on_resize_end.filter(|_| resize_state.is_none());
+        let drag_handle = (self.on_drag_move.is_some() || self.on_dock.is_some()).then(|| {
+            Self::render_drag_handle(
+                &self.id,
+                self.drag_handle_height,
+                resizer_half,
+                self.dock_threshold,
+                self.on_drag_move.clone(),
+                self.on_dock.clone(),
+                window,
+                cx,
+            )
+        });
 
         let outer = div()
-            .id("sidebar-shell")
+            .id(self.id.clone())
             .absolute()
             .top(top)
             .bottom(bottom)
-            .w(self.width)
+            .w(sidebar_width)
             .map(|el| {
-                if is_left {
+                if centered {
+                    el.left(relative(0.5)).ml(-sidebar_width / 2.0)
+                } else if is_left {
                     el.left(inset)
                 } else {
                     el.right(inset)
                 }
             })
             .child(
-                self.elevation
+                elevation
                     .apply(div().id("sidebar-shell-shadow-wrapper").size_full(), cx)
                     .child(sidebar_surface),
             )
-            .child(
+            .children(drag_handle)
+            .children(resize_tracker)
+            .children(edges.into_iter().map(|edge| {
+                let (id, resizer_left) = match edge {
+                    Side::Left => ("sidebar-shell-resizer-left", -resizer_half),
+                    Side::Right => ("sidebar-shell-resizer", sidebar_width - resizer_half),
+                };
+
                 div()
-                    .id("sidebar-shell-resizer")
+                    .id(id)
                     .absolute()
                     .top_0()
                     .bottom_0()
                     .left(resizer_left)
-                    .w(self.resizer_width)
-                    .rounded(px(999.0))
-                    .bg(gpui::transparent_black())
+                    .w(resizer_hit_width)
+                    .flex()
+                    .justify_center()
+                    .group(id)
                     .cursor_col_resize()
-                    .hover(move |s| s.bg(resizer_hover_bg))
-                    .when_some(on_resize_start, move |el, callback| {
-                        el.on_mouse_down(gpui::MouseButton::Left, move |event, window, cx| {
+                    .child(
+                        div()
+                            .h_full()
+                            .w(resizer_width)
+                            .rounded(px(999.0))
+                            .bg(gpui::transparent_black())
+                            .group_hover(id, move |s| s.bg(resizer_hover_bg)),
+                    )
+                    .when(on_resize_start.is_some() || resize_state.is_some(), |el| {
+                        let on_resize_start = on_resize_start.clone();
+                        let resize_state = resize_state.clone();
+                        el.on_mouse_down(MouseButton::Left, move |event, window, cx| {
                             cx.stop_propagation();
-                            callback(sidebar_width, event.position.x, window, cx);
+                            if let Some(state) = &resize_state {
+                                state.update(cx, |state, _| {
+                                    state.resize = Some(SidebarShellResize {
+                                        edge,
+                                        start_x: event.position.x,
+                                        start_width: sidebar_width,
+                                    })
+                                });
+                            }
+                            if let Some(callback) = &on_resize_start {
+                                callback(edge, sidebar_width, event.position.x, window, cx);
+                            }
                         })
                     })
-                    .when_some(on_resize_end, move |el, callback| {
+                    .when_some(on_resize_end.clone(), move |el, callback| {
                         let callback_mouse_up = callback.clone();
                         el.on_mouse_up(gpui::MouseButton::Left, move |_event, window, cx| {
                             callback_mouse_up(window, cx);
@@ -442,10 +1012,10 @@ impl RenderOnce for SidebarShell {
                                 callback(window, cx);
                             },
                         )
-                    }),
-            )
+                    })
+            }))
             .refine_style(&self.style);
 
-        outer
+        outer.into_any_element()
     }
 }
@@ -1,4 +1,4 @@
-use crate::{ActiveTheme, PixelsExt as _};
+use crate::{ActiveTheme, GlobalState, PixelsExt as _};
 use gpui::{
     App, BoxShadow, Corners, DefiniteLength, Div, Edges, FocusHandle, Hsla, ParentElement, Pixels,
     Refineable, StyleRefinement, Styled, Window, div, point, px,
@@ -162,6 +162,19 @@ pub trait StyledExt: Styled + Sized {
         self.border_1().border_color(cx.theme().ring)
     }
 
+    /// Render the keyboard focus ring as a border, using the theme's `ring` color and `ring_width`.
+    ///
+    /// Does nothing when the last interaction was a mouse click, see [`GlobalState::focus_visible`].
+    #[inline]
+    fn focus_ring(self, cx: &App) -> Self {
+        if !GlobalState::global(cx).focus_visible() {
+            return self;
+        }
+
+        self.border(cx.theme().ring_width)
+            .border_color(cx.theme().ring)
+    }
+
     font_weight!(font_thin, THIN);
     font_weight!(font_extralight, EXTRA_LIGHT);
     font_weight!(font_light, LIGHT);
@@ -601,17 +614,26 @@ impl<T: Styled> StyleSized<T> for T {
 }
 
 pub(crate) trait FocusableExt<T: ParentElement + Styled + Sized> {
-    /// Add focus ring to the element.
-    fn focus_ring(self, is_focused: bool, margins: Pixels, window: &Window, cx: &App) -> Self;
+    /// Add a focus ring outside the element's bounds.
+    ///
+    /// Only shown for keyboard focus, see [`GlobalState::focus_visible`].
+    fn outer_focus_ring(self, is_focused: bool, margins: Pixels, window: &Window, cx: &App)
+    -> Self;
 }
 
 impl<T: ParentElement + Styled + Sized> FocusableExt<T> for T {
-    fn focus_ring(mut self, is_focused: bool, margins: Pixels, window: &Window, cx: &App) -> Self {
-        if !is_focused {
+    fn outer_focus_ring(
+        mut self,
+        is_focused: bool,
+        margins: Pixels,
+        window: &Window,
+        cx: &App,
+    ) -> Self {
+        if !is_focused || !GlobalState::global(cx).focus_visible() {
             return self;
         }
 
-        const RING_BORDER_WIDTH: Pixels = px(1.5);
+        let ring_width = cx.theme().ring_width;
         let rem_size = window.rem_size();
         let style = self.style();
 
@@ -661,7 +683,7 @@ impl<T: ParentElement + Styled + Sized> FocusableExt<T> for T {
                 .map(|v| v.to_pixels(rem_size))
                 .unwrap_or_default(),
         }
-        .map(|v| *v + RING_BORDER_WIDTH);
+        .map(|v| *v + ring_width);
 
         let mut inner_style = StyleRefinement::default();
         inner_style.corner_radii.top_left = Some(radius.top_left.into());
@@ -669,7 +691,7 @@ impl<T: ParentElement + Styled + Sized> FocusableExt<T> for T {
         inner_style.corner_radii.bottom_left = Some(radius.bottom_left.into());
         inner_style.corner_radii.bottom_right = Some(radius.bottom_right.into());
 
-        let inset = RING_BORDER_WIDTH + margins;
+        let inset = ring_width + margins;
 
         self.child(
             div()
@@ -679,7 +701,7 @@ impl<T: ParentElement + Styled + Sized> FocusableExt<T> for T {
                 .left(-(inset + border_widths.left))
                 .right(-(inset + border_widths.right))
                 .bottom(-(inset + border_widths.bottom))
-                .border(RING_BORDER_WIDTH)
+                .border(ring_width)
                 .border_color(cx.theme().ring.alpha(0.2))
                 .refine_style(&inner_style),
         )
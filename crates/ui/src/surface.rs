@@ -13,12 +13,14 @@
 //!     .wrap_with_bounds(content, width, height, window, cx, ctx);
 //! ```
 
+use std::cell::Cell;
+
 use gpui::{
-    App, Div, Hsla, ImageSource, IntoElement, ObjectFit, ParentElement, Pixels, Resource, Styled,
-    StyledImage, Window, div, img, px,
+    App, Div, Hsla, ImageSource, ImgResourceLoader, IntoElement, ObjectFit, ParentElement, Pixels,
+    Resource, Styled, StyledImage, Window, div, hsla, img, px,
 };
 
-use crate::{ActiveTheme, StyledExt, ThemeShadowToken};
+use crate::{ActiveTheme, StyledExt, Theme, ThemeShadowToken, global_state::GlobalState};
 
 const GLASS_NOISE_ASSET_PATH: &str = "NoiseAsset_256.png";
 const GLASS_NOISE_TILE_SIZE_BASE: f32 = 128.0;
@@ -74,7 +76,13 @@ pub enum ElevationToken {
 
 impl ElevationToken {
     /// Applies the elevation shadow to the given element.
-    pub fn apply<E: Styled + StyledExt>(&self, element: E, _cx: &App) -> E {
+    ///
+    /// Does nothing when shadows are disabled via `Theme::shadow`.
+    pub fn apply<E: Styled + StyledExt>(&self, element: E, cx: &App) -> E {
+        if !cx.theme().shadow {
+            return element;
+        }
+
         match self {
             ElevationToken::None => element,
             ElevationToken::Xs => element.shadow_sm(),
@@ -94,6 +102,10 @@ pub enum SurfaceColorSource {
     White,
     Sidebar,
     Background,
+    /// Fluent Mica base color from `ThemeMaterial` for the current mode.
+    Mica,
+    /// Fluent default Acrylic color from `ThemeMaterial` for the current mode.
+    Acrylic,
 }
 
 /// Background configuration with light/dark mode variants.
@@ -117,18 +129,32 @@ impl Default for SurfaceBackground {
 impl SurfaceBackground {
     /// Resolves the background color based on theme mode and opacity settings.
     pub fn resolve(&self, cx: &App) -> Hsla {
-        let base = match self.color_source {
-            SurfaceColorSource::Popover => cx.theme().popover,
-            SurfaceColorSource::White => gpui::white(),
-            SurfaceColorSource::Sidebar => cx.theme().sidebar,
-            SurfaceColorSource::Background => cx.theme().background,
-        };
-        let opacity = if cx.theme().mode.is_dark() {
+        self.resolve_with_theme(cx.theme())
+    }
+
+    /// Returns the opacity for the theme's current mode.
+    pub fn opacity_for(&self, theme: &Theme) -> f32 {
+        if theme.mode.is_dark() {
             self.dark_opacity
         } else {
             self.light_opacity
+        }
+    }
+
+    fn resolve_with_theme(&self, theme: &Theme) -> Hsla {
+        let base = match self.color_source {
+            SurfaceColorSource::Popover => theme.popover,
+            SurfaceColorSource::White => gpui::white(),
+            SurfaceColorSource::Sidebar => theme.sidebar,
+            SurfaceColorSource::Background => theme.background,
+            SurfaceColorSource::Mica if theme.mode.is_dark() => theme.material.mica_base_dark,
+            SurfaceColorSource::Mica => theme.material.mica_base_light,
+            SurfaceColorSource::Acrylic if theme.mode.is_dark() => {
+                theme.material.acrylic_default_dark
+            }
+            SurfaceColorSource::Acrylic => theme.material.acrylic_default_light,
         };
-        base.opacity(opacity)
+        base.opacity(self.opacity_for(theme))
     }
 }
 
@@ -167,21 +193,68 @@ impl StrokeSpec {
     }
 
     /// Resolves the stroke color based on the current theme.
+    ///
+    /// In [`Theme::high_contrast`] mode every stroke uses the full border color.
     pub fn resolve_color(&self, cx: &App) -> Hsla {
-        let subtle_stroke_opacity = if cx.theme().mode.is_dark() {
-            cx.theme().material.subtle_stroke_dark_opacity
+        self.resolve_color_with_theme(cx.theme())
+    }
+
+    /// Resolves the stroke width, 1px wider in [`Theme::high_contrast`] mode.
+    pub fn resolve_width(&self, cx: &App) -> Pixels {
+        self.resolve_width_with_theme(cx.theme())
+    }
+
+    fn resolve_width_with_theme(&self, theme: &Theme) -> Pixels {
+        if theme.high_contrast {
+            self.width + px(1.)
         } else {
-            cx.theme().material.subtle_stroke_light_opacity
+            self.width
+        }
+    }
+
+    fn resolve_color_with_theme(&self, theme: &Theme) -> Hsla {
+        if theme.high_contrast {
+            return theme.border;
+        }
+
+        let subtle_stroke_opacity = if theme.mode.is_dark() {
+            theme.material.subtle_stroke_dark_opacity
+        } else {
+            theme.material.subtle_stroke_light_opacity
         };
         match self.color {
-            StrokeColor::Subtle => cx.theme().border.opacity(subtle_stroke_opacity),
-            StrokeColor::Default => cx.theme().border,
-            StrokeColor::Strong => cx.theme().border,
-            StrokeColor::SubtleWithOpacity(opacity) => cx.theme().border.opacity(opacity),
+            StrokeColor::Subtle => theme.border.opacity(subtle_stroke_opacity),
+            StrokeColor::Default => theme.border,
+            StrokeColor::Strong => theme.border,
+            StrokeColor::SubtleWithOpacity(opacity) => theme.border.opacity(opacity),
         }
     }
 }
 
+/// The fully resolved appearance of a surface for the current theme mode.
+///
+/// Returned by [`SurfacePreset::describe`] and [`Theme::describe_surface`] so debug
+/// tooling can show exactly what a preset will render.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SurfaceDescription {
+    pub kind: SurfaceKind,
+    /// Background color with mode opacity and transparency factor applied.
+    pub background: Hsla,
+    /// The mode-dependent background opacity, before the transparency factor.
+    pub opacity: f32,
+    pub transparency_factor: f32,
+    /// Backdrop blur radius, only rendered when blur is enabled.
+    pub blur_radius: Option<Pixels>,
+    /// Noise overlay opacity, only rendered when blur is enabled.
+    pub noise_opacity: f32,
+    pub elevation: ElevationToken,
+    pub stroke_width: Option<Pixels>,
+    pub stroke_color: Option<Hsla>,
+    /// Color of the 1px inner bevel edge, see [`SurfacePreset::with_inner_highlight`].
+    pub inner_highlight: Option<Hsla>,
+    pub radius: Pixels,
+}
+
 /// A preset configuration for surface appearance.
 ///
 /// Surfaces are the foundational visual containers in the UI. This struct provides
@@ -197,6 +270,7 @@ pub struct SurfacePreset {
     pub stroke: Option<StrokeSpec>,
     pub transparency_factor: f32,
     pub radius: Option<Pixels>,
+    pub inner_highlight: bool,
     pub use_theme_material_defaults: bool,
     pub use_theme_elevation_defaults: bool,
 }
@@ -222,6 +296,7 @@ impl SurfacePreset {
             stroke: None,
             transparency_factor: 1.0,
             radius: None,
+            inner_highlight: false,
             use_theme_material_defaults: false,
             use_theme_elevation_defaults: false,
         }
@@ -231,16 +306,32 @@ impl SurfacePreset {
     ///
     /// - 60px blur radius
     /// - Subtle noise
-    /// - Popover background at 0.75/0.85 opacity
-    /// - Small elevation with subtle stroke
+    /// - Popover background at 0.75/0.85 opacity (Acrylic on Windows)
+    /// - Small elevation with subtle stroke and inner highlight
     /// - 12px border radius
     pub fn flyout() -> Self {
+        if cfg!(target_os = "windows") {
+            return Self::acrylic_flyout();
+        }
+
+        Self::flyout_with_source(SurfaceColorSource::Popover)
+    }
+
+    /// Creates a flyout surface preset backed by the Fluent Acrylic material.
+    ///
+    /// Same as [`SurfacePreset::flyout`], with the background taken from
+    /// `ThemeMaterial::acrylic_default_*`. Used by `flyout()` on Windows.
+    pub fn acrylic_flyout() -> Self {
+        Self::flyout_with_source(SurfaceColorSource::Acrylic)
+    }
+
+    fn flyout_with_source(color_source: SurfaceColorSource) -> Self {
         Self {
             kind: SurfaceKind::Flyout,
             blur_radius: Some(px(60.0)),
             noise_intensity: NoiseIntensity::Subtle,
             background: SurfaceBackground {
-                color_source: SurfaceColorSource::Popover,
+                color_source,
                 light_opacity: 0.75,
                 dark_opacity: 0.85,
             },
@@ -248,6 +339,7 @@ impl SurfacePreset {
             stroke: Some(StrokeSpec::subtle()),
             transparency_factor: 1.0,
             radius: Some(px(12.0)),
+            inner_highlight: true,
             use_theme_material_defaults: true,
             use_theme_elevation_defaults: true,
         }
@@ -258,7 +350,7 @@ impl SurfacePreset {
     /// - 120px blur radius
     /// - Heavy noise
     /// - Sidebar background at 0.85/0.90 opacity
-    /// - Large elevation with subtle stroke
+    /// - Large elevation with subtle stroke and inner highlight
     /// - 16px border radius
     pub fn panel() -> Self {
         Self {
@@ -274,6 +366,7 @@ impl SurfacePreset {
             stroke: Some(StrokeSpec::subtle()),
             transparency_factor: 1.0,
             radius: Some(px(16.0)),
+            inner_highlight: true,
             use_theme_material_defaults: true,
             use_theme_elevation_defaults: true,
         }
@@ -300,11 +393,22 @@ impl SurfacePreset {
             stroke: Some(StrokeSpec::default_border()),
             transparency_factor: 1.0,
             radius: None,
+            inner_highlight: false,
             use_theme_material_defaults: true,
             use_theme_elevation_defaults: true,
         }
     }
 
+    /// Returns the default preset for the given surface kind.
+    pub fn from_kind(kind: SurfaceKind) -> Self {
+        match kind {
+            SurfaceKind::Base => Self::base(),
+            SurfaceKind::Flyout => Self::flyout(),
+            SurfaceKind::Panel => Self::panel(),
+            SurfaceKind::Card => Self::card(),
+        }
+    }
+
     /// Sets the transparency factor for the background.
     pub fn with_transparency_factor(mut self, factor: f32) -> Self {
         self.transparency_factor = factor;
@@ -344,6 +448,15 @@ impl SurfacePreset {
         self
     }
 
+    /// Sets whether to render a 1px inner highlight inside the stroke, for a glass bevel.
+    ///
+    /// In dark mode the top edge is lit; in light mode the bottom edge is shaded instead.
+    /// Enabled by default for flyouts and panels.
+    pub fn with_inner_highlight(mut self, inner_highlight: bool) -> Self {
+        self.inner_highlight = inner_highlight;
+        self
+    }
+
     /// Wraps content in a surface container with all configured effects.
     ///
     /// This method creates a complete surface element with:
@@ -352,6 +465,7 @@ impl SurfacePreset {
     /// - Border/stroke styling
     /// - Elevation shadows
     /// - Noise overlay (if blur is enabled)
+    /// - Inner highlight (if enabled)
     pub fn wrap_with_bounds(
         &self,
         content: impl IntoElement,
@@ -363,13 +477,14 @@ impl SurfacePreset {
     ) -> Div {
         let radius = self.radius.unwrap_or(cx.theme().radius);
         let scale_factor = window.scale_factor();
-        let blur_radius = self.resolve_blur_radius(cx);
-        let background = self.resolve_background(cx);
-        let elevation = self.resolve_elevation(cx);
+        let blur_radius = self.resolve_blur_radius(cx.theme());
+        let background = self.resolve_background(cx.theme());
+        let elevation = self.resolve_elevation(cx.theme());
 
         let bg_color = background.resolve(cx).opacity(self.transparency_factor);
         let noise_opacity = self.noise_intensity.opacity();
-        let should_render_noise = ctx.blur_enabled && noise_opacity > 0.0;
+        let should_render_noise =
+            ctx.blur_enabled && noise_opacity > 0.0 && GlobalState::global(cx).noise_enabled();
 
         let mut surface = div().relative().rounded(radius).overflow_hidden();
 
@@ -385,38 +500,96 @@ impl SurfacePreset {
 
         if let Some(ref stroke) = self.stroke {
             surface = surface
-                .border(stroke.width)
+                .border(stroke.resolve_width(cx))
                 .border_color(stroke.resolve_color(cx));
         }
 
         surface = elevation.apply(surface, cx);
 
         if should_render_noise {
-            surface = surface.child(render_noise_overlay(
-                width,
-                height,
-                radius,
-                noise_opacity,
-                scale_factor,
-            ));
+            surface = if noise_asset_available(cx) {
+                surface.child(render_noise_overlay(
+                    width,
+                    height,
+                    radius,
+                    noise_opacity,
+                    scale_factor,
+                ))
+            } else {
+                surface.child(render_noise_fallback(radius, noise_opacity, cx))
+            };
+        }
+
+        let inner_highlight = self.resolve_inner_highlight(cx.theme()).map(|color| {
+            let inset = self
+                .stroke
+                .map_or(px(0.), |stroke| stroke.resolve_width(cx));
+            let edge = div()
+                .absolute()
+                .inset_0()
+                .rounded((radius - inset).max(px(0.)))
+                .border_color(color);
+            if cx.theme().mode.is_dark() {
+                edge.border_t_1()
+            } else {
+                edge.border_b_1()
+            }
+        });
+
+        surface.child(content).children(inner_highlight)
+    }
+
+    /// Resolves every value this preset would render with under the given theme.
+    pub fn describe(&self, theme: &Theme) -> SurfaceDescription {
+        let background = self.resolve_background(theme);
+        SurfaceDescription {
+            kind: self.kind,
+            background: background
+                .resolve_with_theme(theme)
+                .opacity(self.transparency_factor),
+            opacity: background.opacity_for(theme),
+            transparency_factor: self.transparency_factor,
+            blur_radius: self.resolve_blur_radius(theme),
+            noise_opacity: self.noise_intensity.opacity(),
+            elevation: self.resolve_elevation(theme),
+            stroke_width: self
+                .stroke
+                .map(|stroke| stroke.resolve_width_with_theme(theme)),
+            stroke_color: self
+                .stroke
+                .map(|stroke| stroke.resolve_color_with_theme(theme)),
+            inner_highlight: self.resolve_inner_highlight(theme),
+            radius: self.radius.unwrap_or(theme.radius),
+        }
+    }
+
+    /// The highlight is derived from the subtle stroke opacity of the material: white
+    /// along the top edge in dark mode, black along the bottom edge in light mode.
+    fn resolve_inner_highlight(&self, theme: &Theme) -> Option<Hsla> {
+        if !self.inner_highlight {
+            return None;
         }
 
-        surface.child(content)
+        Some(if theme.mode.is_dark() {
+            gpui::white().opacity(theme.material.subtle_stroke_dark_opacity)
+        } else {
+            gpui::black().opacity(theme.material.subtle_stroke_light_opacity)
+        })
     }
 
-    fn resolve_blur_radius(&self, cx: &App) -> Option<Pixels> {
+    fn resolve_blur_radius(&self, theme: &Theme) -> Option<Pixels> {
         if !self.use_theme_material_defaults {
             return self.blur_radius;
         }
 
         match self.kind {
-            SurfaceKind::Flyout => Some(cx.theme().material.flyout_blur_radius),
-            SurfaceKind::Panel => Some(cx.theme().material.panel_blur_radius),
+            SurfaceKind::Flyout => Some(theme.material.flyout_blur_radius),
+            SurfaceKind::Panel => Some(theme.material.panel_blur_radius),
             SurfaceKind::Card | SurfaceKind::Base => self.blur_radius,
         }
     }
 
-    fn resolve_background(&self, cx: &App) -> SurfaceBackground {
+    fn resolve_background(&self, theme: &Theme) -> SurfaceBackground {
         if !self.use_theme_material_defaults {
             return self.background;
         }
@@ -424,37 +597,40 @@ impl SurfacePreset {
         match self.kind {
             SurfaceKind::Flyout => SurfaceBackground {
                 color_source: self.background.color_source,
-                light_opacity: cx.theme().material.flyout_light_opacity,
-                dark_opacity: cx.theme().material.flyout_dark_opacity,
+                light_opacity: theme.material.flyout_light_opacity,
+                dark_opacity: theme.material.flyout_dark_opacity,
             },
             SurfaceKind::Panel => SurfaceBackground {
                 color_source: self.background.color_source,
-                light_opacity: cx.theme().material.panel_light_opacity,
-                dark_opacity: cx.theme().material.panel_dark_opacity,
+                light_opacity: theme.material.panel_light_opacity,
+                dark_opacity: theme.material.panel_dark_opacity,
             },
             SurfaceKind::Card => SurfaceBackground {
                 color_source: self.background.color_source,
-                light_opacity: cx.theme().material.card_light_opacity,
-                dark_opacity: cx.theme().material.card_dark_opacity,
+                light_opacity: theme.material.card_light_opacity,
+                dark_opacity: theme.material.card_dark_opacity,
             },
             SurfaceKind::Base => self.background,
         }
     }
 
-    fn resolve_elevation(&self, cx: &App) -> ElevationToken {
+    fn resolve_elevation(&self, theme: &Theme) -> ElevationToken {
+        if !theme.shadow {
+            return ElevationToken::None;
+        }
         if !self.use_theme_elevation_defaults {
             return self.elevation;
         }
 
         match self.kind {
             SurfaceKind::Flyout => {
-                theme_shadow_token_to_elevation_token(cx.theme().elevation.surface_flyout_shadow)
+                theme_shadow_token_to_elevation_token(theme.elevation.surface_flyout_shadow)
             }
             SurfaceKind::Panel => {
-                theme_shadow_token_to_elevation_token(cx.theme().elevation.surface_panel_shadow)
+                theme_shadow_token_to_elevation_token(theme.elevation.surface_panel_shadow)
             }
             SurfaceKind::Card => {
-                theme_shadow_token_to_elevation_token(cx.theme().elevation.surface_card_shadow)
+                theme_shadow_token_to_elevation_token(theme.elevation.surface_card_shadow)
             }
             SurfaceKind::Base => self.elevation,
         }
@@ -472,6 +648,61 @@ fn theme_shadow_token_to_elevation_token(token: ThemeShadowToken) -> ElevationTo
     }
 }
 
+impl Theme {
+    /// Describes the resolved appearance of the default preset for `kind`.
+    ///
+    /// Useful for debug overlays when tuning material and elevation tokens.
+    pub fn describe_surface(&self, kind: SurfaceKind) -> SurfaceDescription {
+        SurfacePreset::from_kind(kind).describe(self)
+    }
+}
+
+thread_local! {
+    /// Whether the app's asset source has the noise tile, checked on first use.
+    static NOISE_ASSET_AVAILABLE: Cell<Option<bool>> = const { Cell::new(None) };
+}
+
+/// Returns whether `NoiseAsset_256.png` can be loaded, warning once if it can't.
+fn noise_asset_available(cx: &App) -> bool {
+    NOISE_ASSET_AVAILABLE.with(|available| {
+        if let Some(available) = available.get() {
+            return available;
+        }
+
+        let found = matches!(cx.asset_source().load(GLASS_NOISE_ASSET_PATH), Ok(Some(_)));
+        if !found {
+            tracing::warn!(
+                "{} is missing from the asset source, glass surfaces fall back to a flat tint. \
+                 Bundle it or call `GlobalState::set_noise_enabled(false)`.",
+                GLASS_NOISE_ASSET_PATH
+            );
+        }
+        available.set(Some(found));
+        found
+    })
+}
+
+/// A flat tint standing in for the noise texture when its asset is missing.
+fn render_noise_fallback(radius: Pixels, opacity: f32, cx: &App) -> impl IntoElement {
+    let lightness = if cx.theme().mode.is_dark() { 1. } else { 0. };
+    div()
+        .absolute()
+        .inset_0()
+        .rounded(radius)
+        .bg(hsla(0., 0., lightness, opacity * 0.5))
+}
+
+/// Start loading the noise tile used by [`render_noise_overlay`], so the first
+/// glass surface doesn't wait on decoding it.
+pub(crate) fn preload_noise_asset(window: &mut Window, cx: &mut App) {
+    if !GlobalState::global(cx).noise_enabled() || !noise_asset_available(cx) {
+        return;
+    }
+
+    let resource = Resource::Embedded(GLASS_NOISE_ASSET_PATH.into());
+    _ = window.use_asset::<ImgResourceLoader>(&resource, cx);
+}
+
 /// Renders a tiled noise overlay for glass effects.
 ///
 /// This is exposed publicly for cases where the full `wrap_with_bounds` API
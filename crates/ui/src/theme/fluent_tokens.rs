@@ -1,6 +1,8 @@
 use gpui::{Hsla, px};
 
-use crate::{ThemeElevation, ThemeMaterial, ThemeMotion, ThemeShadowToken, try_parse_color};
+use crate::{
+    ThemeElevation, ThemeMaterial, ThemeMode, ThemeMotion, ThemeShadowToken, try_parse_color,
+};
 
 pub(crate) fn theme_motion_defaults() -> ThemeMotion {
     ThemeMotion {
@@ -77,6 +79,26 @@ pub(crate) fn theme_material_defaults() -> ThemeMaterial {
     }
 }
 
+/// Text selection background of [Fluent](crate::ThemeConfig::fluent) themes, from
+/// Fluent `AccentFillColorDefault`.
+pub(crate) fn selection_default(mode: ThemeMode) -> Hsla {
+    if mode.is_dark() {
+        fluent_color("#60CDFF")
+    } else {
+        fluent_color("#005FB8")
+    }
+}
+
+/// Input caret color of [Fluent](crate::ThemeConfig::fluent) themes, matching Fluent
+/// `TextFillColorPrimary`.
+pub(crate) fn caret_default(mode: ThemeMode) -> Hsla {
+    if mode.is_dark() {
+        fluent_color("#FFFFFF")
+    } else {
+        fluent_color("#000000E4")
+    }
+}
+
 fn fluent_color(value: &str) -> Hsla {
     try_parse_color(value).unwrap_or_else(|_| gpui::transparent_black())
 }
@@ -2,10 +2,12 @@ use crate::{
     highlighter::HighlightTheme, list::ListSettings, notification::NotificationSettings,
     scroll::ScrollbarShow, sheet::SheetSettings,
 };
+use anyhow::Result;
 use gpui::{App, Global, Hsla, Pixels, SharedString, Window, WindowAppearance, px};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::{
+    collections::HashMap,
     ops::{Deref, DerefMut},
     rc::Rc,
     sync::Arc,
@@ -14,15 +16,22 @@ use std::{
 mod color;
 mod elevation;
 mod fluent_tokens;
+mod preview;
 mod registry;
 mod schema;
+pub(crate) mod schema_fields;
+mod size_metrics;
 mod theme_color;
+mod transition;
 mod typography;
 
 pub use color::*;
 pub use registry::*;
 pub use schema::*;
+pub use schema_fields::{FieldDescriptor, FieldType, schema, schema_fields};
+pub use size_metrics::*;
 pub use theme_color::*;
+pub use transition::*;
 pub use typography::*;
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
@@ -121,12 +130,95 @@ impl Default for ThemeMaterial {
 }
 
 pub fn init(cx: &mut App) {
+    // Already initialized by `init_following_system`.
+    if cx.has_global::<Theme>() {
+        return;
+    }
+
     registry::init(cx);
 
     Theme::sync_system_appearance(None, cx);
     Theme::sync_scrollbar_appearance(cx);
 }
 
+/// Initialize the theme for the current OS appearance.
+///
+/// Unlike [`init`], the global theme is built directly from the light or dark defaults
+/// matching the system, so it never holds the light colors on a dark-mode system.
+///
+/// Call this before `gpui_component::init`, which then keeps this theme.
+pub fn init_following_system(cx: &mut App) {
+    registry::init(cx);
+
+    let mode = ThemeMode::from(cx.window_appearance());
+    let registry = ThemeRegistry::global(cx);
+    let (light_theme, dark_theme) = (
+        registry.default_light_theme().clone(),
+        registry.default_dark_theme().clone(),
+    );
+
+    let (colors, config) = if mode.is_dark() {
+        (ThemeColor::dark(), dark_theme.clone())
+    } else {
+        (ThemeColor::light(), light_theme.clone())
+    };
+    let mut theme = Theme::from(colors.as_ref());
+    theme.light_theme = light_theme;
+    theme.dark_theme = dark_theme;
+    theme.mode = mode;
+    theme.highlight_theme = if mode.is_dark() {
+        HighlightTheme::default_dark()
+    } else {
+        HighlightTheme::default_light()
+    };
+    theme.apply_config(&config);
+    cx.set_global(theme);
+
+    Theme::sync_scrollbar_appearance(cx);
+}
+
+/// Precompute what components otherwise build lazily on first render, so the first
+/// popover or palette opened doesn't drop frames. Call once the window is open.
+///
+/// This:
+/// - parses the easing strings of the current [`ThemeMotion`],
+/// - starts decoding the `NoiseAsset_256.png` tile of glass surfaces,
+/// - computes the elevation shadows of every [`ThemeElevation`] level, in light and dark.
+///
+/// It can be called again after switching themes to warm up the new tokens.
+pub fn warm_up(window: &mut Window, cx: &mut App) {
+    let theme = cx.theme();
+    let motion = &theme.motion;
+    for easing in [
+        &motion.fast_invoke_easing,
+        &motion.strong_invoke_easing,
+        &motion.fast_dismiss_easing,
+        &motion.soft_dismiss_easing,
+        &motion.point_to_point_easing,
+        &motion.fade_easing,
+    ] {
+        crate::animation::cached_cubic_bezier_easing(easing);
+    }
+
+    let elevation = &theme.elevation;
+    for level in [
+        elevation.control_level,
+        elevation.card_rest_level,
+        elevation.tooltip_level,
+        elevation.flyout_level,
+        elevation.dialog_level,
+        elevation.shell_level,
+        elevation.inactive_window_level,
+        elevation.active_window_level,
+    ] {
+        for is_dark in [false, true] {
+            elevation.computed_shadow(level, is_dark);
+        }
+    }
+
+    crate::surface::preload_noise_asset(window, cx);
+}
+
 pub trait ActiveTheme {
     fn theme(&self) -> &Theme;
 }
@@ -147,6 +239,28 @@ pub struct Theme {
     pub material: ThemeMaterial,
     pub typography: ThemeTypography,
     pub highlight_theme: Arc<HighlightTheme>,
+    /// Highlight themes set by [`Theme::set_highlight_theme`], by appearance.
+    ///
+    /// These take precedence over the `highlight` of the theme config.
+    #[serde(skip)]
+    pub(crate) custom_highlight_themes: HashMap<ThemeMode, Arc<HighlightTheme>>,
+    /// Colors set by [`Theme::override_color`], by field name.
+    ///
+    /// These are applied on top of the theme config colors.
+    #[serde(skip)]
+    pub(crate) color_overrides: HashMap<String, Hsla>,
+    /// Log a warning for built-in color pairs below 4.5:1 contrast whenever a theme
+    /// config is applied, see [`ThemeColor::low_contrast_pairs`].
+    ///
+    /// Only checked in debug builds. Default: false.
+    #[serde(skip)]
+    pub contrast_checks: bool,
+    /// Strengthen borders, surface strokes and the focus ring for accessibility,
+    /// independent of the light/dark mode, see [`Theme::set_high_contrast`].
+    ///
+    /// Default: false.
+    #[serde(skip)]
+    pub high_contrast: bool,
     pub light_theme: Rc<ThemeConfig>,
     pub dark_theme: Rc<ThemeConfig>,
 
@@ -169,10 +283,22 @@ pub struct Theme {
     pub radius: Pixels,
     /// Radius for the large elements, e.g.: Dialog, Notification border radius.
     pub radius_lg: Pixels,
+    /// Whether to render shadows. When false, elevation shadows are skipped for a flat look.
     pub shadow: bool,
+    /// Width of the keyboard focus ring, default is 1.5px.
+    pub ring_width: Pixels,
+    /// Density of sized components, see [`Theme::size_metrics`]. Default: Comfortable.
+    pub density: ThemeDensity,
+    /// Zoom factor of the whole UI, applied to the font sizes and size metrics,
+    /// see [`Theme::set_ui_scale`]. Default: 1.0.
+    pub ui_scale: f32,
     pub transparent: Hsla,
     /// Show the scrollbar mode, default: Scrolling
     pub scrollbar_show: ScrollbarShow,
+    /// Minimum length of the scrollbar thumb, default is 48px.
+    ///
+    /// Keeps the thumb grabbable when the content is much longer than the viewport.
+    pub scrollbar_min_thumb_size: Pixels,
     /// The notification setting.
     pub notification: NotificationSettings,
     /// Tile grid size, default is 4px.
@@ -209,7 +335,44 @@ impl DerefMut for Theme {
 
 impl Global for Theme {}
 
+/// The latest theme change, a global to observe to react to theme changes without
+/// polling, e.g. to recompute cached theme-derived assets.
+///
+/// It is set once per change by [`Theme::change`], also when the [`ThemeRegistry`]
+/// reloads the active theme, [`Theme::change_animated`] when its transition
+/// completes, [`Theme::set_high_contrast`], [`Theme::set_ui_scale`], the highlight
+/// theme setters and the color override setters.
+/// Unlike observing [`Theme`] itself, it doesn't fire for every frame of an
+/// animated mode switch.
+///
+/// ```rust,ignore
+/// cx.observe_global::<ThemeChangedEvent>(|this, cx| {
+///     this.gradient = build_gradient(cx.theme());
+///     cx.notify();
+/// })
+/// .detach();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ThemeChangedEvent {
+    /// The theme mode after the change.
+    pub mode: ThemeMode,
+    /// Increased by one with every change.
+    pub generation: u64,
+}
+
+impl Global for ThemeChangedEvent {}
+
 impl Theme {
+    /// Notify the [`ThemeChangedEvent`] observers of a theme change.
+    pub(crate) fn notify_changed(cx: &mut App) {
+        let generation = cx
+            .try_global::<ThemeChangedEvent>()
+            .map_or(0, |event| event.generation)
+            + 1;
+        let mode = Theme::global(cx).mode;
+        cx.set_global(ThemeChangedEvent { mode, generation });
+    }
+
     /// Returns the global theme reference
     #[inline(always)]
     pub fn global(cx: &App) -> &Theme {
@@ -249,6 +412,36 @@ impl Theme {
         Self::change(appearance, window, cx);
     }
 
+    /// Set the [`Theme::ui_scale`], clamped to 0.5..=2.0.
+    ///
+    /// Changing it calls `window.refresh()` for the given window, other windows pick
+    /// it up on their next render. It is kept across theme and mode changes.
+    pub fn set_ui_scale(ui_scale: f32, window: Option<&mut Window>, cx: &mut App) {
+        Theme::global_mut(cx).ui_scale = ui_scale.clamp(0.5, 2.0);
+        Self::notify_changed(cx);
+        if let Some(window) = window {
+            window.refresh();
+        }
+    }
+
+    /// Returns `value` multiplied by the [`Theme::ui_scale`].
+    #[inline]
+    pub fn scaled(&self, value: Pixels) -> Pixels {
+        value * self.ui_scale
+    }
+
+    /// The base font size with the [`Theme::ui_scale`] applied, used as the rem size.
+    #[inline]
+    pub fn scaled_font_size(&self) -> Pixels {
+        self.scaled(self.font_size)
+    }
+
+    /// The monospace font size with the [`Theme::ui_scale`] applied.
+    #[inline]
+    pub fn scaled_mono_font_size(&self) -> Pixels {
+        self.scaled(self.mono_font_size)
+    }
+
     /// Sync the Scrollbar showing behavior with the system
     pub fn sync_scrollbar_appearance(cx: &mut App) {
         Theme::global_mut(cx).scrollbar_show = if cx.should_auto_hide_scrollbars() {
@@ -275,12 +468,123 @@ impl Theme {
         } else {
             theme.apply_config(&theme.light_theme.clone());
         }
+        Self::notify_changed(cx);
+
+        if let Some(window) = window {
+            window.refresh();
+        }
+    }
+
+    /// Turn the high contrast mode on or off, see [`Theme::high_contrast`].
+    ///
+    /// It is kept across theme and mode changes.
+    pub fn set_high_contrast(high_contrast: bool, window: Option<&mut Window>, cx: &mut App) {
+        let theme = Theme::global_mut(cx);
+        if theme.high_contrast == high_contrast {
+            return;
+        }
+        theme.high_contrast = high_contrast;
+        theme.reapply_config();
+        Self::notify_changed(cx);
 
         if let Some(window) = window {
             window.refresh();
         }
     }
 
+    /// Set a custom syntax highlight theme for its appearance and refresh all windows.
+    ///
+    /// The highlight theme is kept across theme changes, and used whenever the theme mode
+    /// matches `highlight_theme.appearance`, so syntax colors follow the light/dark switch.
+    pub fn set_highlight_theme(highlight_theme: Arc<HighlightTheme>, cx: &mut App) {
+        let theme = Theme::global_mut(cx);
+        if theme.mode == highlight_theme.appearance {
+            theme.highlight_theme = highlight_theme.clone();
+        }
+        theme
+            .custom_highlight_themes
+            .insert(highlight_theme.appearance, highlight_theme);
+        Self::notify_changed(cx);
+
+        cx.refresh_windows();
+    }
+
+    /// Remove the custom highlight theme for `mode`, falling back to the theme config.
+    pub fn reset_highlight_theme(mode: ThemeMode, cx: &mut App) {
+        let theme = Theme::global_mut(cx);
+        if theme.custom_highlight_themes.remove(&mode).is_none() {
+            return;
+        }
+
+        if theme.mode == mode {
+            let config = if mode.is_dark() {
+                theme.dark_theme.clone()
+            } else {
+                theme.light_theme.clone()
+            };
+            if config.highlight.is_none() {
+                theme.highlight_theme = if mode.is_dark() {
+                    HighlightTheme::default_dark()
+                } else {
+                    HighlightTheme::default_light()
+                };
+            }
+            theme.apply_config(&config);
+        }
+        Self::notify_changed(cx);
+
+        cx.refresh_windows();
+    }
+
+    /// Override a single color by its [`ThemeColor`] field name, e.g. `"accent"`, and
+    /// refresh all windows.
+    ///
+    /// Overrides are kept across theme and mode changes until cleared.
+    pub fn override_color(name: &str, color: Hsla, cx: &mut App) -> Result<()> {
+        let theme = Theme::global_mut(cx);
+        theme.colors.set_color(name, color)?;
+        theme.color_overrides.insert(name.to_string(), color);
+        Self::notify_changed(cx);
+
+        cx.refresh_windows();
+        Ok(())
+    }
+
+    /// Remove the override of the color `name`, restoring the theme config color.
+    pub fn clear_override(name: &str, cx: &mut App) {
+        let theme = Theme::global_mut(cx);
+        if theme.color_overrides.remove(name).is_none() {
+            return;
+        }
+        theme.reapply_config();
+        Self::notify_changed(cx);
+
+        cx.refresh_windows();
+    }
+
+    /// Remove all color overrides.
+    pub fn clear_overrides(cx: &mut App) {
+        let theme = Theme::global_mut(cx);
+        if theme.color_overrides.is_empty() {
+            return;
+        }
+        theme.color_overrides.clear();
+        theme.reapply_config();
+        Self::notify_changed(cx);
+
+        cx.refresh_windows();
+    }
+
+    /// Apply the theme config of the current mode again.
+    fn reapply_config(&mut self) {
+        let config = if self.mode.is_dark() {
+            self.dark_theme.clone()
+        } else {
+            self.light_theme.clone()
+        };
+        self.apply_config(&config);
+    }
+
     /// Get the editor background color, if not set, use the theme background color.
     #[inline]
     pub(crate) fn editor_background(&self) -> Hsla {
@@ -310,7 +614,11 @@ impl From<&ThemeColor> for Theme {
             radius: px(6.),
             radius_lg: px(8.),
             shadow: true,
+            ring_width: px(1.5),
+            density: ThemeDensity::default(),
+            ui_scale: 1.,
             scrollbar_show: ScrollbarShow::default(),
+            scrollbar_min_thumb_size: px(48.),
             notification: NotificationSettings::default(),
             tile_grid_size: px(8.),
             tile_shadow: true,
@@ -324,6 +632,10 @@ impl From<&ThemeColor> for Theme {
             light_theme: Rc::new(ThemeConfig::default()),
             dark_theme: Rc::new(ThemeConfig::default()),
             highlight_theme: HighlightTheme::default_light(),
+            custom_highlight_themes: HashMap::new(),
+            color_overrides: HashMap::new(),
+            contrast_checks: false,
+            high_contrast: false,
             sheet: SheetSettings::default(),
         }
     }
@@ -373,3 +685,44 @@ impl From<WindowAppearance> for ThemeMode {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::Cell, rc::Rc};
+
+    use gpui::{TestAppContext, red};
+
+    use super::{Theme, ThemeChangedEvent, ThemeMode, ThemeRegistry};
+    use crate::highlighter::HighlightTheme;
+
+    #[gpui::test]
+    fn test_theme_changed_event_count(cx: &mut TestAppContext) {
+        cx.update(crate::init);
+        let count = Rc::new(Cell::new(0));
+        cx.update(|cx| {
+            let count = count.clone();
+            cx.observe_global::<ThemeChangedEvent>(move |_| count.set(count.get() + 1))
+                .detach();
+        });
+
+        // Each update flushes its own notification
+        cx.update(|cx| Theme::override_color("accent", red(), cx).unwrap());
+        assert_eq!(count.get(), 1);
+        cx.update(|cx| Theme::clear_override("accent", cx));
+        assert_eq!(count.get(), 2);
+        cx.update(Theme::clear_overrides);
+        assert_eq!(count.get(), 2, "nothing to clear");
+
+        cx.update(|cx| Theme::set_highlight_theme(HighlightTheme::default_dark(), cx));
+        assert_eq!(count.get(), 3);
+        cx.update(|cx| Theme::reset_highlight_theme(ThemeMode::Dark, cx));
+        assert_eq!(count.get(), 4);
+
+        cx.update(|cx| {
+            ThemeRegistry::global_mut(cx)
+                .load_from_json(r#"{ "name": "Loaded", "mode": "light" }"#)
+                .unwrap();
+        });
+        assert_eq!(count.get(), 5);
+    }
+}
@@ -0,0 +1,98 @@
+use std::rc::Rc;
+
+use gpui::{IntoElement, ParentElement as _, Pixels, Styled as _, div, px};
+
+use crate::{h_flex, v_flex};
+
+use super::{Theme, ThemeConfig};
+
+const PREVIEW_WIDTH: Pixels = px(160.);
+const PREVIEW_HEIGHT: Pixels = px(100.);
+const PREVIEW_TITLE_BAR_HEIGHT: Pixels = px(14.);
+const PREVIEW_SIDEBAR_WIDTH: Pixels = px(40.);
+
+impl Theme {
+    /// Resolve a theme from the given configuration, without touching the global theme.
+    pub fn from_config(config: &ThemeConfig) -> Self {
+        let mut theme = Theme::default();
+        theme.apply_config(&Rc::new(config.clone()));
+        theme
+    }
+
+    /// Render a compact mock of a window (title bar, sidebar, text and a button)
+    /// using the colors of `config`, e.g. for a grid of theme swatches.
+    ///
+    /// The global theme is left unchanged.
+    pub fn render_preview(config: &ThemeConfig) -> impl IntoElement {
+        let theme = Self::from_config(config);
+        let radius = theme.radius.min(px(4.));
+
+        let line = |width: Pixels, color| div().w(width).h(px(4.)).rounded(px(2.)).bg(color);
+
+        v_flex()
+            .w(PREVIEW_WIDTH)
+            .h(PREVIEW_HEIGHT)
+            .flex_shrink_0()
+            .overflow_hidden()
+            .rounded(theme.radius_lg)
+            .border_1()
+            .border_color(theme.border)
+            .bg(theme.background)
+            .font_family(theme.font_family.clone())
+            .text_size(px(9.))
+            .text_color(theme.foreground)
+            .child(
+                h_flex()
+                    .h(PREVIEW_TITLE_BAR_HEIGHT)
+                    .flex_shrink_0()
+                    .px_1p5()
+                    .gap_1()
+                    .bg(theme.title_bar)
+                    .border_b_1()
+                    .border_color(theme.title_bar_border)
+                    .children(
+                        [theme.red, theme.yellow, theme.green]
+                            .map(|color| div().size(px(5.)).rounded_full().bg(color)),
+                    ),
+            )
+            .child(
+                h_flex()
+                    .flex_1()
+                    .min_h_0()
+                    .child(
+                        v_flex()
+                            .w(PREVIEW_SIDEBAR_WIDTH)
+                            .h_full()
+                            .p_1p5()
+                            .gap_1p5()
+                            .bg(theme.sidebar)
+                            .border_r_1()
+                            .border_color(theme.sidebar_border)
+                            .child(line(px(24.), theme.sidebar_primary))
+                            .child(line(px(20.), theme.sidebar_foreground.opacity(0.4)))
+                            .child(line(px(26.), theme.sidebar_foreground.opacity(0.4))),
+                    )
+                    .child(
+                        v_flex()
+                            .flex_1()
+                            .h_full()
+                            .p_2()
+                            .gap_1p5()
+                            .child(div().child(config.name.clone()))
+                            .child(line(px(72.), theme.muted_foreground.opacity(0.5)))
+                            .child(line(px(56.), theme.muted_foreground.opacity(0.5)))
+                            .child(
+                                h_flex().mt_auto().child(
+                                    div()
+                                        .px_2()
+                                        .py_0p5()
+                                        .rounded(radius)
+                                        .bg(theme.primary)
+                                        .text_color(theme.primary_foreground)
+                                        .child("Button"),
+                                ),
+                            ),
+                    ),
+            )
+    }
+}
@@ -1,4 +1,7 @@
-use crate::{Theme, ThemeColor, ThemeConfig, ThemeMode, ThemeSet, highlighter::HighlightTheme};
+use crate::{
+    Theme, ThemeColor, ThemeConfig, ThemeMode, ThemeParseError, ThemeSet,
+    highlighter::HighlightTheme,
+};
 use anyhow::Result;
 use gpui::{App, Global, SharedString};
 use notify::Watcher as _;
@@ -78,6 +81,8 @@ pub struct ThemeRegistry {
     themes_dir: PathBuf,
     default_themes: HashMap<ThemeMode, Rc<ThemeConfig>>,
     themes: HashMap<SharedString, Rc<ThemeConfig>>,
+    /// Themes loaded with [`ThemeRegistry::load_from_json`], kept across reloads.
+    json_themes: Vec<Rc<ThemeConfig>>,
     has_custom_themes: bool,
 }
 
@@ -117,6 +122,27 @@ impl ThemeRegistry {
         Ok(())
     }
 
+    /// Parse a theme with [`ThemeConfig::from_json_str`] and register it, replacing
+    /// any theme with the same name.
+    ///
+    /// The theme stays registered when the themes directory reloads. If it is the
+    /// active light or dark theme, the change is applied right away.
+    pub fn load_from_json(&mut self, json: &str) -> Result<Rc<ThemeConfig>, ThemeParseError> {
+        let theme = Rc::new(ThemeConfig::from_json_str(json)?);
+        self.json_themes.retain(|loaded| loaded.name != theme.name);
+        self.json_themes.push(theme.clone());
+        self.register(theme.clone());
+        Ok(theme)
+    }
+
+    fn register(&mut self, theme: Rc<ThemeConfig>) {
+        if theme.is_default {
+            self.default_themes.insert(theme.mode, theme.clone());
+        }
+        self.has_custom_themes = true;
+        self.themes.insert(theme.name.clone(), theme);
+    }
+
     /// Returns a reference to the map of themes (including default themes).
     pub fn themes(&self) -> &HashMap<SharedString, Rc<ThemeConfig>> {
         &self.themes
@@ -264,6 +290,10 @@ impl ThemeRegistry {
                 .insert(theme.name.clone(), Rc::new(theme.clone()));
         }
 
+        for theme in self.json_themes.clone() {
+            self.register(theme);
+        }
+
         Ok(())
     }
 }
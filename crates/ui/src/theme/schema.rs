@@ -4,9 +4,10 @@ use gpui::{SharedString, px};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use super::fluent_tokens;
 use crate::{
-    Colorize, Theme, ThemeColor, ThemeElevation, ThemeMaterial, ThemeMode, ThemeMotion,
-    ThemeShadowToken, ThemeTypographyConfig,
+    Colorize, Theme, ThemeColor, ThemeDensity, ThemeElevation, ThemeMaterial, ThemeMode,
+    ThemeMotion, ThemeShadowToken, ThemeTypographyConfig,
     highlighter::{HighlightTheme, HighlightThemeStyle},
     try_parse_color,
 };
@@ -35,6 +36,9 @@ pub struct ThemeConfig {
     pub name: SharedString,
     /// The mode of the theme, default is light.
     pub mode: ThemeMode,
+    /// Whether the theme follows Fluent, using the Fluent selection and caret colors
+    /// when `colors` doesn't set them, default is false.
+    pub fluent: bool,
 
     /// The base font size, default is 16.
     #[serde(rename = "font.size")]
@@ -61,6 +65,13 @@ pub struct ThemeConfig {
     /// Set shadows in the theme, for example the Input and Button, default is true.
     #[serde(rename = "shadow")]
     pub shadow: Option<bool>,
+    /// The width of the keyboard focus ring, default is 1.5.
+    #[serde(rename = "ring.width")]
+    pub ring_width: Option<f32>,
+    /// The density of sized components: `compact`, `comfortable` or `spacious`,
+    /// default is `comfortable`.
+    #[serde(rename = "density")]
+    pub density: Option<ThemeDensity>,
     /// Motion token overrides sourced from Fluent animation tokens.
     pub motion: Option<ThemeMotionConfig>,
     /// Elevation token overrides sourced from Fluent elevation tokens.
@@ -78,6 +89,84 @@ pub struct ThemeConfig {
     pub highlight: Option<HighlightThemeStyle>,
 }
 
+/// An error from [`ThemeConfig::from_json_str`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum ThemeParseError {
+    /// The JSON is malformed, or a field has the wrong type.
+    Json {
+        message: String,
+        line: usize,
+        column: usize,
+    },
+    /// A key in `colors` that isn't a theme color, e.g. a misspelled one.
+    UnknownColor { key: String },
+    /// A value in `colors` that can't be parsed as a color.
+    InvalidColor { key: String, value: String },
+}
+
+impl std::fmt::Display for ThemeParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Json {
+                message,
+                line,
+                column,
+            } => write!(
+                f,
+                "invalid theme at line {line}, column {column}: {message}"
+            ),
+            Self::UnknownColor { key } => write!(f, "unknown theme color `{key}`"),
+            Self::InvalidColor { key, value } => {
+                write!(f, "invalid value `{value}` for theme color `{key}`")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ThemeParseError {}
+
+impl From<serde_json::Error> for ThemeParseError {
+    fn from(err: serde_json::Error) -> Self {
+        Self::Json {
+            message: err.to_string(),
+            line: err.line(),
+            column: err.column(),
+        }
+    }
+}
+
+impl ThemeConfig {
+    /// Parse a single theme, e.g. a user-authored theme file, from JSON.
+    ///
+    /// Every field is optional: omitted colors and settings fall back to the default
+    /// theme of the same mode when the theme is applied, so partial themes work.
+    /// Unlike the themes directory, which skips invalid colors, unknown color keys
+    /// and unparsable color values are reported as errors.
+    pub fn from_json_str(json: &str) -> Result<ThemeConfig, ThemeParseError> {
+        let config: ThemeConfig = serde_json::from_str(json)?;
+
+        let value: serde_json::Value = serde_json::from_str(json)?;
+        if let Some(colors) = value.get("colors").and_then(|colors| colors.as_object()) {
+            let known = serde_json::to_value(ThemeConfigColors::default())?;
+            for (key, color) in colors {
+                if known.get(key).is_none() {
+                    return Err(ThemeParseError::UnknownColor { key: key.clone() });
+                }
+                if let Some(color) = color.as_str() {
+                    if try_parse_color(color).is_err() {
+                        return Err(ThemeParseError::InvalidColor {
+                            key: key.clone(),
+                            value: color.to_string(),
+                        });
+                    }
+                }
+            }
+        }
+
+        Ok(config)
+    }
+}
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 pub struct ThemeMotionConfig {
@@ -416,6 +505,9 @@ pub struct ThemeConfigColors {
     #[serde(rename = "group_box.title.foreground")]
     pub group_box_title_foreground: Option<SharedString>,
     /// Input caret color (Blinking cursor).
+    ///
+    /// Defaults to the primary color, or the Fluent primary text color in
+    /// [Fluent](ThemeConfig::fluent) themes.
     #[serde(rename = "caret")]
     pub caret: Option<SharedString>,
     /// Chart 1 color.
@@ -541,6 +633,9 @@ pub struct ThemeConfigColors {
     /// Scrollbar thumb hover background color.
     #[serde(rename = "scrollbar.thumb.hover.background")]
     pub scrollbar_thumb_hover: Option<SharedString>,
+    /// Scrollbar track color.
+    #[serde(rename = "scrollbar.track.background")]
+    pub scrollbar_track: Option<SharedString>,
     /// Secondary background color.
     #[serde(rename = "secondary.background")]
     pub secondary: Option<SharedString>,
@@ -554,6 +649,9 @@ pub struct ThemeConfigColors {
     #[serde(rename = "secondary.hover.background")]
     pub secondary_hover: Option<SharedString>,
     /// Input selection background color.
+    ///
+    /// Defaults to the primary color, or the Fluent accent fill in
+    /// [Fluent](ThemeConfig::fluent) themes, capped at 30% opacity.
     #[serde(rename = "selection.background")]
     pub selection: Option<SharedString>,
     /// Sidebar background color.
@@ -876,7 +974,14 @@ impl ThemeColor {
                 )
         );
         apply_color!(group_box_foreground, fallback = self.foreground);
-        apply_color!(caret, fallback = self.primary);
+        apply_color!(
+            caret,
+            fallback = if config.fluent {
+                fluent_tokens::caret_default(config.mode)
+            } else {
+                self.primary
+            }
+        );
         apply_color!(chart_1, fallback = self.blue.lighten(0.4));
         apply_color!(chart_2, fallback = self.blue.lighten(0.2));
         apply_color!(chart_3, fallback = self.blue);
@@ -922,7 +1027,15 @@ impl ThemeColor {
         apply_color!(scrollbar, fallback = self.background);
         apply_color!(scrollbar_thumb, fallback = self.accent);
         apply_color!(scrollbar_thumb_hover, fallback = self.scrollbar_thumb);
-        apply_color!(selection, fallback = self.primary);
+        apply_color!(scrollbar_track, fallback = self.border);
+        apply_color!(
+            selection,
+            fallback = if config.fluent {
+                fluent_tokens::selection_default(config.mode)
+            } else {
+                self.primary
+            }
+        );
         apply_color!(
             sidebar,
             fallback = self.background.blend(self.border.opacity(0.15))
@@ -984,7 +1097,9 @@ impl Theme {
         } else {
             self.light_theme = config.clone();
         }
-        if let Some(style) = &config.highlight {
+        if let Some(highlight_theme) = self.custom_highlight_themes.get(&config.mode) {
+            self.highlight_theme = highlight_theme.clone();
+        } else if let Some(style) = &config.highlight {
             let highlight_theme = Arc::new(HighlightTheme {
                 name: config.name.to_string(),
                 appearance: config.mode,
@@ -1034,15 +1149,77 @@ impl Theme {
         } else {
             self.shadow = default_theme.shadow;
         }
+        if let Some(ring_width) = config.ring_width {
+            self.ring_width = px(ring_width);
+        } else {
+            self.ring_width = default_theme.ring_width;
+        }
+        self.density = config.density.unwrap_or(default_theme.density);
         self.motion
             .apply_config(config.motion.as_ref(), &default_theme.motion);
         self.elevation
             .apply_config(config.elevation.as_ref(), &default_theme.elevation);
         self.material
             .apply_config(config.material.as_ref(), &default_theme.material);
-        self.typography.apply_config(config.typography.as_ref());
+        self.typography
+            .apply_config(config.typography.as_ref(), config.mode);
 
         self.colors.apply_config(&config, &default_theme.colors);
+        if self.high_contrast {
+            self.colors.apply_high_contrast();
+            self.ring_width = self.ring_width.max(px(2.));
+        }
+        for (name, color) in &self.color_overrides {
+            _ = self.colors.set_color(name, *color);
+        }
         self.mode = config.mode;
+
+        if cfg!(debug_assertions) && self.contrast_checks {
+            for (fg, bg, ratio) in self.colors.low_contrast_pairs() {
+                tracing::warn!(
+                    "theme `{}`: `{}` on `{}` has a contrast ratio of {:.2}:1, below 4.5:1",
+                    config.name,
+                    fg,
+                    bg,
+                    ratio
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ThemeConfig, ThemeParseError};
+    use crate::ThemeMode;
+
+    #[test]
+    fn test_theme_config_from_json_str() {
+        let config = ThemeConfig::from_json_str(
+            r##"{ "name": "Partial", "mode": "dark", "colors": { "background": "#101010" } }"##,
+        )
+        .unwrap();
+        assert_eq!(config.name, "Partial");
+        assert_eq!(config.mode, ThemeMode::Dark);
+        assert_eq!(config.colors.background.as_deref(), Some("#101010"));
+        assert!(config.colors.border.is_none());
+
+        assert_eq!(
+            ThemeConfig::from_json_str(r#"{ "colors": { "backgrund": "#101010" } }"#),
+            Err(ThemeParseError::UnknownColor {
+                key: "backgrund".into()
+            })
+        );
+        assert_eq!(
+            ThemeConfig::from_json_str(r#"{ "colors": { "border": "not a color" } }"#),
+            Err(ThemeParseError::InvalidColor {
+                key: "border".into(),
+                value: "not a color".into()
+            })
+        );
+        assert!(matches!(
+            ThemeConfig::from_json_str(r#"{ "name": 1 }"#),
+            Err(ThemeParseError::Json { line: 1, .. })
+        ));
     }
 }
@@ -0,0 +1,241 @@
+//! The theme config schema, and its fields flattened for generating settings forms.
+
+use gpui::SharedString;
+use serde_json::{Map, Value};
+
+use crate::ThemeConfig;
+
+/// Returns the JSON schema of [`ThemeConfig`], the format of theme files.
+pub fn schema() -> schemars::Schema {
+    schemars::schema_for!(ThemeConfig)
+}
+
+/// The value type of a [`FieldDescriptor`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldType {
+    Bool,
+    Integer,
+    Number,
+    String,
+    /// One of the listed string values.
+    Enum(Vec<SharedString>),
+    /// A list or map the schema doesn't describe further.
+    Other,
+}
+
+/// A leaf field of the theme schema, for generating a settings form.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldDescriptor {
+    /// The JSON keys from the root of the theme config to this field.
+    ///
+    /// Keys may contain dots, e.g. `["colors", "background"]` or `["font.size"]`.
+    pub path: Vec<SharedString>,
+    pub ty: FieldType,
+    /// Whether the field may be `null` or omitted to use the default theme value.
+    pub optional: bool,
+    /// The default value declared by the schema, if any.
+    pub default: Option<Value>,
+    /// The doc comment of the field, if any.
+    pub description: Option<SharedString>,
+}
+
+/// Flatten [`schema`] into one [`FieldDescriptor`] per leaf field.
+///
+/// Nested objects (e.g. `colors`, `motion`) are expanded into their fields rather
+/// than listed themselves.
+pub fn schema_fields() -> Vec<FieldDescriptor> {
+    let schema = schema();
+    let root = schema.as_value();
+    let mut fields = Vec::new();
+    let mut visiting = Vec::new();
+    collect_fields(
+        root,
+        root,
+        &mut Vec::new(),
+        None,
+        &mut visiting,
+        &mut fields,
+    );
+    fields
+}
+
+fn collect_fields<'a>(
+    root: &'a Value,
+    schema: &'a Value,
+    path: &mut Vec<SharedString>,
+    default: Option<&Value>,
+    visiting: &mut Vec<&'a str>,
+    fields: &mut Vec<FieldDescriptor>,
+) {
+    let Some(object) = schema.as_object() else {
+        return;
+    };
+
+    let default = object.get("default").or(default);
+
+    // Follow `$ref` into `$defs`, guarding against recursive definitions
+    if let Some(reference) = object.get("$ref").and_then(Value::as_str) {
+        if visiting.contains(&reference) {
+            return;
+        }
+        let Some(target) = resolve_ref(root, reference) else {
+            return;
+        };
+        visiting.push(reference);
+        collect_fields(root, target, path, default, visiting, fields);
+        visiting.pop();
+        return;
+    }
+
+    if let Some(properties) = object.get("properties").and_then(Value::as_object) {
+        for (key, property) in properties {
+            path.push(key.clone().into());
+            let default = default.and_then(|default| default.get(key));
+            collect_fields(root, property, path, default, visiting, fields);
+            path.pop();
+        }
+        return;
+    }
+
+    // `Option<Struct>` is `anyOf: [{ "$ref": .. }, { "type": "null" }]`
+    if let Some(variants) = variants(object) {
+        let non_null: Vec<&Value> = variants.iter().filter(|v| !is_null_type(v)).collect();
+        if let [inner] = non_null.as_slice() {
+            if is_object(root, inner) {
+                collect_fields(root, inner, path, default, visiting, fields);
+                return;
+            }
+        }
+    }
+
+    let (ty, optional) = field_type(root, object);
+    fields.push(FieldDescriptor {
+        path: path.clone(),
+        ty,
+        optional,
+        default: default.cloned(),
+        description: object
+            .get("description")
+            .and_then(Value::as_str)
+            .map(|description| SharedString::from(description.to_string())),
+    });
+}
+
+fn resolve_ref<'a>(root: &'a Value, reference: &str) -> Option<&'a Value> {
+    let name = reference.strip_prefix("#/$defs/")?;
+    root.get("$defs")?.get(name)
+}
+
+fn variants(object: &Map<String, Value>) -> Option<&Vec<Value>> {
+    object
+        .get("anyOf")
+        .or_else(|| object.get("oneOf"))
+        .and_then(Value::as_array)
+}
+
+fn is_null_type(schema: &Value) -> bool {
+    schema.get("type").and_then(Value::as_str) == Some("null")
+}
+
+/// Whether `schema` describes an object with properties, following `$ref`.
+fn is_object(root: &Value, schema: &Value) -> bool {
+    let schema = match schema.get("$ref").and_then(Value::as_str) {
+        Some(reference) => match resolve_ref(root, reference) {
+            Some(target) => target,
+            None => return false,
+        },
+        None => schema,
+    };
+    schema.get("properties").is_some()
+}
+
+/// Resolve the value type of a leaf schema and whether it accepts `null`.
+fn field_type(root: &Value, object: &Map<String, Value>) -> (FieldType, bool) {
+    if let Some(reference) = object.get("$ref").and_then(Value::as_str) {
+        return match resolve_ref(root, reference).and_then(Value::as_object) {
+            Some(target) => field_type(root, target),
+            None => (FieldType::Other, false),
+        };
+    }
+
+    if let Some(values) = object.get("enum").and_then(Value::as_array) {
+        let optional = values.iter().any(Value::is_null);
+        let values = values
+            .iter()
+            .filter_map(Value::as_str)
+            .map(|value| SharedString::from(value.to_string()))
+            .collect();
+        return (FieldType::Enum(values), optional);
+    }
+
+    if let Some(variants) = variants(object) {
+        let optional = variants.iter().any(is_null_type);
+        // Unit enum variants with doc comments are `oneOf` of `const` values
+        let consts: Vec<SharedString> = variants
+            .iter()
+            .filter_map(|variant| variant.get("const")?.as_str())
+            .map(|value| SharedString::from(value.to_string()))
+            .collect();
+        if !consts.is_empty() {
+            return (FieldType::Enum(consts), optional);
+        }
+
+        return match variants.iter().find(|variant| !is_null_type(variant)) {
+            Some(inner) => match inner.as_object() {
+                Some(inner) => (field_type(root, inner).0, optional),
+                None => (FieldType::Other, optional),
+            },
+            None => (FieldType::Other, optional),
+        };
+    }
+
+    let types: Vec<&str> = match object.get("type") {
+        Some(Value::String(ty)) => vec![ty.as_str()],
+        Some(Value::Array(types)) => types.iter().filter_map(Value::as_str).collect(),
+        _ => Vec::new(),
+    };
+    let optional = types.contains(&"null");
+    let ty = match types.into_iter().find(|ty| *ty != "null") {
+        Some("boolean") => FieldType::Bool,
+        Some("integer") => FieldType::Integer,
+        Some("number") => FieldType::Number,
+        Some("string") => FieldType::String,
+        _ => FieldType::Other,
+    };
+    (ty, optional)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FieldType, schema_fields};
+    use gpui::SharedString;
+
+    #[test]
+    fn test_schema_fields() {
+        let fields = schema_fields();
+        let field = |path: &[&str]| {
+            let path: Vec<SharedString> = path.iter().map(|key| SharedString::from(*key)).collect();
+            fields
+                .iter()
+                .find(|field| field.path == path)
+                .unwrap_or_else(|| panic!("missing field {:?}", path))
+        };
+
+        let radius = field(&["radius"]);
+        assert_eq!(radius.ty, FieldType::Integer);
+        assert!(radius.optional);
+
+        assert_eq!(field(&["colors", "background"]).ty, FieldType::String);
+        assert_eq!(
+            field(&["motion", "fast_duration_ms"]).ty,
+            FieldType::Integer
+        );
+
+        let density = field(&["density"]);
+        assert!(matches!(&density.ty, FieldType::Enum(values) if values.len() == 3));
+
+        // Nested objects are expanded, not listed
+        let colors = [SharedString::from("colors")];
+        assert!(fields.iter().all(|field| field.path != colors));
+    }
+}
@@ -0,0 +1,131 @@
+use gpui::{Pixels, Rems, px, rems};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::Size;
+
+use super::Theme;
+
+/// How densely sized components are laid out, see [`Theme::size_metrics`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ThemeDensity {
+    /// Tighter spacing, for information-dense apps.
+    Compact,
+    /// The default spacing.
+    #[default]
+    Comfortable,
+    /// Roomier spacing, e.g. for touch input.
+    Spacious,
+}
+
+impl ThemeDensity {
+    /// The factor applied to paddings, gaps and control heights.
+    pub fn scale(&self) -> f32 {
+        match self {
+            Self::Compact => 0.75,
+            Self::Comfortable => 1.0,
+            Self::Spacious => 1.25,
+        }
+    }
+}
+
+/// Dimensions of a component at a given [`Size`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SizeMetrics {
+    /// Horizontal padding, also used for content insets.
+    pub padding_x: Pixels,
+    /// Vertical padding.
+    pub padding_y: Pixels,
+    /// Gap between inline children, e.g. an icon and a label.
+    pub gap: Pixels,
+    /// Text size, not affected by density.
+    pub text_size: Rems,
+    /// Height of a single-line control, e.g. a Button.
+    pub control_height: Pixels,
+}
+
+impl SizeMetrics {
+    fn scale(self, factor: f32) -> Self {
+        Self {
+            padding_x: self.padding_x * factor,
+            padding_y: self.padding_y * factor,
+            gap: self.gap * factor,
+            text_size: self.text_size,
+            control_height: self.control_height * factor,
+        }
+    }
+}
+
+impl Theme {
+    /// Returns the dimensions for a component at `size`, scaled by the theme density
+    /// and [`Theme::ui_scale`].
+    ///
+    /// A custom `Size::Size` uses the medium metrics with its value as control height.
+    pub fn size_metrics(&self, size: impl Into<Size>) -> SizeMetrics {
+        let size = size.into();
+        let metrics = match size {
+            Size::XSmall => SizeMetrics {
+                padding_x: px(6.),
+                padding_y: px(0.),
+                gap: px(4.),
+                text_size: rems(0.875),
+                control_height: px(20.),
+            },
+            Size::Small => SizeMetrics {
+                padding_x: px(8.),
+                padding_y: px(2.),
+                gap: px(4.),
+                text_size: rems(0.875),
+                control_height: px(24.),
+            },
+            Size::Large => SizeMetrics {
+                padding_x: px(16.),
+                padding_y: px(6.),
+                gap: px(8.),
+                text_size: rems(1.),
+                control_height: px(40.),
+            },
+            Size::Medium | Size::Size(_) => SizeMetrics {
+                padding_x: px(12.),
+                padding_y: px(4.),
+                gap: px(8.),
+                text_size: rems(1.),
+                control_height: match size {
+                    Size::Size(height) => height,
+                    _ => px(32.),
+                },
+            },
+        };
+
+        metrics.scale(self.density.scale() * self.ui_scale)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::px;
+
+    use super::ThemeDensity;
+    use crate::{Size, Theme};
+
+    #[test]
+    fn test_size_metrics_density() {
+        let mut theme = Theme::default();
+        let medium = theme.size_metrics(Size::Medium);
+        assert_eq!(medium.padding_x, px(12.));
+        assert_eq!(medium.control_height, px(32.));
+
+        theme.density = ThemeDensity::Compact;
+        let compact = theme.size_metrics(Size::Medium);
+        assert_eq!(compact.padding_x, px(9.));
+        assert_eq!(compact.control_height, px(24.));
+        assert_eq!(compact.text_size, medium.text_size);
+
+        theme.density = ThemeDensity::Comfortable;
+        theme.ui_scale = 1.5;
+        let zoomed = theme.size_metrics(Size::Medium);
+        assert_eq!(zoomed.padding_x, px(18.));
+        assert_eq!(zoomed.control_height, px(48.));
+    }
+}
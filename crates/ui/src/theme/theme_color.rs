@@ -1,13 +1,14 @@
 use std::sync::Arc;
 
-use crate::{ThemeMode, theme::DEFAULT_THEME_COLORS};
+use crate::{Colorize as _, ThemeMode, theme::DEFAULT_THEME_COLORS};
 
+use anyhow::{Result, anyhow};
 use gpui::Hsla;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// Theme colors used throughout the UI components.
-#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, JsonSchema)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct ThemeColor {
     /// Used for accents such as hover background on MenuItem, ListItem, etc.
     pub accent: Hsla,
@@ -109,6 +110,8 @@ pub struct ThemeColor {
     pub scrollbar_thumb: Hsla,
     /// Scrollbar thumb hover background color.
     pub scrollbar_thumb_hover: Hsla,
+    /// Scrollbar track color, drawn along the bar while it is hovered or dragged.
+    pub scrollbar_track: Hsla,
     /// Secondary background color.
     pub secondary: Hsla,
     /// Active secondary background color.
@@ -243,6 +246,133 @@ pub struct ThemeColor {
     pub cyan_light: Hsla,
 }
 
+/// Calls `$macro!` with the name of every [`ThemeColor`] field, to generate code per
+/// color instead of going through serde.
+///
+/// Keep it in sync with the fields, [`ThemeColor::interpolate`] builds the struct
+/// from it and doesn't compile with a field missing.
+macro_rules! theme_color_fields {
+    ($macro:ident) => {
+        $macro!(
+            accent,
+            accent_foreground,
+            accordion,
+            accordion_hover,
+            background,
+            border,
+            group_box,
+            group_box_foreground,
+            caret,
+            chart_1,
+            chart_2,
+            chart_3,
+            chart_4,
+            chart_5,
+            danger,
+            danger_active,
+            danger_foreground,
+            danger_hover,
+            description_list_label,
+            description_list_label_foreground,
+            drag_border,
+            drop_target,
+            foreground,
+            info,
+            info_active,
+            info_foreground,
+            info_hover,
+            input,
+            link,
+            link_active,
+            link_hover,
+            list,
+            list_active,
+            list_active_border,
+            list_even,
+            list_head,
+            list_hover,
+            muted,
+            muted_foreground,
+            popover,
+            popover_foreground,
+            primary,
+            primary_active,
+            primary_foreground,
+            primary_hover,
+            progress_bar,
+            ring,
+            scrollbar,
+            scrollbar_thumb,
+            scrollbar_thumb_hover,
+            scrollbar_track,
+            secondary,
+            secondary_active,
+            secondary_foreground,
+            secondary_hover,
+            selection,
+            sidebar,
+            sidebar_accent,
+            sidebar_accent_foreground,
+            sidebar_border,
+            sidebar_foreground,
+            sidebar_primary,
+            sidebar_primary_foreground,
+            skeleton,
+            slider_bar,
+            slider_thumb,
+            success,
+            success_foreground,
+            success_hover,
+            success_active,
+            bullish,
+            bearish,
+            switch,
+            switch_thumb,
+            tab,
+            tab_active,
+            tab_active_foreground,
+            tab_bar,
+            tab_bar_segmented,
+            tab_foreground,
+            table,
+            table_active,
+            table_active_border,
+            table_even,
+            table_head,
+            table_head_foreground,
+            table_hover,
+            table_row_border,
+            title_bar,
+            title_bar_border,
+            tiles,
+            warning,
+            warning_active,
+            warning_hover,
+            warning_foreground,
+            overlay,
+            window_border,
+            disabled_foreground,
+            control_stroke,
+            card,
+            card_foreground,
+            solid_background,
+            red,
+            red_light,
+            green,
+            green_light,
+            blue,
+            blue_light,
+            yellow,
+            yellow_light,
+            magenta,
+            magenta_light,
+            cyan,
+            cyan_light,
+        )
+    };
+}
+pub(crate) use theme_color_fields;
+
 impl ThemeColor {
     /// Get the default light theme colors.
     pub fn light() -> Arc<Self> {
@@ -253,4 +383,205 @@ impl ThemeColor {
     pub fn dark() -> Arc<Self> {
         DEFAULT_THEME_COLORS[&ThemeMode::Dark].0.clone()
     }
+
+    /// Set the color with the given field name, e.g. `"accent"` or `"sidebar_border"`.
+    ///
+    /// Returns an error if there is no color with that name.
+    pub fn set_color(&mut self, name: &str, color: Hsla) -> Result<()> {
+        macro_rules! set_field {
+            ($($field:ident),* $(,)?) => {
+                match name {
+                    $(stringify!($field) => self.$field = color,)*
+                    _ => return Err(anyhow!("unknown theme color `{}`", name)),
+                }
+            };
+        }
+
+        theme_color_fields!(set_field);
+        Ok(())
+    }
+
+    /// Returns the WCAG 2 contrast ratio of `fg` on `bg`, from 1.0 to 21.0.
+    ///
+    /// A translucent `fg` is composited over `bg` first; `bg` is treated as opaque.
+    /// WCAG AA asks for at least 4.5 for normal text.
+    pub fn contrast_ratio(fg: Hsla, bg: Hsla) -> f32 {
+        let fg = fg.to_rgb();
+        let bg = bg.to_rgb();
+        let blend = |fg_channel: f32, bg_channel: f32| fg_channel * fg.a + bg_channel * (1. - fg.a);
+        let fg_luminance =
+            relative_luminance(blend(fg.r, bg.r), blend(fg.g, bg.g), blend(fg.b, bg.b));
+        let bg_luminance = relative_luminance(bg.r, bg.g, bg.b);
+
+        let (lighter, darker) = if fg_luminance > bg_luminance {
+            (fg_luminance, bg_luminance)
+        } else {
+            (bg_luminance, fg_luminance)
+        };
+        (lighter + 0.05) / (darker + 0.05)
+    }
+
+    /// Returns a disabled text color for content drawn on `bg`.
+    ///
+    /// Takes whichever of `foreground` and `background` stands out more on `bg` and
+    /// fades it towards `bg` until the contrast drops to about 3:1, so disabled text
+    /// reads as dimmed on any surface while staying legible. On surfaces where even
+    /// the opaque color is below 3:1 it is returned unfaded.
+    pub fn disabled_foreground_on(&self, bg: Hsla) -> Hsla {
+        const TARGET_RATIO: f32 = 3.;
+
+        let fg = if Self::contrast_ratio(self.foreground, bg)
+            >= Self::contrast_ratio(self.background, bg)
+        {
+            self.foreground
+        } else {
+            self.background
+        };
+        if Self::contrast_ratio(fg, bg) <= TARGET_RATIO {
+            return fg;
+        }
+
+        // Contrast grows with opacity, search the lowest opacity reaching the target
+        let (mut low, mut high) = (0., 1.);
+        for _ in 0..16 {
+            let mid = (low + high) / 2.;
+            if Self::contrast_ratio(fg.opacity(mid), bg) < TARGET_RATIO {
+                low = mid;
+            } else {
+                high = mid;
+            }
+        }
+        fg.opacity(high)
+    }
+
+    /// Strengthen the borders and the focus ring for [`crate::Theme::high_contrast`].
+    ///
+    /// They are mixed toward the foreground color and made fully opaque.
+    pub(crate) fn apply_high_contrast(&mut self) {
+        let foreground = self.foreground;
+        for (color, factor) in [
+            (&mut self.border, 0.6),
+            (&mut self.input, 0.6),
+            (&mut self.ring, 0.7),
+        ] {
+            *color = Hsla {
+                a: 1.,
+                ..color.mix(foreground, factor)
+            };
+        }
+    }
+
+    /// Returns the built-in foreground/background pairs with a contrast ratio below
+    /// 4.5:1, as `(foreground, background, ratio)`.
+    pub fn low_contrast_pairs(&self) -> Vec<(&'static str, &'static str, f32)> {
+        macro_rules! pair {
+            ($fg:ident, $bg:ident) => {
+                (stringify!($fg), stringify!($bg), self.$fg, self.$bg)
+            };
+        }
+
+        [
+            pair!(foreground, background),
+            pair!(muted_foreground, background),
+            pair!(muted_foreground, muted),
+            pair!(muted_foreground, sidebar),
+            pair!(sidebar_foreground, sidebar),
+            pair!(popover_foreground, popover),
+            pair!(primary_foreground, primary),
+            pair!(secondary_foreground, secondary),
+            pair!(accent_foreground, accent),
+            pair!(danger_foreground, danger),
+        ]
+        .into_iter()
+        .filter_map(|(fg_name, bg_name, fg, bg)| {
+            let ratio = Self::contrast_ratio(fg, bg);
+            (ratio < 4.5).then_some((fg_name, bg_name, ratio))
+        })
+        .collect()
+    }
+}
+
+/// The WCAG relative luminance of an sRGB color with channels from 0.0 to 1.0.
+fn relative_luminance(r: f32, g: f32, b: f32) -> f32 {
+    let linear = |channel: f32| {
+        if channel <= 0.04045 {
+            channel / 12.92
+        } else {
+            ((channel + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    0.2126 * linear(r) + 0.7152 * linear(g) + 0.0722 * linear(b)
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::{black, hsla, white};
+
+    use super::ThemeColor;
+
+    #[test]
+    fn test_contrast_ratio() {
+        let ratio = ThemeColor::contrast_ratio(black(), white());
+        assert!((ratio - 21.).abs() < 0.01);
+        assert_eq!(
+            ratio,
+            ThemeColor::contrast_ratio(white(), black()),
+            "contrast ratio is symmetric"
+        );
+        assert!((ThemeColor::contrast_ratio(white(), white()) - 1.).abs() < 0.001);
+
+        // #777777 on white is just below AA
+        let gray = gpui::rgb(0x777777).into();
+        let ratio = ThemeColor::contrast_ratio(gray, white());
+        assert!((ratio - 4.48).abs() < 0.01, "got {ratio}");
+
+        // Fully transparent text has no contrast at all
+        let ratio = ThemeColor::contrast_ratio(black().opacity(0.), white());
+        assert!((ratio - 1.).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_apply_high_contrast() {
+        let mut colors = ThemeColor::default();
+        colors.foreground = black();
+        colors.background = white();
+        colors.border = hsla(0., 0., 0.9, 0.5);
+        colors.ring = hsla(0.6, 0.8, 0.6, 0.8);
+
+        let border_ratio = ThemeColor::contrast_ratio(colors.border, colors.background);
+        let ring_ratio = ThemeColor::contrast_ratio(colors.ring, colors.background);
+        colors.apply_high_contrast();
+
+        assert_eq!(colors.border.a, 1.);
+        assert_eq!(colors.ring.a, 1.);
+        assert!(ThemeColor::contrast_ratio(colors.border, colors.background) > border_ratio);
+        assert!(ThemeColor::contrast_ratio(colors.ring, colors.background) > ring_ratio);
+    }
+
+    #[test]
+    fn test_disabled_foreground_on() {
+        let mut colors = ThemeColor::default();
+        colors.foreground = black();
+        colors.background = white();
+
+        for bg in [
+            white(),
+            black(),
+            hsla(0.6, 0.9, 0.45, 1.),
+            hsla(0.1, 0.8, 0.85, 1.),
+        ] {
+            let disabled = colors.disabled_foreground_on(bg);
+            let ratio = ThemeColor::contrast_ratio(disabled, bg);
+            assert!((3.0..3.05).contains(&ratio), "got {ratio} on {bg:?}");
+        }
+
+        // Light text is used on dark surfaces
+        assert_eq!(colors.disabled_foreground_on(black()).l, 1.);
+
+        // Neither gray reaches 3:1 on mid gray, keep the better one as is
+        colors.foreground = hsla(0., 0., 0.35, 1.);
+        colors.background = hsla(0., 0., 0.7, 1.);
+        let gray = hsla(0., 0., 0.5, 1.);
+        assert_eq!(colors.disabled_foreground_on(gray), colors.background);
+    }
 }
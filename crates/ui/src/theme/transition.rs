@@ -0,0 +1,237 @@
+use std::{
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+use gpui::{App, Global, Hsla, Rgba};
+
+use super::theme_color::theme_color_fields;
+use crate::{Theme, ThemeColor, ThemeConfig, ThemeMode, animation::ActiveMotion};
+
+/// Interval between the frames of an animated mode switch.
+const FRAME_INTERVAL: Duration = Duration::from_millis(16);
+
+/// Mix `from` and `to` in the OKLab color space, `t` from 0.0 (`from`) to 1.0 (`to`).
+///
+/// Compared to mixing HSL channels, the colors in between keep an even perceived
+/// lightness and don't swing through unrelated hues.
+pub fn mix_oklab(from: Hsla, to: Hsla, t: f32) -> Hsla {
+    let t = t.clamp(0., 1.);
+    let from_rgb = from.to_rgb();
+    let to_rgb = to.to_rgb();
+    let from_lab = linear_srgb_to_oklab(decode(from_rgb));
+    let to_lab = linear_srgb_to_oklab(decode(to_rgb));
+    let lab: [f32; 3] = std::array::from_fn(|ix| from_lab[ix] + (to_lab[ix] - from_lab[ix]) * t);
+    let [r, g, b] = oklab_to_linear_srgb(lab).map(|channel| encode(channel).clamp(0., 1.));
+
+    Rgba {
+        r,
+        g,
+        b,
+        a: from_rgb.a + (to_rgb.a - from_rgb.a) * t,
+    }
+    .into()
+}
+
+fn decode(rgb: Rgba) -> [f32; 3] {
+    [rgb.r, rgb.g, rgb.b].map(|channel| {
+        if channel <= 0.04045 {
+            channel / 12.92
+        } else {
+            ((channel + 0.055) / 1.055).powf(2.4)
+        }
+    })
+}
+
+fn encode(channel: f32) -> f32 {
+    if channel <= 0.0031308 {
+        channel * 12.92
+    } else {
+        1.055 * channel.max(0.).powf(1. / 2.4) - 0.055
+    }
+}
+
+/// Reference: https://bottosson.github.io/posts/oklab/
+fn linear_srgb_to_oklab([r, g, b]: [f32; 3]) -> [f32; 3] {
+    let l = (0.4122214708 * r + 0.5363325363 * g + 0.0514459929 * b).cbrt();
+    let m = (0.2119034982 * r + 0.6806995451 * g + 0.1073969566 * b).cbrt();
+    let s = (0.0883024619 * r + 0.2817188376 * g + 0.6299787005 * b).cbrt();
+
+    [
+        0.2104542553 * l + 0.7936177850 * m - 0.0040720468 * s,
+        1.9779984951 * l - 2.4285922050 * m + 0.4505937099 * s,
+        0.0259040371 * l + 0.7827717662 * m - 0.8086757660 * s,
+    ]
+}
+
+fn oklab_to_linear_srgb([l, a, b]: [f32; 3]) -> [f32; 3] {
+    let l_ = (l + 0.3963377774 * a + 0.2158037573 * b).powi(3);
+    let m_ = (l - 0.1055613458 * a - 0.0638541728 * b).powi(3);
+    let s_ = (l - 0.0894841775 * a - 1.2914855480 * b).powi(3);
+
+    [
+        4.0767416621 * l_ - 3.3077115913 * m_ + 0.2309699292 * s_,
+        -1.2684380046 * l_ + 2.6097574011 * m_ - 0.3413193965 * s_,
+        -0.0041960863 * l_ - 0.7034186168 * m_ + 1.7076147010 * s_,
+    ]
+}
+
+impl ThemeColor {
+    /// Returns every color mixed between `self` and `other` with [`mix_oklab`].
+    pub fn interpolate(&self, other: &ThemeColor, t: f32) -> ThemeColor {
+        macro_rules! mix_fields {
+            ($($field:ident),* $(,)?) => {
+                ThemeColor {
+                    $($field: mix_oklab(self.$field, other.$field, t),)*
+                }
+            };
+        }
+
+        theme_color_fields!(mix_fields)
+    }
+}
+
+impl Theme {
+    /// Returns a theme blended between the `light` and `dark` theme configs, `t` from
+    /// 0.0 (light) to 1.0 (dark).
+    ///
+    /// Colors are mixed in OKLab; everything that doesn't interpolate, such as the
+    /// mode, fonts, radii and motion durations, switches over at `t = 0.5`.
+    /// Animate `t` to transition between modes, see [`Theme::change_animated`].
+    pub fn interpolated(light: &ThemeConfig, dark: &ThemeConfig, t: f32) -> Theme {
+        let resolve = |config: &ThemeConfig| {
+            let mut theme = Theme::default();
+            theme.apply_config(&Rc::new(config.clone()));
+            theme
+        };
+
+        let mut theme = resolve(light).blend(&resolve(dark), t);
+        theme.light_theme = Rc::new(light.clone());
+        theme.dark_theme = Rc::new(dark.clone());
+        theme
+    }
+
+    fn blend(&self, other: &Theme, t: f32) -> Theme {
+        let t = t.clamp(0., 1.);
+        let mut theme = if t < 0.5 { self.clone() } else { other.clone() };
+        theme.colors = self.colors.interpolate(&other.colors, t);
+        theme.transparent = mix_oklab(self.transparent, other.transparent, t);
+        theme
+    }
+
+    /// Change the theme mode like [`Theme::change`], fading the colors over the
+    /// theme's fade duration.
+    ///
+    /// Switches instantly when motion is reduced. The mode and everything but the
+    /// colors switch right away, only the colors are faded into the global theme,
+    /// so other settings changed meanwhile, e.g. [`Theme::set_ui_scale`], are kept.
+    ///
+    /// A new change while one is running continues from the colors currently shown.
+    /// Any other change of the colors meanwhile, e.g. [`Theme::set_high_contrast`] or
+    /// [`Theme::override_color`], ends the fade with the colors resolved again.
+    pub fn change_animated(mode: impl Into<ThemeMode>, cx: &mut App) {
+        let mode = mode.into();
+        let generation = cx.default_global::<ThemeTransition>().next();
+        if !cx.has_global::<Theme>() || !cx.should_animate() {
+            Theme::change(mode, None, cx);
+            cx.refresh_windows();
+            return;
+        }
+
+        let from = cx.global::<Theme>().clone();
+        let mut to = from.clone();
+        to.mode = mode;
+        let config = if mode.is_dark() {
+            to.dark_theme.clone()
+        } else {
+            to.light_theme.clone()
+        };
+        to.apply_config(&config);
+        let duration = Duration::from_millis(u64::from(to.motion.fade_duration_ms));
+
+        let (from_colors, from_transparent) = (from.colors, from.transparent);
+        let (to_colors, to_transparent) = (to.colors, to.transparent);
+        to.colors = from_colors;
+        to.transparent = from_transparent;
+        cx.set_global(to);
+        // The colors last faded in, telling apart changes made by others
+        let mut shown = from_colors;
+
+        cx.spawn(async move |cx| {
+            let start = Instant::now();
+            loop {
+                cx.background_executor().timer(FRAME_INTERVAL).await;
+                let t = if duration.is_zero() {
+                    1.
+                } else {
+                    (start.elapsed().as_secs_f32() / duration.as_secs_f32()).min(1.)
+                };
+
+                let done = cx.update(|cx| {
+                    // Superseded by a newer change
+                    if cx.global::<ThemeTransition>().0 != generation {
+                        return true;
+                    }
+                    let theme = Theme::global_mut(cx);
+                    let done = t >= 1. || theme.colors != shown;
+                    if done {
+                        theme.transparent = to_transparent;
+                        theme.reapply_config();
+                        Theme::notify_changed(cx);
+                    } else {
+                        theme.colors = from_colors.interpolate(&to_colors, t);
+                        theme.transparent = mix_oklab(from_transparent, to_transparent, t);
+                        shown = theme.colors;
+                    }
+                    cx.refresh_windows();
+                    done
+                });
+                if done.unwrap_or(true) {
+                    break;
+                }
+            }
+        })
+        .detach();
+    }
+}
+
+/// Generation of the latest [`Theme::change_animated`], to stop superseded transitions.
+#[derive(Default)]
+struct ThemeTransition(u64);
+
+impl ThemeTransition {
+    fn next(&mut self) -> u64 {
+        self.0 += 1;
+        self.0
+    }
+}
+
+impl Global for ThemeTransition {}
+
+#[cfg(test)]
+mod tests {
+    use gpui::{black, hsla, white};
+
+    use super::mix_oklab;
+
+    #[test]
+    fn test_mix_oklab() {
+        let close = |a: gpui::Hsla, b: gpui::Hsla| {
+            let (a, b) = (a.to_rgb(), b.to_rgb());
+            (a.r - b.r).abs() < 0.005 && (a.g - b.g).abs() < 0.005 && (a.b - b.b).abs() < 0.005
+        };
+
+        let blue = hsla(0.6, 0.8, 0.5, 1.);
+        assert!(close(mix_oklab(black(), blue, 0.), black()));
+        assert!(close(mix_oklab(black(), blue, 1.), blue));
+
+        // Halfway from black to white is a perceptual mid gray, darker than the
+        // sRGB average
+        let mid = mix_oklab(black(), white(), 0.5).to_rgb();
+        assert!((mid.r - mid.g).abs() < 0.005 && (mid.g - mid.b).abs() < 0.005);
+        assert!((0.35..0.42).contains(&mid.r), "got {}", mid.r);
+
+        let faded = mix_oklab(blue.opacity(0.), blue, 0.5);
+        assert!((faded.a - 0.5).abs() < 0.001);
+    }
+}
@@ -2,6 +2,8 @@ use gpui::{FontWeight, Pixels, px};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use super::ThemeMode;
+
 /// A single step in the type ramp.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema)]
 pub struct TypeRampToken {
@@ -89,6 +91,15 @@ pub struct ThemeTypographyConfig {
     pub title: Option<TypeRampTokenConfig>,
     pub title_large: Option<TypeRampTokenConfig>,
     pub display: Option<TypeRampTokenConfig>,
+    /// Overrides applied on top of the ramp above in light mode.
+    ///
+    /// Only set values are overridden; nested `light`/`dark` keys are ignored.
+    pub light: Option<Box<ThemeTypographyConfig>>,
+    /// Overrides applied on top of the ramp above in dark mode, e.g. a subtly
+    /// heavier `body` weight for legibility.
+    ///
+    /// Only set values are overridden; nested `light`/`dark` keys are ignored.
+    pub dark: Option<Box<ThemeTypographyConfig>>,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
@@ -100,7 +111,8 @@ pub struct TypeRampTokenConfig {
 }
 
 impl ThemeTypography {
-    pub fn apply_config(&mut self, config: Option<&ThemeTypographyConfig>) {
+    /// Apply the config, followed by its `light` or `dark` overrides for `mode`.
+    pub fn apply_config(&mut self, config: Option<&ThemeTypographyConfig>, mode: ThemeMode) {
         let defaults = ThemeTypography::default();
         if let Some(config) = config {
             macro_rules! apply_ramp {
@@ -126,8 +138,45 @@ impl ThemeTypography {
             apply_ramp!(title);
             apply_ramp!(title_large);
             apply_ramp!(display);
+
+            let mode_overrides = if mode.is_dark() {
+                config.dark.as_deref()
+            } else {
+                config.light.as_deref()
+            };
+            if let Some(mode_overrides) = mode_overrides {
+                self.apply_overrides(mode_overrides);
+            }
         } else {
             *self = defaults;
         }
     }
+
+    /// Override only the values set in `config`, keeping the current ones otherwise.
+    fn apply_overrides(&mut self, config: &ThemeTypographyConfig) {
+        macro_rules! override_ramp {
+            ($field:ident) => {
+                if let Some(ref cfg) = config.$field {
+                    if let Some(size) = cfg.size {
+                        self.$field.size = px(size);
+                    }
+                    if let Some(line_height) = cfg.line_height {
+                        self.$field.line_height = px(line_height);
+                    }
+                    if let Some(weight) = cfg.weight {
+                        self.$field.weight = FontWeight(weight);
+                    }
+                }
+            };
+        }
+        override_ramp!(caption);
+        override_ramp!(body);
+        override_ramp!(body_strong);
+        override_ramp!(body_large);
+        override_ramp!(body_large_strong);
+        override_ramp!(subtitle);
+        override_ramp!(title);
+        override_ramp!(title_large);
+        override_ramp!(display);
+    }
 }
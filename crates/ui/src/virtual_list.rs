@@ -197,6 +197,7 @@ where
         item_sizes,
         render_items: Box::new(render_range),
         sizing_behavior: ListSizingBehavior::default(),
+        overscan: 0,
     }
 }
 
@@ -212,6 +213,7 @@ pub struct VirtualList {
         dyn for<'a> Fn(Range<usize>, &'a mut Window, &'a mut App) -> SmallVec<[AnyElement; 64]>,
     >,
     sizing_behavior: ListSizingBehavior,
+    overscan: usize,
 }
 
 impl Styled for VirtualList {
@@ -233,6 +235,14 @@ impl VirtualList {
         self
     }
 
+    /// Set the number of extra items to render before and after the visible range.
+    ///
+    /// Default is 0.
+    pub fn overscan(mut self, overscan: usize) -> Self {
+        self.overscan = overscan;
+        self
+    }
+
     fn compute_item_bounds(
         &self,
         ix: usize,
@@ -631,8 +641,11 @@ impl Element for VirtualList {
                         self.items_count,
                     );
 
-                    let visible_range = first_visible_element_ix
-                        ..cmp::min(last_visible_element_ix, self.items_count);
+                    let visible_range = first_visible_element_ix.saturating_sub(self.overscan)
+                        ..cmp::min(
+                            last_visible_element_ix.saturating_add(self.overscan),
+                            self.items_count,
+                        );
 
                     let items = (self.render_items)(visible_range.clone(), window, cx);
 
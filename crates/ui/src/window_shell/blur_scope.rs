@@ -101,7 +101,10 @@ impl Element for BlurEnabledScope {
             .child
             .take()
             .expect("BlurEnabledScope child already taken");
+        // Components render during layout, so the context must be visible here too
+        GlobalState::global_mut(cx).push_blur_enabled(self.enabled);
         let layout_id = child.request_layout(window, cx);
+        GlobalState::global_mut(cx).pop_blur_enabled();
         (layout_id, BlurEnabledScopeLayoutState { child })
     }
 
@@ -0,0 +1,101 @@
+//! ElevationScope - A wrapper element that provides elevation context to children.
+//!
+//! This element pushes an `ElevationToken` onto the global context stack before
+//! rendering children, then pops it after. Components like `SidebarShell` read this
+//! value via `GlobalState::global(cx).elevation()` when no explicit elevation is set.
+
+use gpui::{
+    AnyElement, App, Bounds, Element, ElementId, GlobalElementId, InspectorElementId, IntoElement,
+    LayoutId, Pixels, Window,
+};
+
+use crate::{ElevationToken, global_state::GlobalState};
+
+/// A wrapper element that provides `elevation` context to its children.
+///
+/// Used by `WindowShell` to apply per-sidebar elevation overrides without
+/// requiring the consumer to configure each `SidebarShell` explicitly.
+pub struct ElevationScope {
+    elevation: ElevationToken,
+    child: Option<AnyElement>,
+}
+
+impl ElevationScope {
+    /// Create a new elevation context scope.
+    pub fn new(elevation: ElevationToken, child: impl IntoElement) -> Self {
+        Self {
+            elevation,
+            child: Some(child.into_any_element()),
+        }
+    }
+}
+
+impl IntoElement for ElevationScope {
+    type Element = Self;
+
+    fn into_element(self) -> Self::Element {
+        self
+    }
+}
+
+pub struct ElevationScopeLayoutState {
+    child: AnyElement,
+}
+
+impl Element for ElevationScope {
+    type RequestLayoutState = ElevationScopeLayoutState;
+    type PrepaintState = ();
+
+    fn id(&self) -> Option<ElementId> {
+        None
+    }
+
+    fn source_location(&self) -> Option<&'static std::panic::Location<'static>> {
+        None
+    }
+
+    fn request_layout(
+        &mut self,
+        _global_id: Option<&GlobalElementId>,
+        _inspector_id: Option<&InspectorElementId>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> (LayoutId, Self::RequestLayoutState) {
+        let mut child = self
+            .child
+            .take()
+            .expect("ElevationScope child already taken");
+        // `SidebarShell` resolves its elevation in `render`, which runs during layout
+        GlobalState::global_mut(cx).push_elevation(self.elevation);
+        let layout_id = child.request_layout(window, cx);
+        GlobalState::global_mut(cx).pop_elevation();
+        (layout_id, ElevationScopeLayoutState { child })
+    }
+
+    fn prepaint(
+        &mut self,
+        _global_id: Option<&GlobalElementId>,
+        _inspector_id: Option<&InspectorElementId>,
+        _bounds: Bounds<Pixels>,
+        request_layout: &mut Self::RequestLayoutState,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Self::PrepaintState {
+        request_layout.child.prepaint(window, cx);
+    }
+
+    fn paint(
+        &mut self,
+        _global_id: Option<&GlobalElementId>,
+        _inspector_id: Option<&InspectorElementId>,
+        _bounds: Bounds<Pixels>,
+        request_layout: &mut Self::RequestLayoutState,
+        _prepaint: &mut Self::PrepaintState,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        GlobalState::global_mut(cx).push_elevation(self.elevation);
+        request_layout.child.paint(window, cx);
+        GlobalState::global_mut(cx).pop_elevation();
+    }
+}
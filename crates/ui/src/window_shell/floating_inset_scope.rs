@@ -21,6 +21,12 @@ impl FloatingInsetScope {
             child: Some(child.into_any_element()),
         }
     }
+
+    /// Replace the provided inset, e.g. while animating between values.
+    pub(crate) fn inset(mut self, inset: Pixels) -> Self {
+        self.inset = inset;
+        self
+    }
 }
 
 impl IntoElement for FloatingInsetScope {
@@ -58,7 +64,10 @@ impl Element for FloatingInsetScope {
             .child
             .take()
             .expect("FloatingInsetScope child already taken");
+        // `SidebarShell` resolves its inset in `render`, which runs during layout
+        GlobalState::global_mut(cx).push_floating_inset(self.inset);
         let layout_id = child.request_layout(window, cx);
+        GlobalState::global_mut(cx).pop_floating_inset();
         (layout_id, FloatingInsetScopeLayoutState { child })
     }
 
@@ -0,0 +1,161 @@
+//! Keyboard focus switching between the regions of a `WindowShell`.
+//!
+//! Associate a focus handle with each region via `WindowShell::region_focus`, then
+//! `secondary-1` / `secondary-2` / `secondary-3` (cmd on macOS, ctrl elsewhere) move focus
+//! to the left sidebar, main content and right sidebar. `f6` / `shift-f6` cycle focus
+//! forward and backward across the regions whose slot is populated.
+
+use gpui::{App, FocusHandle, KeyBinding, Window, actions};
+
+pub(super) const CONTEXT: &str = "WindowShell";
+
+actions!(
+    window_shell,
+    [
+        FocusLeftSidebar,
+        FocusMain,
+        FocusRightSidebar,
+        FocusNextRegion,
+        FocusPreviousRegion
+    ]
+);
+
+pub(super) fn init(cx: &mut App) {
+    cx.bind_keys([
+        KeyBinding::new("secondary-1", FocusLeftSidebar, Some(CONTEXT)),
+        KeyBinding::new("secondary-2", FocusMain, Some(CONTEXT)),
+        KeyBinding::new("secondary-3", FocusRightSidebar, Some(CONTEXT)),
+        KeyBinding::new("f6", FocusNextRegion, Some(CONTEXT)),
+        KeyBinding::new("shift-f6", FocusPreviousRegion, Some(CONTEXT)),
+    ]);
+}
+
+/// A focusable region of a `WindowShell`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WindowRegion {
+    LeftSidebar,
+    Main,
+    RightSidebar,
+}
+
+impl WindowRegion {
+    /// All regions in focus cycle order.
+    pub(super) const ALL: [WindowRegion; 3] = [Self::LeftSidebar, Self::Main, Self::RightSidebar];
+}
+
+/// The focus handles associated with the regions of a `WindowShell`.
+#[derive(Clone, Default)]
+pub(super) struct RegionFocusHandles {
+    left_sidebar: Option<FocusHandle>,
+    main: Option<FocusHandle>,
+    right_sidebar: Option<FocusHandle>,
+}
+
+impl RegionFocusHandles {
+    pub(super) fn get(&self, region: WindowRegion) -> Option<&FocusHandle> {
+        match region {
+            WindowRegion::LeftSidebar => self.left_sidebar.as_ref(),
+            WindowRegion::Main => self.main.as_ref(),
+            WindowRegion::RightSidebar => self.right_sidebar.as_ref(),
+        }
+    }
+
+    pub(super) fn set(&mut self, region: WindowRegion, handle: FocusHandle) {
+        let slot = match region {
+            WindowRegion::LeftSidebar => &mut self.left_sidebar,
+            WindowRegion::Main => &mut self.main,
+            WindowRegion::RightSidebar => &mut self.right_sidebar,
+        };
+        *slot = Some(handle);
+    }
+
+    /// Focus the handle of `region`, returns false if none is associated.
+    pub(super) fn focus(&self, region: WindowRegion, window: &mut Window, cx: &mut App) -> bool {
+        let Some(handle) = self.get(region) else {
+            return false;
+        };
+        handle.focus(window, cx);
+        true
+    }
+
+    /// Returns the region containing the focused element, if any.
+    fn focused(&self, window: &Window, cx: &App) -> Option<WindowRegion> {
+        WindowRegion::ALL.into_iter().find(|region| {
+            self.get(*region)
+                .is_some_and(|handle| handle.contains_focused(window, cx))
+        })
+    }
+
+    /// Move focus to the next (or previous) of the `present` regions that has a
+    /// focus handle, wrapping around. Returns false if there is none.
+    pub(super) fn cycle(
+        &self,
+        present: &[WindowRegion],
+        forward: bool,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> bool {
+        let regions = present
+            .iter()
+            .copied()
+            .filter(|region| self.get(*region).is_some())
+            .collect::<Vec<_>>();
+        let Some(region) = next_region(&regions, self.focused(window, cx), forward) else {
+            return false;
+        };
+
+        self.focus(region, window, cx);
+        window.refresh();
+        true
+    }
+}
+
+/// Returns the region after `current` in `regions`, or before it when not `forward`,
+/// wrapping around. Without a current region, starts from the first (or last) one.
+fn next_region(
+    regions: &[WindowRegion],
+    current: Option<WindowRegion>,
+    forward: bool,
+) -> Option<WindowRegion> {
+    let len = regions.len();
+    if len == 0 {
+        return None;
+    }
+
+    let ix = match current.and_then(|current| regions.iter().position(|r| *r == current)) {
+        Some(ix) if forward => (ix + 1) % len,
+        Some(ix) => (ix + len - 1) % len,
+        None if forward => 0,
+        None => len - 1,
+    };
+    Some(regions[ix])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{WindowRegion, next_region};
+
+    #[test]
+    fn test_next_region() {
+        use WindowRegion::*;
+
+        let all = WindowRegion::ALL;
+        assert_eq!(next_region(&all, Some(LeftSidebar), true), Some(Main));
+        assert_eq!(
+            next_region(&all, Some(RightSidebar), true),
+            Some(LeftSidebar)
+        );
+        assert_eq!(
+            next_region(&all, Some(LeftSidebar), false),
+            Some(RightSidebar)
+        );
+        assert_eq!(next_region(&all, None, true), Some(LeftSidebar));
+        assert_eq!(next_region(&all, None, false), Some(RightSidebar));
+
+        // Regions without a slot are skipped
+        let present = [Main, RightSidebar];
+        assert_eq!(next_region(&present, Some(RightSidebar), true), Some(Main));
+        assert_eq!(next_region(&present, Some(LeftSidebar), true), Some(Main));
+        assert_eq!(next_region(&[], None, true), None);
+    }
+}
@@ -5,10 +5,14 @@
 //!
 //! # Layout Modes
 //!
-//! - **Standard**: Docked sidebars in a flex row; main fills remaining space.
+//! - **Standard**: Docked sidebars in a flex row; main fills remaining space, between
+//!   the optional top and bottom panels.
 //! - **FloatingPanels**: Inset glass panels using `SidebarShell`; main spans full window.
+//!   Sidebars given a width get a WindowShell-owned splitter, as in Split mode.
 //! - **Overlay**: Sidebars overlay main content as absolute panels.
-//! - **Split**: Docked layout with WindowShell-owned splitter bar.
+//! - **Split**: Docked layout with WindowShell-owned splitter bars, including one above
+//!   the bottom panel.
+//! - **Panes**: A nested `PaneTree` of resizable panes.
 //!
 //! # Example
 //!
@@ -31,29 +35,74 @@
 //!   underlying content from intercepting clicks in the title bar region.
 
 mod blur_scope;
+mod elevation_scope;
 mod floating_inset_scope;
+mod focus_region;
+mod pane_tree;
 mod reduced_motion_scope;
 
 pub use blur_scope::BlurEnabledScope;
+pub use elevation_scope::ElevationScope;
 pub use floating_inset_scope::FloatingInsetScope;
+pub use focus_region::{
+    FocusLeftSidebar, FocusMain, FocusNextRegion, FocusPreviousRegion, FocusRightSidebar,
+    WindowRegion,
+};
+pub use pane_tree::PaneTree;
 pub use reduced_motion_scope::ReducedMotionScope;
 
 use std::rc::Rc;
+use std::time::Duration;
 
 use gpui::{
-    AnyElement, App, Hsla, InteractiveElement, IntoElement, MouseButton, MouseMoveEvent,
-    MouseUpEvent, ParentElement, Pixels, RenderOnce, StyleRefinement, Styled, Window,
-    WindowDecorations, WindowOptions, div, prelude::FluentBuilder as _, px, transparent_black,
+    Animation, AnimationExt as _, AnyElement, App, Corners, CursorStyle, Decorations, Div,
+    ElementId, Entity, FocusHandle, HitboxBehavior, Hsla, InteractiveElement, IntoElement,
+    MouseButton, MouseDownEvent, MouseMoveEvent, MouseUpEvent, ParentElement, Pixels, Point,
+    RenderOnce, StyleRefinement, Styled, Window, WindowDecorations, WindowOptions, canvas, div,
+    prelude::FluentBuilder as _, px, relative, transparent_black,
+};
+
+use focus_region::RegionFocusHandles;
+use pane_tree::{PaneResizeHandler, PaneSplitterStyle};
+
+use crate::{
+    ActiveTheme, ElevationToken, Placement, Side, Sizable as _, StyledExt, TITLE_BAR_HEIGHT,
+    TitleBar,
+    animation::{
+        ActiveMotion, PresenceOptions, PresencePhase, PresenceTransition,
+        animation_with_theme_easing, fade_animation, keyed_presence, point_to_point_animation,
+    },
+    global_state::GlobalState,
+    progress::Progress,
+    spinner::Spinner,
 };
 
-use crate::{ActiveTheme, StyledExt, TITLE_BAR_HEIGHT, TitleBar};
+pub(crate) fn init(cx: &mut App) {
+    focus_region::init(cx);
+}
 
 /// Default additional safe area offsets for title bar content.
 const DEFAULT_SAFE_AREA_LEFT: Pixels = px(0.0);
 const DEFAULT_SAFE_AREA_RIGHT: Pixels = px(0.0);
 
+/// Distance from the top edge at which an auto-hidden title bar is revealed.
+const TITLE_BAR_REVEAL_EDGE: Pixels = px(4.0);
+
+/// Height of the loading bar, see [`WindowShell::progress`].
+const PROGRESS_BAR_HEIGHT: Pixels = px(2.0);
+/// Width of the moving segment of an indeterminate loading bar, relative to the window.
+const INDETERMINATE_SEGMENT: f32 = 0.3;
+
 /// Default splitter width for Split layout mode.
 const DEFAULT_SPLITTER_WIDTH: Pixels = px(4.0);
+/// Default narrowest sidebar width reachable by dragging the splitter.
+const DEFAULT_SPLIT_MIN_WIDTH: Pixels = px(120.0);
+/// Default widest sidebar width reachable by dragging the splitter.
+const DEFAULT_SPLIT_MAX_WIDTH: Pixels = px(600.0);
+/// Default lowest bottom panel height reachable by dragging its splitter.
+const DEFAULT_SPLIT_BOTTOM_MIN_HEIGHT: Pixels = px(80.0);
+/// Default tallest bottom panel height reachable by dragging its splitter.
+const DEFAULT_SPLIT_BOTTOM_MAX_HEIGHT: Pixels = px(600.0);
 
 /// Layout modes for WindowShell.
 ///
@@ -78,6 +127,10 @@ pub enum WindowLayoutMode {
     /// Docked layout with a draggable splitter between sidebar and main.
     /// IDE-style panes with persistent resizable layout.
     Split,
+
+    /// A recursive tree of panes set by [`WindowShell::panes`], with a
+    /// draggable splitter between adjacent panes. Sidebars are not rendered.
+    Panes,
 }
 
 /// A complete window primitive with layout modes, title bar, and sidebar support.
@@ -101,8 +154,12 @@ pub struct WindowShell {
     layout_mode: WindowLayoutMode,
     title_bar_height: Pixels,
     inset: Pixels,
+    inset_when_maximized: Option<Pixels>,
     blur_enabled: bool,
     reduced_motion: bool,
+    window_radius: Option<Pixels>,
+    window_border: bool,
+    auto_hide_title_bar: bool,
 
     // Safe area offsets
     safe_area_left: Pixels,
@@ -111,7 +168,16 @@ pub struct WindowShell {
     // Content slots
     sidebar_left: Option<AnyElement>,
     sidebar_right: Option<AnyElement>,
+    sidebar_top: Option<AnyElement>,
+    sidebar_bottom: Option<AnyElement>,
     main: Option<AnyElement>,
+    region_focus: RegionFocusHandles,
+
+    // Per-sidebar overrides (None inherits from the shell)
+    sidebar_left_blur: Option<bool>,
+    sidebar_right_blur: Option<bool>,
+    sidebar_left_elevation: Option<ElevationToken>,
+    sidebar_right_elevation: Option<ElevationToken>,
 
     // Title bar slots
     title_bar_left: Option<AnyElement>,
@@ -124,15 +190,34 @@ pub struct WindowShell {
     // Additional slots
     background: Option<AnyElement>,
     overlay_children: Option<AnyElement>,
+    /// `None` hides the bar, `Some(None)` shows it as indeterminate.
+    progress: Option<Option<f32>>,
+    interaction_enabled: bool,
+    busy_spinner: bool,
 
     // Mouse event forwarding
     on_mouse_move: Option<Rc<dyn Fn(&MouseMoveEvent, &mut Window, &mut App)>>,
     on_mouse_up: Option<Rc<dyn Fn(&MouseUpEvent, &mut Window, &mut App)>>,
+    force_cursor: Option<CursorStyle>,
 
     // Split mode configuration
-    on_split_resize: Option<Rc<dyn Fn(Pixels, &mut Window, &mut App)>>,
+    on_split_resize: Option<Rc<dyn Fn(Side, Pixels, &mut Window, &mut App)>>,
+    sidebar_left_width: Option<Pixels>,
+    sidebar_right_width: Option<Pixels>,
     splitter_width: Pixels,
     splitter_style: StyleRefinement,
+    split_snap_points: Vec<Pixels>,
+    split_snap_threshold: Pixels,
+    split_min_width: Pixels,
+    split_max_width: Pixels,
+    on_split_bottom_resize: Option<Rc<dyn Fn(Pixels, &mut Window, &mut App)>>,
+    split_bottom_min_height: Pixels,
+    split_bottom_max_height: Pixels,
+    on_splitter_double_click: Option<SplitterDoubleClickHandler>,
+
+    // Panes mode configuration
+    panes: Option<PaneTree>,
+    on_pane_resize: Option<PaneResizeHandler>,
 
     // Root style
     style: StyleRefinement,
@@ -160,24 +245,51 @@ impl WindowShell {
             layout_mode: WindowLayoutMode::default(),
             title_bar_height: TITLE_BAR_HEIGHT,
             inset: px(4.0),
+            inset_when_maximized: None,
             blur_enabled: true,
             reduced_motion: false,
+            window_radius: None,
+            window_border: false,
+            auto_hide_title_bar: false,
             safe_area_left: DEFAULT_SAFE_AREA_LEFT,
             safe_area_right: DEFAULT_SAFE_AREA_RIGHT,
             sidebar_left: None,
             sidebar_right: None,
+            sidebar_top: None,
+            sidebar_bottom: None,
             main: None,
+            region_focus: RegionFocusHandles::default(),
+            sidebar_left_blur: None,
+            sidebar_right_blur: None,
+            sidebar_left_elevation: None,
+            sidebar_right_elevation: None,
             title_bar_left: None,
             title_bar_center: None,
             title_bar_right: None,
             title_bar_override: None,
             background: None,
             overlay_children: None,
+            progress: None,
+            interaction_enabled: true,
+            busy_spinner: false,
             on_mouse_move: None,
             on_mouse_up: None,
+            force_cursor: None,
             on_split_resize: None,
+            sidebar_left_width: None,
+            sidebar_right_width: None,
             splitter_width: DEFAULT_SPLITTER_WIDTH,
             splitter_style: StyleRefinement::default(),
+            split_snap_points: Vec::new(),
+            split_snap_threshold: px(0.0),
+            split_min_width: DEFAULT_SPLIT_MIN_WIDTH,
+            split_max_width: DEFAULT_SPLIT_MAX_WIDTH,
+            on_split_bottom_resize: None,
+            split_bottom_min_height: DEFAULT_SPLIT_BOTTOM_MIN_HEIGHT,
+            split_bottom_max_height: DEFAULT_SPLIT_BOTTOM_MAX_HEIGHT,
+            on_splitter_double_click: None,
+            panes: None,
+            on_pane_resize: None,
             style: StyleRefinement::default(),
         }
     }
@@ -218,6 +330,16 @@ impl WindowShell {
         self
     }
 
+    /// Set the outer inset used while the window is maximized (used in FloatingPanels mode).
+    ///
+    /// Switching between maximized and restored animates from one inset to the other
+    /// using the theme's point-to-point motion, unless reduced motion is enabled.
+    /// Defaults to `inset`.
+    pub fn inset_when_maximized(mut self, inset: impl Into<Pixels>) -> Self {
+        self.inset_when_maximized = Some(inset.into());
+        self
+    }
+
     /// Set whether blur effects are enabled.
     pub fn blur_enabled(mut self, enabled: bool) -> Self {
         self.blur_enabled = enabled;
@@ -230,6 +352,39 @@ impl WindowShell {
         self
     }
 
+    /// Set the window corner radius for client-decorated windows (Linux/Windows).
+    ///
+    /// Rounds and clips the shell root, and rounds the background and title bar layers
+    /// so nothing square pokes out of the window corners. Corners touching a tiled or
+    /// maximized edge stay square. No-op on macOS and for server-side decorations,
+    /// where the OS rounds the window.
+    pub fn window_radius(mut self, radius: impl Into<Pixels>) -> Self {
+        self.window_radius = Some(radius.into());
+        self
+    }
+
+    /// Draw a 1px outline around client-decorated windows (Linux/Windows).
+    ///
+    /// The outline is stronger while the window is active and fades when it loses
+    /// focus, following the theme's `active_window_level` and `inactive_window_level`
+    /// elevation. Skipped on macOS, for server-side decorations and while maximized.
+    ///
+    /// Default: false.
+    pub fn window_border(mut self, window_border: bool) -> Self {
+        self.window_border = window_border;
+        self
+    }
+
+    /// Set whether the title bar hides while the window is fullscreen, default is `false`.
+    ///
+    /// The content then takes the full window height, and the title bar slides down
+    /// over it when the cursor reaches the top edge, hiding again once the cursor
+    /// leaves it. Outside fullscreen the title bar is always shown.
+    pub fn auto_hide_title_bar(mut self, auto_hide: bool) -> Self {
+        self.auto_hide_title_bar = auto_hide;
+        self
+    }
+
     // ─────────────────────────────────────────────────────────────────────────────
     // Safe area configuration
     // ─────────────────────────────────────────────────────────────────────────────
@@ -262,12 +417,83 @@ impl WindowShell {
         self
     }
 
+    /// Set a panel docked above the main content, between the sidebars
+    /// (Standard and Split modes only).
+    pub fn sidebar_top(mut self, element: impl IntoElement) -> Self {
+        self.sidebar_top = Some(element.into_any_element());
+        self
+    }
+
+    /// Set a panel docked below the main content, between the sidebars, such as
+    /// a terminal or problems list (Standard and Split modes only).
+    ///
+    /// The panel sets its own height. In Split mode it's resizable with the
+    /// splitter above it, see [`Self::on_split_bottom_resize`].
+    pub fn sidebar_bottom(mut self, element: impl IntoElement) -> Self {
+        self.sidebar_bottom = Some(element.into_any_element());
+        self
+    }
+
     /// Set the main content area.
     pub fn main(mut self, element: impl IntoElement) -> Self {
         self.main = Some(element.into_any_element());
         self
     }
 
+    /// Associate a focus handle with a region, for keyboard focus switching.
+    ///
+    /// The `FocusLeftSidebar`, `FocusMain` and `FocusRightSidebar` actions, bound to
+    /// `secondary-1/2/3` by default, move focus to the associated handle. The handle
+    /// should be tracked by the region's content.
+    ///
+    /// `FocusNextRegion` and `FocusPreviousRegion`, bound to `f6` / `shift-f6`, cycle
+    /// focus across the regions with a handle and a populated slot.
+    pub fn region_focus(mut self, region: WindowRegion, handle: &FocusHandle) -> Self {
+        self.region_focus.set(region, handle.clone());
+        self
+    }
+
+    /// Move focus to `region`, returns false if no focus handle is associated with it.
+    pub fn focus_region(&self, region: WindowRegion, window: &mut Window, cx: &mut App) -> bool {
+        self.region_focus.focus(region, window, cx)
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────────
+    // Per-sidebar overrides
+    // ─────────────────────────────────────────────────────────────────────────────
+
+    /// Override blur for the left sidebar only.
+    ///
+    /// When unset, the left sidebar inherits the shell-wide `blur_enabled` value.
+    pub fn sidebar_left_blur(mut self, enabled: bool) -> Self {
+        self.sidebar_left_blur = Some(enabled);
+        self
+    }
+
+    /// Override blur for the right sidebar only.
+    ///
+    /// When unset, the right sidebar inherits the shell-wide `blur_enabled` value.
+    pub fn sidebar_right_blur(mut self, enabled: bool) -> Self {
+        self.sidebar_right_blur = Some(enabled);
+        self
+    }
+
+    /// Override the elevation of the left sidebar.
+    ///
+    /// Applies to `SidebarShell`s without an explicit elevation of their own.
+    pub fn sidebar_left_elevation(mut self, elevation: ElevationToken) -> Self {
+        self.sidebar_left_elevation = Some(elevation);
+        self
+    }
+
+    /// Override the elevation of the right sidebar.
+    ///
+    /// Applies to `SidebarShell`s without an explicit elevation of their own.
+    pub fn sidebar_right_elevation(mut self, elevation: ElevationToken) -> Self {
+        self.sidebar_right_elevation = Some(elevation);
+        self
+    }
+
     // ─────────────────────────────────────────────────────────────────────────────
     // Title bar slots
     // ─────────────────────────────────────────────────────────────────────────────
@@ -329,6 +555,33 @@ impl WindowShell {
         self
     }
 
+    /// Show a thin loading bar across the window, just below the title bar.
+    ///
+    /// `Some(fraction)` shows determinate progress from 0.0 to 1.0; `None` shows an
+    /// indeterminate sweep. The bar is hidden unless this is called.
+    pub fn progress(mut self, progress: Option<f32>) -> Self {
+        self.progress = Some(progress);
+        self
+    }
+
+    /// Set whether the shell content accepts mouse interaction, e.g. `false` while saving.
+    ///
+    /// When disabled, a transparent layer over everything below the title bar swallows
+    /// clicks and hovers, so the window can still be moved and closed. It fades in and
+    /// out with the theme motion. Default: true.
+    pub fn interaction_enabled(mut self, enabled: bool) -> Self {
+        self.interaction_enabled = enabled;
+        self
+    }
+
+    /// Show a spinner in the middle of the layer added by `interaction_enabled(false)`.
+    ///
+    /// Default: false.
+    pub fn busy_spinner(mut self, busy_spinner: bool) -> Self {
+        self.busy_spinner = busy_spinner;
+        self
+    }
+
     // ─────────────────────────────────────────────────────────────────────────────
     // Mouse event forwarding
     // ─────────────────────────────────────────────────────────────────────────────
@@ -355,21 +608,72 @@ impl WindowShell {
         self
     }
 
+    /// Force a cursor over the whole window, e.g. `ResizeLeftRight` during a resize
+    /// drag or a wait cursor during a long operation.
+    ///
+    /// While set, the cursors of hovered children are ignored; mouse events still reach
+    /// them. Pass `None` to restore normal cursor handling.
+    pub fn force_cursor(mut self, cursor: Option<CursorStyle>) -> Self {
+        self.force_cursor = cursor;
+        self
+    }
+
     // ─────────────────────────────────────────────────────────────────────────────
     // Split mode configuration
     // ─────────────────────────────────────────────────────────────────────────────
 
-    /// Set the callback for split resize operations (Split mode only).
+    /// Set the callback for split resize operations (Split and FloatingPanels
+    /// modes).
     ///
-    /// The callback receives the new sidebar width.
+    /// The callback receives the new sidebar width while a splitter is dragged,
+    /// clamped to `split_min_width`..=`split_max_width`. Use
+    /// [`Self::on_split_resize_side`] to also know which sidebar changed.
     pub fn on_split_resize(
         mut self,
         handler: impl Fn(Pixels, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_split_resize = Some(Rc::new(move |_, width, window, cx| {
+            handler(width, window, cx)
+        }));
+        self
+    }
+
+    /// Set the callback for split resize operations with the side of the
+    /// resized sidebar (Split and FloatingPanels modes).
+    ///
+    /// The shell sizes the dragged sidebar itself; store the width keyed by side,
+    /// e.g. in app settings, and pass it back to [`Self::sidebar_left_width`] or
+    /// [`Self::sidebar_right_width`] to restore it on the next launch.
+    pub fn on_split_resize_side(
+        mut self,
+        handler: impl Fn(Side, Pixels, &mut Window, &mut App) + 'static,
     ) -> Self {
         self.on_split_resize = Some(Rc::new(handler));
         self
     }
 
+    /// Set the initial width of the left sidebar (Split and FloatingPanels modes).
+    ///
+    /// Used until the splitter is dragged, after which the shell keeps the
+    /// dragged width until a different width is passed here. Leave unset to
+    /// size the sidebar by its content.
+    ///
+    /// In FloatingPanels mode, a sidebar with a width is sized by the shell and
+    /// gets a splitter on its inner edge, so pass content that fills it rather
+    /// than a `SidebarShell`, which sizes itself.
+    pub fn sidebar_left_width(mut self, width: impl Into<Pixels>) -> Self {
+        self.sidebar_left_width = Some(width.into());
+        self
+    }
+
+    /// Set the initial width of the right sidebar (Split and FloatingPanels modes).
+    ///
+    /// See [`Self::sidebar_left_width`].
+    pub fn sidebar_right_width(mut self, width: impl Into<Pixels>) -> Self {
+        self.sidebar_right_width = Some(width.into());
+        self
+    }
+
     /// Set the width of the splitter bar (Split mode only).
     pub fn splitter_width(mut self, width: impl Into<Pixels>) -> Self {
         self.splitter_width = width.into();
@@ -382,6 +686,95 @@ impl WindowShell {
         self
     }
 
+    /// Set preset widths the sidebar snaps to while resizing (Split mode only).
+    ///
+    /// A width within `threshold` of a snap point is replaced by the nearest
+    /// point before `on_split_resize` is called.
+    pub fn split_snap_points(mut self, points: &[Pixels], threshold: impl Into<Pixels>) -> Self {
+        self.split_snap_points = points.to_vec();
+        self.split_snap_threshold = threshold.into();
+        self
+    }
+
+    /// Set the narrowest sidebar width reachable by dragging the splitter
+    /// (Split mode only), default 120px.
+    pub fn split_min_width(mut self, width: impl Into<Pixels>) -> Self {
+        self.split_min_width = width.into();
+        self
+    }
+
+    /// Set the widest sidebar width reachable by dragging the splitter
+    /// (Split mode only), default 600px.
+    pub fn split_max_width(mut self, width: impl Into<Pixels>) -> Self {
+        self.split_max_width = width.into();
+        self
+    }
+
+    /// Set the callback for resizing the bottom panel (Split mode only).
+    ///
+    /// The callback receives the new panel height while the splitter between
+    /// main and the bottom panel is dragged, clamped to
+    /// `split_bottom_min_height`..=`split_bottom_max_height`.
+    pub fn on_split_bottom_resize(
+        mut self,
+        handler: impl Fn(Pixels, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_split_bottom_resize = Some(Rc::new(handler));
+        self
+    }
+
+    /// Set the lowest bottom panel height reachable by dragging its splitter
+    /// (Split mode only), default 80px.
+    pub fn split_bottom_min_height(mut self, height: impl Into<Pixels>) -> Self {
+        self.split_bottom_min_height = height.into();
+        self
+    }
+
+    /// Set the tallest bottom panel height reachable by dragging its splitter
+    /// (Split mode only), default 600px.
+    pub fn split_bottom_max_height(mut self, height: impl Into<Pixels>) -> Self {
+        self.split_bottom_max_height = height.into();
+        self
+    }
+
+    /// Set the callback for double-clicking a splitter (Split mode only).
+    ///
+    /// Double-clicking a splitter collapses the adjacent panel to zero, and a
+    /// second double-click restores its last expanded size. The callback
+    /// receives the panel's edge and whether it's now collapsed.
+    pub fn on_splitter_double_click(
+        mut self,
+        handler: impl Fn(Placement, bool, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_splitter_double_click = Some(Rc::new(handler));
+        self
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────────
+    // Panes mode configuration
+    // ─────────────────────────────────────────────────────────────────────────────
+
+    /// Set the pane tree rendered in Panes mode, falling back to `main` if unset.
+    ///
+    /// Splitters use `splitter_width` and `splitter_style`.
+    pub fn panes(mut self, panes: PaneTree) -> Self {
+        self.panes = Some(panes);
+        self
+    }
+
+    /// Set the callback for dragging a splitter between panes (Panes mode only).
+    ///
+    /// The callback receives the path of the resized pane, its child indices from
+    /// the root of the tree, and its new extent along the parent split. Store it
+    /// and pass it back with [`PaneTree::size`] on the next render.
+    pub fn on_pane_resize(
+        mut self,
+        handler: impl Fn(&[usize], Pixels, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_pane_resize = Some(Rc::new(handler));
+        self
+    }
+
     // ─────────────────────────────────────────────────────────────────────────────
     // Getters
     // ─────────────────────────────────────────────────────────────────────────────
@@ -410,9 +803,354 @@ impl WindowShell {
     // Layout rendering
     // ─────────────────────────────────────────────────────────────────────────────
 
+    /// Provide the floating inset to `content`, animating it when the maximized state changes.
+    fn inset_scope(
+        inset: Pixels,
+        inset_when_maximized: Option<Pixels>,
+        reduced_motion: bool,
+        content: impl IntoElement,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> AnyElement {
+        let maximized = window.is_maximized();
+        let target = if maximized {
+            inset_when_maximized.unwrap_or(inset)
+        } else {
+            inset
+        };
+
+        let transition = window.use_keyed_state("window-shell-inset-transition", cx, |_, _| {
+            InsetTransition {
+                maximized,
+                from: target,
+                to: target,
+                generation: 0,
+            }
+        });
+        let state = transition.update(cx, |state, _| {
+            if state.maximized != maximized {
+                state.maximized = maximized;
+                state.from = state.to;
+                state.generation += 1;
+            } else if state.to != target {
+                // Inset changed without a maximize toggle, apply it directly.
+                state.from = target;
+            }
+            state.to = target;
+            *state
+        });
+
+        let scope = FloatingInsetScope::new(target, content);
+        let reduced_motion = reduced_motion || GlobalState::global(cx).reduced_motion();
+        let animation = point_to_point_animation(&cx.theme().motion, reduced_motion);
+        match animation {
+            Some(animation) if state.from != state.to => scope
+                .with_animation(
+                    ElementId::NamedInteger("window-shell-inset".into(), state.generation),
+                    animation,
+                    move |scope, delta| scope.inset(state.from + (state.to - state.from) * delta),
+                )
+                .into_any_element(),
+            _ => scope.into_any_element(),
+        }
+    }
+
+    /// Resolve the corner radii to apply for `window_radius`, if any.
+    fn window_corner_radii(radius: Option<Pixels>, window: &Window) -> Option<Corners<Pixels>> {
+        let radius = radius.filter(|_| cfg!(not(target_os = "macos")))?;
+        if window.is_maximized() {
+            return None;
+        }
+
+        match window.window_decorations() {
+            Decorations::Server => None,
+            Decorations::Client { tiling } => {
+                let corner = |edge_a: bool, edge_b: bool| {
+                    if edge_a || edge_b { px(0.) } else { radius }
+                };
+                Some(Corners {
+                    top_left: corner(tiling.top, tiling.left),
+                    top_right: corner(tiling.top, tiling.right),
+                    bottom_left: corner(tiling.bottom, tiling.left),
+                    bottom_right: corner(tiling.bottom, tiling.right),
+                })
+            }
+        }
+    }
+
+    /// Resolve the outline color for `window_border`, `None` when no outline is drawn.
+    fn window_border_color(window: &Window, cx: &App) -> Option<Hsla> {
+        if cfg!(target_os = "macos")
+            || window.is_maximized()
+            || window.is_fullscreen()
+            || matches!(window.window_decorations(), Decorations::Server)
+        {
+            return None;
+        }
+
+        let elevation = &cx.theme().elevation;
+        let level = if window.is_window_active() {
+            elevation.active_window_level
+        } else {
+            elevation.inactive_window_level
+        };
+        let strength = level as f32 / elevation.active_window_level.max(1) as f32;
+        Some(cx.theme().border.opacity(strength.clamp(0., 1.)))
+    }
+
+    /// Render the title bar overlay.
+    ///
+    /// An auto-hidden title bar passes its reveal `presence`: it then gets an opaque
+    /// background, as it covers the content, and slides down from above the window.
+    fn render_title_bar_layer(
+        title_bar: TitleBar,
+        title_bar_height: Pixels,
+        top_corner_radii: Option<Corners<Pixels>>,
+        presence: Option<PresenceTransition>,
+        animate: bool,
+        cx: &App,
+    ) -> Option<AnyElement> {
+        if presence.is_some_and(|presence| !presence.should_render()) {
+            return None;
+        }
+
+        let layer = div()
+            .id("window-shell-titlebar")
+            .absolute()
+            .top_0()
+            .left_0()
+            .right_0()
+            .h(title_bar_height)
+            .when_some(top_corner_radii, round_corners)
+            .when(cfg!(not(target_os = "macos")), |el| el.occlude())
+            .when(presence.is_some(), |el| {
+                el.occlude().bg(cx.theme().title_bar)
+            })
+            .child(title_bar);
+
+        let Some(presence) = presence else {
+            return Some(layer.into_any_element());
+        };
+        let top = move |progress: f32| title_bar_height * (progress - 1.0);
+        Some(
+            match point_to_point_animation(&cx.theme().motion, !animate) {
+                Some(animation) if presence.transition_active() => layer
+                    .with_animation(
+                        ElementId::NamedInteger(
+                            "window-shell-titlebar-slide".into(),
+                            u64::from(matches!(presence.phase, PresencePhase::Entering)),
+                        ),
+                        animation,
+                        move |el, delta| el.top(top(presence.progress(delta))),
+                    )
+                    .into_any_element(),
+                _ => layer.top(top(presence.progress(1.0))).into_any_element(),
+            },
+        )
+    }
+
+    /// Track whether the auto-hidden title bar is revealed, from window-wide mouse moves.
+    ///
+    /// Reaching the top edge reveals it, leaving the title bar hides it again.
+    fn render_title_bar_reveal_tracker(
+        revealed: Entity<bool>,
+        title_bar_height: Pixels,
+    ) -> impl IntoElement {
+        canvas(
+            |_, _, _| {},
+            move |bounds, _, window, _| {
+                window.on_mouse_event(move |event: &MouseMoveEvent, phase, window, cx| {
+                    if !phase.bubble() {
+                        return;
+                    }
+
+                    let offset = event.position.y - bounds.top();
+                    let was_revealed = *revealed.read(cx);
+                    let reveal = if was_revealed {
+                        offset <= title_bar_height
+                    } else {
+                        offset <= TITLE_BAR_REVEAL_EDGE
+                    };
+                    if reveal != was_revealed {
+                        revealed.update(cx, |revealed, _| *revealed = reveal);
+                        window.refresh();
+                    }
+                });
+            },
+        )
+        .absolute()
+        .size_full()
+    }
+
+    /// End the splitter drag on a mouse up anywhere in the window, not only over
+    /// the shell's hitbox.
+    fn render_split_drag_end_tracker(split_drag: Entity<SplitDragState>) -> impl IntoElement {
+        canvas(
+            |_, _, _| {},
+            move |_, _, window, _| {
+                window.on_mouse_event(move |_: &MouseUpEvent, phase, _, cx| {
+                    if phase.bubble() {
+                        split_drag.update(cx, |state, cx| {
+                            state.drag = None;
+                            cx.notify();
+                        });
+                    }
+                });
+            },
+        )
+        .absolute()
+        .size_0()
+    }
+
+    /// Render the loading bar for `progress`, see [`WindowShell::progress`].
+    fn render_progress(
+        progress: Option<f32>,
+        title_bar_height: Pixels,
+        reduced_motion: bool,
+        cx: &App,
+    ) -> AnyElement {
+        let bar = div()
+            .id("window-shell-progress")
+            .absolute()
+            .top(title_bar_height)
+            .left_0()
+            .right_0()
+            .h(PROGRESS_BAR_HEIGHT)
+            .overflow_hidden();
+
+        if let Some(fraction) = progress {
+            return bar
+                .child(
+                    Progress::new("window-shell-progress-bar")
+                        .value(fraction.clamp(0., 1.) * 100.)
+                        .with_size(PROGRESS_BAR_HEIGHT)
+                        .rounded_none(),
+                )
+                .into_any_element();
+        }
+
+        let color = cx.theme().progress_bar;
+        let reduced_motion = reduced_motion || GlobalState::global(cx).reduced_motion();
+        if reduced_motion {
+            return bar.bg(color.opacity(0.5)).into_any_element();
+        }
+
+        let motion = &cx.theme().motion;
+        let animation = animation_with_theme_easing(
+            Animation::new(Duration::from_millis(
+                u64::from(motion.slow_duration_ms) * 3,
+            )),
+            &motion.point_to_point_easing,
+        )
+        .repeat();
+        bar.bg(color.opacity(0.2))
+            .child(
+                div()
+                    .absolute()
+                    .top_0()
+                    .h_full()
+                    .w(relative(INDETERMINATE_SEGMENT))
+                    .bg(color)
+                    .with_animation("window-shell-progress-sweep", animation, |el, delta| {
+                        el.left(relative(
+                            (1. + INDETERMINATE_SEGMENT) * delta - INDETERMINATE_SEGMENT,
+                        ))
+                    }),
+            )
+            .into_any_element()
+    }
+
+    /// Render the layer blocking interaction, see [`WindowShell::interaction_enabled`].
+    fn render_busy_overlay(
+        presence: PresenceTransition,
+        blocking: bool,
+        spinner: bool,
+        title_bar_height: Pixels,
+        animate: bool,
+        cx: &App,
+    ) -> AnyElement {
+        let overlay = div()
+            .id("window-shell-busy")
+            .absolute()
+            .top(title_bar_height)
+            .left_0()
+            .right_0()
+            .bottom_0()
+            .flex()
+            .items_center()
+            .justify_center()
+            // Stop blocking as soon as interaction is re-enabled, while fading out
+            .when(blocking, |el| el.occlude())
+            .when(spinner, |el| el.child(Spinner::new().large()));
+
+        match fade_animation(&cx.theme().motion, !animate) {
+            Some(animation) if presence.transition_active() => overlay
+                .with_animation(
+                    ElementId::NamedInteger(
+                        "window-shell-busy-fade".into(),
+                        u64::from(matches!(presence.phase, PresencePhase::Entering)),
+                    ),
+                    animation,
+                    move |el, delta| el.opacity(presence.progress(delta)),
+                )
+                .into_any_element(),
+            _ => overlay.into_any_element(),
+        }
+    }
+
+    /// Wrap a sidebar in context scopes for its blur and elevation overrides.
+    fn scope_sidebar(
+        sidebar: Option<AnyElement>,
+        blur: Option<bool>,
+        elevation: Option<ElevationToken>,
+    ) -> Option<AnyElement> {
+        sidebar.map(|sidebar| {
+            let sidebar = match elevation {
+                Some(elevation) => ElevationScope::new(elevation, sidebar).into_any_element(),
+                None => sidebar,
+            };
+            match blur {
+                Some(enabled) => BlurEnabledScope::new(enabled, sidebar).into_any_element(),
+                None => sidebar,
+            }
+        })
+    }
+
+    /// Stack the top panel, main and the bottom panel in the column between
+    /// the sidebars.
+    fn render_main_column(
+        sidebar_top: Option<AnyElement>,
+        main: Option<AnyElement>,
+        sidebar_bottom: Option<AnyElement>,
+    ) -> Option<AnyElement> {
+        if sidebar_top.is_none() && sidebar_bottom.is_none() {
+            return main.map(|main| div().flex_1().size_full().child(main).into_any_element());
+        }
+
+        Some(
+            div()
+                .id("window-shell-main-column")
+                .flex_1()
+                .h_full()
+                .min_w_0()
+                .flex()
+                .flex_col()
+                .when_some(sidebar_top, |el, panel| {
+                    el.child(div().flex_shrink_0().w_full().child(panel))
+                })
+                .child(div().flex_1().min_h_0().w_full().children(main))
+                .when_some(sidebar_bottom, |el, panel| {
+                    el.child(div().flex_shrink_0().w_full().child(panel))
+                })
+                .into_any_element(),
+        )
+    }
+
     fn render_standard_layout(
         sidebar_left: Option<AnyElement>,
         sidebar_right: Option<AnyElement>,
+        sidebar_top: Option<AnyElement>,
+        sidebar_bottom: Option<AnyElement>,
         main: Option<AnyElement>,
         title_bar_height: Pixels,
     ) -> impl IntoElement {
@@ -426,20 +1164,66 @@ impl WindowShell {
             .flex()
             .flex_row()
             .when_some(sidebar_left, |el, sidebar| el.child(sidebar))
-            .when_some(main, |el, main| {
-                el.child(div().flex_1().size_full().child(main))
-            })
+            .children(Self::render_main_column(sidebar_top, main, sidebar_bottom))
             .when_some(sidebar_right, |el, sidebar| el.child(sidebar))
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn render_floating_panels_layout(
         sidebar_left: Option<AnyElement>,
         sidebar_right: Option<AnyElement>,
         main: Option<AnyElement>,
         _title_bar_height: Pixels,
+        splitter: PaneSplitterStyle,
+        sidebar_widths: (Option<Pixels>, Option<Pixels>),
+        split_drag: Entity<SplitDragState>,
+        on_double_click: Option<SplitterDoubleClickHandler>,
+        animate: bool,
+        cx: &App,
     ) -> impl IntoElement {
+        let state = split_drag.read(cx);
+        let (left, right) = (state.left, state.right);
         // In FloatingPanels mode, sidebars are expected to be SidebarShell instances
-        // which handle their own absolute positioning and insets.
+        // which handle their own absolute positioning and insets. A sidebar with a
+        // width is sized by the shell instead, with a splitter as in Split mode.
+        let sized_sidebar = |edge: Placement,
+                             sidebar: AnyElement,
+                             panel: SplitPanelSize,
+                             width: Option<Pixels>| {
+            let Some(width) = width else {
+                return sidebar;
+            };
+            let sidebar = Self::measure_split_sidebar(
+                edge,
+                sidebar,
+                panel,
+                Some(width),
+                point_to_point_animation(&cx.theme().motion, !animate),
+                split_drag.clone(),
+            );
+            let splitter =
+                Self::render_splitter(edge, &splitter, split_drag.clone(), on_double_click.clone());
+
+            div()
+                .absolute()
+                .top_0()
+                .bottom_0()
+                .flex()
+                .flex_row()
+                .map(|el| {
+                    if edge == Placement::Left {
+                        el.left_0().child(sidebar).child(splitter)
+                    } else {
+                        el.right_0().child(splitter).child(sidebar)
+                    }
+                })
+                .into_any_element()
+        };
+        let sidebar_left = sidebar_left
+            .map(|sidebar| sized_sidebar(Placement::Left, sidebar, left, sidebar_widths.0));
+        let sidebar_right = sidebar_right
+            .map(|sidebar| sized_sidebar(Placement::Right, sidebar, right, sidebar_widths.1));
+
         div()
             .id("window-shell-floating-layout")
             .absolute()
@@ -498,14 +1282,42 @@ impl WindowShell {
     fn render_split_layout(
         sidebar_left: Option<AnyElement>,
         sidebar_right: Option<AnyElement>,
+        sidebar_top: Option<AnyElement>,
+        sidebar_bottom: Option<AnyElement>,
         main: Option<AnyElement>,
         title_bar_height: Pixels,
-        splitter_width: Pixels,
-        splitter_style: StyleRefinement,
-        on_split_resize: Option<Rc<dyn Fn(Pixels, &mut Window, &mut App)>>,
+        splitter: PaneSplitterStyle,
+        sidebar_widths: (Option<Pixels>, Option<Pixels>),
+        split_drag: Entity<SplitDragState>,
+        on_double_click: Option<SplitterDoubleClickHandler>,
+        animate: bool,
         cx: &App,
     ) -> impl IntoElement {
-        let splitter_hover_bg = cx.theme().border;
+        let state = split_drag.read(cx);
+        let (left, right, bottom) = (state.left, state.right, state.bottom);
+        let animation = || point_to_point_animation(&cx.theme().motion, !animate);
+        let sidebar_bottom = sidebar_bottom.map(|panel| {
+            div()
+                .flex_shrink_0()
+                .w_full()
+                .flex()
+                .flex_col()
+                .child(Self::render_splitter(
+                    Placement::Bottom,
+                    &splitter,
+                    split_drag.clone(),
+                    on_double_click.clone(),
+                ))
+                .child(Self::measure_split_sidebar(
+                    Placement::Bottom,
+                    panel,
+                    bottom,
+                    None,
+                    animation(),
+                    split_drag.clone(),
+                ))
+                .into_any_element()
+        });
 
         div()
             .id("window-shell-split-layout")
@@ -517,50 +1329,337 @@ impl WindowShell {
             .flex()
             .flex_row()
             .when_some(sidebar_left, |el, sidebar| {
-                el.child(sidebar).child(Self::render_splitter(
-                    "left",
-                    splitter_width,
-                    splitter_style.clone(),
-                    splitter_hover_bg,
-                    on_split_resize.clone(),
+                el.child(Self::measure_split_sidebar(
+                    Placement::Left,
+                    sidebar,
+                    left,
+                    sidebar_widths.0,
+                    animation(),
+                    split_drag.clone(),
+                ))
+                .child(Self::render_splitter(
+                    Placement::Left,
+                    &splitter,
+                    split_drag.clone(),
+                    on_double_click.clone(),
                 ))
             })
-            .when_some(main, |el, main| {
-                el.child(div().flex_1().size_full().child(main))
-            })
+            .children(Self::render_main_column(sidebar_top, main, sidebar_bottom))
             .when_some(sidebar_right, |el, sidebar| {
                 el.child(Self::render_splitter(
-                    "right",
-                    splitter_width,
-                    splitter_style,
-                    splitter_hover_bg,
-                    on_split_resize,
+                    Placement::Right,
+                    &splitter,
+                    split_drag.clone(),
+                    on_double_click.clone(),
+                ))
+                .child(Self::measure_split_sidebar(
+                    Placement::Right,
+                    sidebar,
+                    right,
+                    sidebar_widths.1,
+                    animation(),
+                    split_drag.clone(),
                 ))
-                .child(sidebar)
             })
     }
 
+    fn render_panes_layout(
+        panes: Option<PaneTree>,
+        main: Option<AnyElement>,
+        title_bar_height: Pixels,
+        splitter: PaneSplitterStyle,
+        on_pane_resize: Option<PaneResizeHandler>,
+    ) -> impl IntoElement {
+        div()
+            .id("window-shell-panes-layout")
+            .absolute()
+            .top(title_bar_height)
+            .left_0()
+            .right_0()
+            .bottom_0()
+            .flex()
+            .map(|el| match panes {
+                Some(panes) => el.child(panes.render(Vec::new(), &splitter, on_pane_resize)),
+                None => el.children(main),
+            })
+    }
+
+    /// Wrap a Split mode sidebar to size it and record its laid out size, the
+    /// starting point of a splitter drag.
+    ///
+    /// The size animates from the one before the last collapse toggle.
+    fn measure_split_sidebar(
+        edge: Placement,
+        sidebar: AnyElement,
+        panel: SplitPanelSize,
+        initial_size: Option<Pixels>,
+        animation: Option<Animation>,
+        split_drag: Entity<SplitDragState>,
+    ) -> AnyElement {
+        let size = panel.dragged.or(initial_size);
+        let sized = move |el: Div, size: Pixels| {
+            if edge.is_horizontal() {
+                el.w(size)
+            } else {
+                el.h(size)
+            }
+        };
+
+        let el = div()
+            .relative()
+            .flex_shrink_0()
+            .map(|el| {
+                if edge.is_horizontal() {
+                    el.h_full()
+                } else {
+                    el.w_full()
+                }
+            })
+            .when_some(size, |el, size| sized(el, size).overflow_hidden())
+            .child(sidebar)
+            .child(
+                canvas(
+                    move |bounds, _, cx| {
+                        split_drag.update(cx, |state, _| {
+                            state.panel_mut(edge).laid_out = if edge.is_horizontal() {
+                                bounds.size.width
+                            } else {
+                                bounds.size.height
+                            };
+                        })
+                    },
+                    |_, _, _, _| {},
+                )
+                .absolute()
+                .size_full(),
+            );
+
+        match (animation, size, panel.toggled_from) {
+            (Some(animation), Some(size), Some(from)) => el
+                .with_animation(
+                    ElementId::NamedInteger(
+                        format!("window-shell-split-{}", splitter_key(edge)).into(),
+                        panel.toggles,
+                    ),
+                    animation,
+                    move |el, delta| sized(el, from + (size - from) * delta),
+                )
+                .into_any_element(),
+            _ => el.into_any_element(),
+        }
+    }
+
     fn render_splitter(
-        id: &'static str,
-        width: Pixels,
-        style: StyleRefinement,
-        hover_bg: Hsla,
-        _on_resize: Option<Rc<dyn Fn(Pixels, &mut Window, &mut App)>>,
+        edge: Placement,
+        splitter: &PaneSplitterStyle,
+        split_drag: Entity<SplitDragState>,
+        on_double_click: Option<SplitterDoubleClickHandler>,
     ) -> impl IntoElement {
+        let (width, hover_bg) = (splitter.width, splitter.hover_bg);
+
         div()
-            .id(format!("window-shell-splitter-{}", id))
-            .w(width)
-            .h_full()
+            .id(format!("window-shell-splitter-{}", splitter_key(edge)))
             .flex_shrink_0()
-            .cursor_col_resize()
+            .map(|el| {
+                if edge.is_horizontal() {
+                    el.w(width).h_full().cursor_col_resize()
+                } else {
+                    el.h(width).w_full().cursor_row_resize()
+                }
+            })
             .bg(transparent_black())
             .hover(move |s| s.bg(hover_bg))
-            .refine_style(&style)
-        // Note: Actual drag handling requires consumer to use on_mouse_move at root level
-        // or implement via on_drag. The splitter is styled and ready for interaction.
+            .refine_style(&splitter.style)
+            .on_mouse_down(
+                MouseButton::Left,
+                move |event: &MouseDownEvent, window, cx| {
+                    cx.stop_propagation();
+                    let collapsed = split_drag.update(cx, |state, _| {
+                        state.panel_mut(edge).mouse_down(event.click_count)
+                    });
+                    if let Some(collapsed) = collapsed {
+                        split_drag.update(cx, |state, cx| {
+                            state.drag = None;
+                            cx.notify();
+                        });
+                        if let Some(on_double_click) = &on_double_click {
+                            on_double_click(edge, collapsed, window, cx);
+                        }
+                        return;
+                    }
+
+                    split_drag.update(cx, |state, cx| {
+                        let start = if edge.is_horizontal() {
+                            event.position.x
+                        } else {
+                            event.position.y
+                        };
+                        let start_size = state.panel_mut(edge).laid_out;
+                        state.drag = Some(SplitDrag {
+                            edge,
+                            start,
+                            start_size,
+                        });
+                        cx.notify();
+                    });
+                },
+            )
+    }
+}
+
+/// Callback for a double-clicked splitter, see [`WindowShell::on_splitter_double_click`].
+type SplitterDoubleClickHandler = Rc<dyn Fn(Placement, bool, &mut Window, &mut App)>;
+
+/// Returns the element id suffix for the splitter of the panel at `edge`.
+fn splitter_key(edge: Placement) -> &'static str {
+    match edge {
+        Placement::Left => "left",
+        Placement::Right => "right",
+        Placement::Top => "top",
+        Placement::Bottom => "bottom",
+    }
+}
+
+/// Apply per-corner radii to `el`.
+fn round_corners<E: Styled>(el: E, radii: Corners<Pixels>) -> E {
+    el.rounded_tl(radii.top_left)
+        .rounded_tr(radii.top_right)
+        .rounded_bl(radii.bottom_left)
+        .rounded_br(radii.bottom_right)
+}
+
+/// Returns the snap point nearest to `width` if it lies within `threshold`,
+/// otherwise `width` unchanged.
+fn snap_width(width: Pixels, snap_points: &[Pixels], threshold: Pixels) -> Pixels {
+    snap_points
+        .iter()
+        .copied()
+        .map(|point| (point, (point - width).abs()))
+        .filter(|(_, distance)| *distance <= threshold)
+        .min_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
+        .map_or(width, |(point, _)| point)
+}
+
+/// Splitter drag state of Split mode, keyed per window.
+#[derive(Default)]
+struct SplitDragState {
+    drag: Option<SplitDrag>,
+    left: SplitPanelSize,
+    right: SplitPanelSize,
+    bottom: SplitPanelSize,
+}
+
+impl SplitDragState {
+    fn panel_mut(&mut self, edge: Placement) -> &mut SplitPanelSize {
+        match edge {
+            Placement::Left => &mut self.left,
+            Placement::Right => &mut self.right,
+            // Only the bottom panel has a splitter
+            Placement::Top | Placement::Bottom => &mut self.bottom,
+        }
+    }
+}
+
+/// Size of a Split mode panel along its splitter's axis.
+#[derive(Default, Clone, Copy)]
+struct SplitPanelSize {
+    /// Size from the last layout.
+    laid_out: Pixels,
+    /// Initial size passed to the shell, see [`WindowShell::sidebar_left_width`].
+    initial: Option<Pixels>,
+    /// Size set by dragging or double-clicking the splitter, overriding the
+    /// initial size.
+    dragged: Option<Pixels>,
+    /// Size to restore on the next double-click while collapsed.
+    restore: Option<Pixels>,
+    /// Size before the last collapse toggle, animated from.
+    toggled_from: Option<Pixels>,
+    /// Number of collapse toggles, keying the animation.
+    toggles: u64,
+}
+
+impl SplitPanelSize {
+    /// Handle a mouse down on the splitter. A double-click toggles the collapse and
+    /// returns whether the panel is now collapsed, any other click returns `None`
+    /// to start a drag.
+    ///
+    /// The first click of a double-click lands here too, so it leaves the size to
+    /// restore alone, see [`Self::drag_to`].
+    fn mouse_down(&mut self, click_count: usize) -> Option<bool> {
+        (click_count >= 2).then(|| self.toggle_collapsed())
+    }
+
+    /// Set the initial size passed to the shell. A different one replaces any
+    /// dragged or collapsed size, so the consumer stays in control of the size.
+    fn set_initial(&mut self, initial: Option<Pixels>) {
+        if self.initial != initial {
+            self.initial = initial;
+            self.dragged = None;
+            self.restore = None;
+        }
+    }
+
+    /// Set the size from a splitter drag that moved. Dragging out of a collapsed
+    /// panel starts a new size, so there is nothing left to restore.
+    fn drag_to(&mut self, size: Pixels) {
+        self.dragged = Some(size);
+        self.restore = None;
+    }
+
+    /// Collapse the panel to zero, or restore its last expanded size when
+    /// collapsed. Returns whether the panel is now collapsed.
+    fn toggle_collapsed(&mut self) -> bool {
+        let current = self.dragged.unwrap_or(self.laid_out);
+        self.toggled_from = Some(current);
+        self.toggles += 1;
+
+        match self.restore.take() {
+            Some(restore) => {
+                self.dragged = Some(restore);
+                false
+            }
+            None => {
+                self.restore = Some(current);
+                self.dragged = Some(px(0.));
+                true
+            }
+        }
+    }
+}
+
+/// A splitter drag in progress.
+#[derive(Clone, Copy)]
+struct SplitDrag {
+    /// Window edge of the panel being resized.
+    edge: Placement,
+    /// Mouse position along the drag axis at the start of the drag.
+    start: Pixels,
+    /// Panel size at the start of the drag.
+    start_size: Pixels,
+}
+
+impl SplitDrag {
+    /// Returns the panel size for the mouse at `position`, clamped to `min..=max`.
+    fn size(&self, position: Point<Pixels>, min: Pixels, max: Pixels) -> Pixels {
+        let size = match self.edge {
+            Placement::Left => self.start_size + (position.x - self.start),
+            Placement::Right => self.start_size - (position.x - self.start),
+            Placement::Top => self.start_size + (position.y - self.start),
+            Placement::Bottom => self.start_size - (position.y - self.start),
+        };
+        size.min(max).max(min)
     }
 }
 
+/// Inset animation state across maximize toggles.
+#[derive(Clone, Copy)]
+struct InsetTransition {
+    maximized: bool,
+    from: Pixels,
+    to: Pixels,
+    generation: u64,
+}
+
 impl Styled for WindowShell {
     fn style(&mut self) -> &mut StyleRefinement {
         &mut self.style
@@ -568,7 +1667,7 @@ impl Styled for WindowShell {
 }
 
 impl RenderOnce for WindowShell {
-    fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
         let title_bar_height = self.title_bar_height;
         let titlebar_bg = cx.theme().transparent;
 
@@ -594,41 +1693,148 @@ impl RenderOnce for WindowShell {
             title_bar = override_fn(title_bar);
         }
 
+        let present_regions = WindowRegion::ALL
+            .into_iter()
+            .filter(|region| match region {
+                WindowRegion::LeftSidebar => self.sidebar_left.is_some(),
+                WindowRegion::Main => self.main.is_some() || self.panes.is_some(),
+                WindowRegion::RightSidebar => self.sidebar_right.is_some(),
+            })
+            .collect::<Rc<[_]>>();
+
+        // Scope per-sidebar overrides; the shell-wide scopes below remain the fallback
+        let sidebar_left = Self::scope_sidebar(
+            self.sidebar_left,
+            self.sidebar_left_blur,
+            self.sidebar_left_elevation,
+        );
+        let sidebar_right = Self::scope_sidebar(
+            self.sidebar_right,
+            self.sidebar_right_blur,
+            self.sidebar_right_elevation,
+        );
+
+        let animate = !self.reduced_motion && cx.should_animate();
+        let fade_duration = Duration::from_millis(u64::from(cx.theme().motion.fade_duration_ms));
+        let busy = keyed_presence(
+            "window-shell-busy".into(),
+            !self.interaction_enabled,
+            animate,
+            fade_duration,
+            fade_duration,
+            PresenceOptions::default(),
+            window,
+            cx,
+        );
+
+        // An auto-hidden title bar gives its height to the content
+        let auto_hide_title_bar = self.auto_hide_title_bar && window.is_fullscreen();
+        let content_top = if auto_hide_title_bar {
+            px(0.)
+        } else {
+            title_bar_height
+        };
+        let title_bar_revealed = auto_hide_title_bar
+            .then(|| window.use_keyed_state("window-shell-title-bar-revealed", cx, |_, _| false));
+        let title_bar_presence = title_bar_revealed.as_ref().map(|revealed| {
+            let slide_duration =
+                Duration::from_millis(u64::from(cx.theme().motion.fast_duration_ms));
+            keyed_presence(
+                "window-shell-title-bar-reveal".into(),
+                *revealed.read(cx),
+                animate,
+                slide_duration,
+                slide_duration,
+                PresenceOptions::default(),
+                window,
+                cx,
+            )
+        });
+
+        let split_drag = window.use_keyed_state("window-shell-split-drag", cx, |_, _| {
+            SplitDragState::default()
+        });
+        split_drag.update(cx, |state, _| {
+            state.left.set_initial(self.sidebar_left_width);
+            state.right.set_initial(self.sidebar_right_width);
+        });
+        let split_dragging = matches!(
+            self.layout_mode,
+            WindowLayoutMode::Split | WindowLayoutMode::FloatingPanels
+        ) && split_drag.read(cx).drag.is_some();
+        let on_split_resize = self.on_split_resize;
+        let (split_snap_points, split_snap_threshold) =
+            (self.split_snap_points, self.split_snap_threshold);
+        let on_split_bottom_resize = self.on_split_bottom_resize;
+        let (split_min_width, split_max_width) = (self.split_min_width, self.split_max_width);
+        let (split_bottom_min_height, split_bottom_max_height) =
+            (self.split_bottom_min_height, self.split_bottom_max_height);
+
         // Build layout based on mode
         let content_layer = match self.layout_mode {
             WindowLayoutMode::Standard => Self::render_standard_layout(
-                self.sidebar_left,
-                self.sidebar_right,
+                sidebar_left,
+                sidebar_right,
+                self.sidebar_top,
+                self.sidebar_bottom,
                 self.main,
-                title_bar_height,
+                content_top,
             )
             .into_any_element(),
 
             WindowLayoutMode::FloatingPanels => Self::render_floating_panels_layout(
-                self.sidebar_left,
-                self.sidebar_right,
+                sidebar_left,
+                sidebar_right,
                 self.main,
-                title_bar_height,
+                content_top,
+                PaneSplitterStyle {
+                    width: self.splitter_width,
+                    style: self.splitter_style,
+                    hover_bg: cx.theme().border,
+                },
+                (self.sidebar_left_width, self.sidebar_right_width),
+                split_drag.clone(),
+                self.on_splitter_double_click,
+                animate,
+                cx,
             )
             .into_any_element(),
 
-            WindowLayoutMode::Overlay => Self::render_overlay_layout(
-                self.sidebar_left,
-                self.sidebar_right,
+            WindowLayoutMode::Overlay => {
+                Self::render_overlay_layout(sidebar_left, sidebar_right, self.main, content_top)
+                    .into_any_element()
+            }
+
+            WindowLayoutMode::Split => Self::render_split_layout(
+                sidebar_left,
+                sidebar_right,
+                self.sidebar_top,
+                self.sidebar_bottom,
                 self.main,
-                title_bar_height,
+                content_top,
+                PaneSplitterStyle {
+                    width: self.splitter_width,
+                    style: self.splitter_style,
+                    hover_bg: cx.theme().border,
+                },
+                (self.sidebar_left_width, self.sidebar_right_width),
+                split_drag.clone(),
+                self.on_splitter_double_click,
+                animate,
+                cx,
             )
             .into_any_element(),
 
-            WindowLayoutMode::Split => Self::render_split_layout(
-                self.sidebar_left,
-                self.sidebar_right,
+            WindowLayoutMode::Panes => Self::render_panes_layout(
+                self.panes,
                 self.main,
-                title_bar_height,
-                self.splitter_width,
-                self.splitter_style,
-                self.on_split_resize,
-                cx,
+                content_top,
+                PaneSplitterStyle {
+                    width: self.splitter_width,
+                    style: self.splitter_style,
+                    hover_bg: cx.theme().border,
+                },
+                self.on_pane_resize,
             )
             .into_any_element(),
         };
@@ -636,17 +1842,134 @@ impl RenderOnce for WindowShell {
         // Wrap content layer with blur and reduced motion context so child components can inherit
         let content_layer = BlurEnabledScope::new(self.blur_enabled, content_layer);
         let content_layer = ReducedMotionScope::new(self.reduced_motion, content_layer);
-        let content_layer = FloatingInsetScope::new(self.inset, content_layer);
+        let content_layer = Self::inset_scope(
+            self.inset,
+            self.inset_when_maximized,
+            self.reduced_motion,
+            content_layer,
+            window,
+            cx,
+        );
 
         // Clone handlers for use in closures
         let on_mouse_move = self.on_mouse_move.clone();
         let on_mouse_up = self.on_mouse_up.clone();
+        // Col-resize everywhere while the splitter is dragged, even past its bounds
+        let split_cursor = split_drag.read(cx).drag.map(|drag| {
+            if drag.edge.is_vertical() {
+                CursorStyle::ResizeUpDown
+            } else {
+                CursorStyle::ResizeLeftRight
+            }
+        });
+        let force_cursor = self
+            .force_cursor
+            .or(split_cursor.filter(|_| split_dragging));
+        let region_focus = self.region_focus.clone();
+
+        // Content masks are rectangular, so every full-bleed layer is rounded as well
+        let corner_radii = Self::window_corner_radii(self.window_radius, window);
+        let top_corner_radii = corner_radii.map(|radii| Corners {
+            bottom_left: px(0.),
+            bottom_right: px(0.),
+            ..radii
+        });
+        // Activation changes refresh the window, so this follows focus
+        let window_border = self
+            .window_border
+            .then(|| Self::window_border_color(window, cx))
+            .flatten();
 
         div()
             .id("window-shell")
             .size_full()
             .relative()
+            .when_some(corner_radii, |el, radii| {
+                round_corners(el, radii).overflow_hidden()
+            })
             .refine_style(&self.style)
+            // Keyboard focus switching between regions
+            .key_context(focus_region::CONTEXT)
+            .when(
+                region_focus.get(WindowRegion::LeftSidebar).is_some(),
+                |el| {
+                    let region_focus = region_focus.clone();
+                    el.on_action(move |_: &FocusLeftSidebar, window, cx| {
+                        region_focus.focus(WindowRegion::LeftSidebar, window, cx);
+                    })
+                },
+            )
+            .when(region_focus.get(WindowRegion::Main).is_some(), |el| {
+                let region_focus = region_focus.clone();
+                el.on_action(move |_: &FocusMain, window, cx| {
+                    region_focus.focus(WindowRegion::Main, window, cx);
+                })
+            })
+            .when(
+                region_focus.get(WindowRegion::RightSidebar).is_some(),
+                |el| {
+                    let region_focus = region_focus.clone();
+                    el.on_action(move |_: &FocusRightSidebar, window, cx| {
+                        region_focus.focus(WindowRegion::RightSidebar, window, cx);
+                    })
+                },
+            )
+            .on_action({
+                let region_focus = region_focus.clone();
+                let present_regions = present_regions.clone();
+                move |_: &FocusNextRegion, window, cx| {
+                    region_focus.cycle(&present_regions, true, window, cx);
+                }
+            })
+            .on_action({
+                let region_focus = region_focus.clone();
+                move |_: &FocusPreviousRegion, window, cx| {
+                    region_focus.cycle(&present_regions, false, window, cx);
+                }
+            })
+            // Splitter drag, tracked at the root so it follows the mouse past the bar
+            .when(split_dragging, |el| {
+                let split_drag_move = split_drag.clone();
+                let split_drag_up = split_drag.clone();
+                el.on_mouse_move(move |event, window, cx| {
+                    let Some(drag) = split_drag_move.read(cx).drag else {
+                        return;
+                    };
+                    let size = if drag.edge.is_vertical() {
+                        drag.size(
+                            event.position,
+                            split_bottom_min_height,
+                            split_bottom_max_height,
+                        )
+                    } else {
+                        let width = drag.size(event.position, split_min_width, split_max_width);
+                        snap_width(width, &split_snap_points, split_snap_threshold)
+                    };
+                    split_drag_move.update(cx, |state, cx| {
+                        state.panel_mut(drag.edge).drag_to(size);
+                        cx.notify();
+                    });
+
+                    match drag.edge {
+                        Placement::Left | Placement::Right => {
+                            if let Some(handler) = &on_split_resize {
+                                let side = if drag.edge == Placement::Left {
+                                    Side::Left
+                                } else {
+                                    Side::Right
+                                };
+                                handler(side, size, window, cx);
+                            }
+                        }
+                        Placement::Top | Placement::Bottom => {
+                            if let Some(handler) = &on_split_bottom_resize {
+                                handler(size, window, cx);
+                            }
+                        }
+                    }
+                })
+                .child(Self::render_split_drag_end_tracker(split_drag_up))
+            })
             // Mouse event forwarding
             .when_some(on_mouse_move, |el, handler| {
                 el.on_mouse_move(move |event, window, cx| {
@@ -665,22 +1988,37 @@ impl RenderOnce for WindowShell {
                         .id("window-shell-background")
                         .absolute()
                         .inset_0()
+                        .when_some(corner_radii, |el, radii| {
+                            round_corners(el, radii).overflow_hidden()
+                        })
                         .child(bg),
                 )
             })
             // Title bar background strip
-            .child(
-                div()
-                    .id("window-shell-titlebar-bg")
-                    .absolute()
-                    .top_0()
-                    .left_0()
-                    .right_0()
-                    .h(title_bar_height)
-                    .bg(titlebar_bg),
-            )
+            .when(!auto_hide_title_bar, |el| {
+                el.child(
+                    div()
+                        .id("window-shell-titlebar-bg")
+                        .absolute()
+                        .top_0()
+                        .left_0()
+                        .right_0()
+                        .h(title_bar_height)
+                        .when_some(top_corner_radii, round_corners)
+                        .bg(titlebar_bg),
+                )
+            })
             // Content layer
             .child(content_layer)
+            // Loading bar, above content but below overlays
+            .when_some(self.progress, |el, progress| {
+                el.child(Self::render_progress(
+                    progress,
+                    content_top,
+                    self.reduced_motion,
+                    cx,
+                ))
+            })
             // Overlay children
             .when_some(self.overlay_children, |el, overlay| {
                 el.child(
@@ -691,17 +2029,164 @@ impl RenderOnce for WindowShell {
                         .child(overlay),
                 )
             })
+            // Busy layer, over content and overlays but below the title bar
+            .when(busy.should_render(), |el| {
+                el.child(Self::render_busy_overlay(
+                    busy,
+                    !self.interaction_enabled,
+                    self.busy_spinner,
+                    content_top,
+                    animate,
+                    cx,
+                ))
+            })
             // Title bar overlay
-            .child(
-                div()
-                    .id("window-shell-titlebar")
+            .children(Self::render_title_bar_layer(
+                title_bar,
+                title_bar_height,
+                top_corner_radii,
+                title_bar_presence,
+                animate,
+                cx,
+            ))
+            .when_some(title_bar_revealed, |el, revealed| {
+                el.child(Self::render_title_bar_reveal_tracker(
+                    revealed,
+                    title_bar_height,
+                ))
+            })
+            // Window outline, above everything and transparent to the mouse
+            .when_some(window_border, |el, color| {
+                el.child(
+                    div()
+                        .absolute()
+                        .inset_0()
+                        .when_some(corner_radii, round_corners)
+                        .border_1()
+                        .border_color(color),
+                )
+            })
+            // Forced cursor, painted last so it wins over any hovered child
+            .when_some(force_cursor, |el, cursor| {
+                el.child(
+                    canvas(
+                        |bounds, window, _| window.insert_hitbox(bounds, HitboxBehavior::Normal),
+                        move |_, hitbox, window, _| window.set_cursor_style(cursor, &hitbox),
+                    )
                     .absolute()
-                    .top_0()
-                    .left_0()
-                    .right_0()
-                    .h(title_bar_height)
-                    .when(cfg!(not(target_os = "macos")), |el| el.occlude())
-                    .child(title_bar),
-            )
+                    .size_full(),
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SplitDrag, SplitPanelSize, snap_width};
+    use crate::Placement;
+    use gpui::{point, px};
+
+    #[test]
+    fn test_snap_width() {
+        let points = [px(240.), px(320.), px(400.)];
+
+        assert_eq!(snap_width(px(236.), &points, px(8.)), px(240.));
+        assert_eq!(snap_width(px(327.), &points, px(8.)), px(320.));
+        assert_eq!(snap_width(px(280.), &points, px(8.)), px(280.));
+        assert_eq!(snap_width(px(236.), &points, px(0.)), px(236.));
+        assert_eq!(snap_width(px(300.), &[], px(8.)), px(300.));
+    }
+
+    #[test]
+    fn test_split_drag_size() {
+        let at = |x: f32, y: f32| point(px(x), px(y));
+
+        let left = SplitDrag {
+            edge: Placement::Left,
+            start: px(200.),
+            start_size: px(200.),
+        };
+        assert_eq!(left.size(at(260., 0.), px(120.), px(600.)), px(260.));
+        assert_eq!(left.size(at(20., 0.), px(120.), px(600.)), px(120.));
+        assert_eq!(left.size(at(900., 0.), px(120.), px(600.)), px(600.));
+
+        // Right sidebars grow as the splitter moves left
+        let right = SplitDrag {
+            edge: Placement::Right,
+            start: px(800.),
+            start_size: px(240.),
+        };
+        assert_eq!(right.size(at(760., 0.), px(120.), px(600.)), px(280.));
+        assert_eq!(right.size(at(1000., 0.), px(120.), px(600.)), px(120.));
+
+        // Bottom panels grow as the splitter moves up, ignoring x
+        let bottom = SplitDrag {
+            edge: Placement::Bottom,
+            start: px(500.),
+            start_size: px(200.),
+        };
+        assert_eq!(bottom.size(at(300., 450.), px(80.), px(600.)), px(250.));
+        assert_eq!(bottom.size(at(0., 700.), px(80.), px(600.)), px(80.));
+    }
+
+    #[test]
+    fn test_split_panel_toggle_collapsed() {
+        let mut panel = SplitPanelSize {
+            laid_out: px(240.),
+            ..Default::default()
+        };
+
+        assert!(panel.toggle_collapsed());
+        assert_eq!(panel.dragged, Some(px(0.)));
+        assert_eq!(panel.toggled_from, Some(px(240.)));
+
+        assert!(!panel.toggle_collapsed());
+        assert_eq!(panel.dragged, Some(px(240.)));
+        assert_eq!(panel.toggled_from, Some(px(0.)));
+        assert_eq!(panel.toggles, 2);
+    }
+
+    #[test]
+    fn test_split_panel_double_click_restores() {
+        let mut panel = SplitPanelSize {
+            laid_out: px(240.),
+            ..Default::default()
+        };
+
+        // Two double-clicks, each starting with a single mouse down
+        assert_eq!(panel.mouse_down(1), None);
+        assert_eq!(panel.mouse_down(2), Some(true));
+        assert_eq!(panel.dragged, Some(px(0.)));
+        assert_eq!(panel.mouse_down(1), None);
+        assert_eq!(panel.mouse_down(2), Some(false));
+        assert_eq!(panel.dragged, Some(px(240.)));
+
+        // A drag out of the collapsed panel keeps its new size instead
+        assert_eq!(panel.mouse_down(2), Some(true));
+        panel.drag_to(px(180.));
+        assert_eq!(panel.mouse_down(2), Some(true));
+        assert_eq!(panel.mouse_down(2), Some(false));
+        assert_eq!(panel.dragged, Some(px(180.)));
+    }
+
+    #[test]
+    fn test_split_panel_initial_change_resets_drag() {
+        let mut panel = SplitPanelSize::default();
+        panel.set_initial(Some(px(240.)));
+        panel.drag_to(px(300.));
+
+        // The same initial size keeps the dragged one
+        panel.set_initial(Some(px(240.)));
+        assert_eq!(panel.dragged, Some(px(300.)));
+
+        panel.set_initial(Some(px(200.)));
+        assert_eq!(panel.dragged, None);
+
+        // Collapsing, then passing a new size, drops the size to restore
+        panel.laid_out = px(200.);
+        assert!(panel.toggle_collapsed());
+        panel.set_initial(Some(px(260.)));
+        assert_eq!(panel.dragged, None);
+        assert!(panel.toggle_collapsed());
     }
 }
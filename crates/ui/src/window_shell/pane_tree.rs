@@ -0,0 +1,217 @@
+use std::rc::Rc;
+
+use gpui::{
+    AnyElement, App, AppContext as _, Axis, Context, DragMoveEvent, Empty, Hsla,
+    InteractiveElement as _, IntoElement, ParentElement as _, Pixels, Render, SharedString,
+    StatefulInteractiveElement as _, StyleRefinement, Styled, Window, div,
+    prelude::FluentBuilder as _, px, transparent_black,
+};
+
+use crate::{AxisExt as _, StyledExt as _};
+
+/// Callback for a pane resized by dragging a splitter, see [`super::WindowShell::on_pane_resize`].
+pub(crate) type PaneResizeHandler = Rc<dyn Fn(&[usize], Pixels, &mut Window, &mut App)>;
+
+/// A recursive tree of panes, rendered by [`super::WindowLayoutMode::Panes`].
+///
+/// Leaves hold any element, splits lay their children out side by side
+/// (`horizontal`) or stacked (`vertical`) with a draggable splitter between
+/// each pair of adjacent panes. Splits can nest to any depth.
+///
+/// ```ignore
+/// PaneTree::horizontal([
+///     PaneTree::leaf(explorer).size(px(240.)),
+///     PaneTree::vertical([
+///         PaneTree::leaf(editor),
+///         PaneTree::leaf(terminal).size(px(200.)),
+///     ]),
+/// ])
+/// ```
+pub struct PaneTree {
+    size: Option<Pixels>,
+    kind: PaneKind,
+}
+
+enum PaneKind {
+    Leaf(AnyElement),
+    Split { axis: Axis, children: Vec<PaneTree> },
+}
+
+impl PaneTree {
+    /// Create a leaf pane.
+    pub fn leaf(element: impl IntoElement) -> Self {
+        Self {
+            size: None,
+            kind: PaneKind::Leaf(element.into_any_element()),
+        }
+    }
+
+    /// Create a split laying out `children` side by side, left to right.
+    pub fn horizontal(children: impl IntoIterator<Item = PaneTree>) -> Self {
+        Self::split(Axis::Horizontal, children)
+    }
+
+    /// Create a split stacking `children` top to bottom.
+    pub fn vertical(children: impl IntoIterator<Item = PaneTree>) -> Self {
+        Self::split(Axis::Vertical, children)
+    }
+
+    fn split(axis: Axis, children: impl IntoIterator<Item = PaneTree>) -> Self {
+        Self {
+            size: None,
+            kind: PaneKind::Split {
+                axis,
+                children: children.into_iter().collect(),
+            },
+        }
+    }
+
+    /// Set the extent of this pane along its parent split: the width in a
+    /// horizontal split, the height in a vertical one.
+    ///
+    /// Panes without a size share the remaining space.
+    pub fn size(mut self, size: impl Into<Pixels>) -> Self {
+        self.size = Some(size.into());
+        self
+    }
+
+    pub(crate) fn render(
+        self,
+        path: Vec<usize>,
+        splitter: &PaneSplitterStyle,
+        on_resize: Option<PaneResizeHandler>,
+    ) -> AnyElement {
+        let (axis, children) = match self.kind {
+            PaneKind::Leaf(element) => return element,
+            PaneKind::Split { axis, children } => (axis, children),
+        };
+
+        let last_ix = children.len().saturating_sub(1);
+        let mut elements = Vec::with_capacity(children.len() * 2);
+        for (ix, child) in children.into_iter().enumerate() {
+            let mut child_path = path.clone();
+            child_path.push(ix);
+            elements.push(Self::render_pane(
+                child, child_path, axis, splitter, &on_resize,
+            ));
+            if ix < last_ix {
+                elements.push(Self::render_splitter(&path, ix, axis, splitter).into_any_element());
+            }
+        }
+
+        div()
+            .id(SharedString::from(format!(
+                "window-shell-panes-{}",
+                path_key(&path)
+            )))
+            .size_full()
+            .flex()
+            .when(axis.is_horizontal(), |el| el.flex_row())
+            .when(axis.is_vertical(), |el| el.flex_col())
+            .overflow_hidden()
+            .children(elements)
+            .into_any_element()
+    }
+
+    /// Render a child of a split, resizing it while the splitter after it is dragged.
+    fn render_pane(
+        pane: PaneTree,
+        path: Vec<usize>,
+        axis: Axis,
+        splitter: &PaneSplitterStyle,
+        on_resize: &Option<PaneResizeHandler>,
+    ) -> AnyElement {
+        let size = pane.size;
+        let drag_path = path.clone();
+        let on_drag_resize = on_resize.clone();
+
+        div()
+            .id(SharedString::from(format!(
+                "window-shell-pane-{}",
+                path_key(&path)
+            )))
+            .relative()
+            .overflow_hidden()
+            .min_w_0()
+            .min_h_0()
+            .map(|el| match size {
+                Some(size) if axis.is_horizontal() => el.flex_shrink_0().w(size).h_full(),
+                Some(size) => el.flex_shrink_0().h(size).w_full(),
+                None => el.flex_1(),
+            })
+            .when_some(on_drag_resize, |el, on_resize| {
+                el.on_drag_move(move |event: &DragMoveEvent<PaneSplitterDrag>, window, cx| {
+                    // The splitter after a pane has the pane's own path
+                    if event.drag(cx).0 != drag_path {
+                        return;
+                    }
+
+                    let position = event.event.position;
+                    let extent = if axis.is_horizontal() {
+                        position.x - event.bounds.left()
+                    } else {
+                        position.y - event.bounds.top()
+                    };
+                    on_resize(&drag_path, extent.max(px(0.)), window, cx);
+                })
+            })
+            .child(pane.render(path, splitter, on_resize.clone()))
+            .into_any_element()
+    }
+
+    fn render_splitter(
+        path: &[usize],
+        ix: usize,
+        axis: Axis,
+        splitter: &PaneSplitterStyle,
+    ) -> impl IntoElement {
+        let mut pane_path = path.to_vec();
+        pane_path.push(ix);
+        let hover_bg = splitter.hover_bg;
+
+        div()
+            .id(SharedString::from(format!(
+                "window-shell-pane-splitter-{}",
+                path_key(&pane_path)
+            )))
+            .flex_shrink_0()
+            .map(|el| {
+                if axis.is_horizontal() {
+                    el.w(splitter.width).h_full().cursor_col_resize()
+                } else {
+                    el.h(splitter.width).w_full().cursor_row_resize()
+                }
+            })
+            .bg(transparent_black())
+            .hover(move |s| s.bg(hover_bg))
+            .refine_style(&splitter.style)
+            .on_drag(PaneSplitterDrag(pane_path), |drag, _, _, cx| {
+                cx.stop_propagation();
+                cx.new(|_| drag.clone())
+            })
+    }
+}
+
+/// Splitter appearance shared by every split of a [`PaneTree`].
+pub(crate) struct PaneSplitterStyle {
+    pub(crate) width: Pixels,
+    pub(crate) style: StyleRefinement,
+    pub(crate) hover_bg: Hsla,
+}
+
+/// Drag payload of a pane splitter, holding the path of the pane before it.
+#[derive(Clone)]
+struct PaneSplitterDrag(Vec<usize>);
+
+impl Render for PaneSplitterDrag {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        Empty
+    }
+}
+
+fn path_key(path: &[usize]) -> String {
+    path.iter()
+        .map(usize::to_string)
+        .collect::<Vec<_>>()
+        .join("-")
+}